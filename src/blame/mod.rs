@@ -0,0 +1,209 @@
+// Author: kelexine (https://github.com/kelexine)
+// blame/mod.rs — Per-author ownership of code lines via git blame
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::counter::{self, LineKind, ScanConfig};
+use crate::git;
+use crate::models::{FileInfo, ScanResult};
+
+/// Author git blame reports for lines that are not committed yet.
+pub const UNCOMMITTED: &str = "Not Committed Yet";
+
+/// Code lines per author for one file, directory, extension or the whole scan.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Ownership {
+    /// Code lines attributed to any author.
+    pub lines: usize,
+    pub authors: BTreeMap<String, usize>,
+    /// Fewest authors who together own at least half of `lines`.
+    pub bus_factor: usize,
+}
+
+impl Ownership {
+    fn add_lines(&mut self, author: &str, lines: usize) {
+        if lines == 0 {
+            return;
+        }
+        *self.authors.entry(author.to_string()).or_default() += lines;
+        self.lines += lines;
+    }
+
+    fn merge(&mut self, other: &Ownership) {
+        for (author, &lines) in &other.authors {
+            self.add_lines(author, lines);
+        }
+    }
+
+    /// Authors ordered by lines owned, largest first (ties by name).
+    pub fn ranked(&self) -> Vec<(&str, usize)> {
+        let mut ranked: Vec<_> = self.authors.iter().map(|(a, &n)| (a.as_str(), n)).collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        ranked
+    }
+
+    /// `lines` as a fraction of all owned lines, between 0.0 and 1.0.
+    pub fn share(&self, lines: usize) -> f64 {
+        if self.lines == 0 {
+            0.0
+        } else {
+            lines as f64 / self.lines as f64
+        }
+    }
+
+    fn finish(&mut self) {
+        let mut owned = 0;
+        let mut bus_factor = 0;
+        for (_, lines) in self.ranked() {
+            if owned * 2 >= self.lines {
+                break;
+            }
+            owned += lines;
+            bus_factor += 1;
+        }
+        self.bus_factor = bus_factor;
+    }
+}
+
+/// Ownership aggregated at every level of the scan.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct BlameReport {
+    pub total: Ownership,
+    pub files: BTreeMap<PathBuf, Ownership>,
+    /// Every directory below the scan root, including all files beneath it.
+    pub directories: BTreeMap<PathBuf, Ownership>,
+    /// Keyed like `Breakdown`.
+    pub extensions: BTreeMap<String, Ownership>,
+}
+
+/// Blame every text file in `result`, attributing each code line (as counted
+/// by the scan, so comments and blanks are excluded) to its author.
+///
+/// Files git cannot blame, such as untracked ones, are attributed to
+/// [`UNCOMMITTED`] as a whole.
+pub fn run_blame(result: &ScanResult, config: &ScanConfig) -> Result<BlameReport> {
+    if !config.is_git_repo {
+        bail!(
+            "--blame needs a git repository: {}",
+            config.target_dir.display()
+        );
+    }
+
+    let blame_one = |fi: &&FileInfo| (fi.path.clone(), blame_file(fi, config));
+    let text_files: Vec<_> = result.files.iter().filter(|f| !f.is_binary).collect();
+    let files: BTreeMap<PathBuf, Ownership> = if config.parallel {
+        text_files.par_iter().map(blame_one).collect()
+    } else {
+        text_files.iter().map(blame_one).collect()
+    };
+
+    let mut report = BlameReport::default();
+    for fi in text_files {
+        let own = &files[&fi.path];
+        report.total.merge(own);
+        report
+            .extensions
+            .entry(fi.breakdown_key())
+            .or_default()
+            .merge(own);
+
+        let mut dir = fi.path.parent();
+        while let Some(d) = dir
+            && d.starts_with(&result.root)
+            && d != result.root
+        {
+            report
+                .directories
+                .entry(d.to_path_buf())
+                .or_default()
+                .merge(own);
+            dir = d.parent();
+        }
+    }
+    report.files = files;
+
+    report.total.finish();
+    report
+        .files
+        .values_mut()
+        .chain(report.directories.values_mut())
+        .chain(report.extensions.values_mut())
+        .for_each(Ownership::finish);
+    Ok(report)
+}
+
+fn blame_file(fi: &FileInfo, config: &ScanConfig) -> Ownership {
+    let mut own = Ownership::default();
+    let rel = fi.path.strip_prefix(&config.target_dir).unwrap_or(&fi.path);
+
+    match git::blame(&config.target_dir, config.rev.as_deref(), rel) {
+        Ok(lines) => attribute(&mut own, &fi.path, &lines),
+        Err(_) => own.add_lines(UNCOMMITTED, fi.code),
+    }
+    own
+}
+
+/// Credit the code lines among `lines` to their authors.
+fn attribute(own: &mut Ownership, path: &Path, lines: &[git::BlameLine]) {
+    let content: String = lines.iter().map(|l| format!("{}\n", l.content)).collect();
+    let kinds = counter::classify_lines(path, &content);
+    for (line, kind) in lines.iter().zip(kinds) {
+        if matches!(kind, LineKind::Code | LineKind::Mixed) {
+            own.add_lines(&line.author, 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blame_line(author: &str, content: &str) -> git::BlameLine {
+        git::BlameLine {
+            author: author.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_attribute_counts_code_lines_only() {
+        let lines = [
+            blame_line("ada", "// header"),
+            blame_line("ada", "fn a() {"),
+            blame_line("bob", ""),
+            blame_line("bob", "    b();"),
+            blame_line("bob", "}"),
+        ];
+        let mut own = Ownership::default();
+        attribute(&mut own, Path::new("x.rs"), &lines);
+        assert_eq!(own.lines, 3);
+        assert_eq!(own.authors["ada"], 1);
+        assert_eq!(own.authors["bob"], 2);
+    }
+
+    #[test]
+    fn test_bus_factor() {
+        let mut own = Ownership::default();
+        own.add_lines("a", 40);
+        own.add_lines("b", 30);
+        own.add_lines("c", 20);
+        own.add_lines("d", 10);
+        own.finish();
+        assert_eq!(own.bus_factor, 2);
+        assert_eq!(own.ranked()[0], ("a", 40));
+
+        let mut solo = Ownership::default();
+        solo.add_lines("a", 5);
+        solo.finish();
+        assert_eq!(solo.bus_factor, 1);
+
+        let mut empty = Ownership::default();
+        empty.finish();
+        assert_eq!(empty.bus_factor, 0);
+    }
+}
@@ -0,0 +1,228 @@
+// Author: kelexine (https://github.com/kelexine)
+// cache/mod.rs — Persistent per-project scan cache for incremental re-scans
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::FileInfo;
+
+/// Layout and meaning of cached `FileInfo`s. Bump whenever counting or
+/// extraction changes what a file's result would be, so stale entries written
+/// by an earlier build of the same version are not served.
+pub const CACHE_SCHEMA: u32 = 7;
+
+/// `dirs::cache_dir()/loc-rs`, where the CLI keeps one cache file per scanned directory.
+pub fn default_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("loc-rs"))
+}
+
+/// Size and modification time of a file on disk. A cached result is reused
+/// only while both are unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub size: u64,
+    pub mtime_secs: u64,
+    pub mtime_nanos: u32,
+}
+
+impl Fingerprint {
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = path.metadata().ok()?;
+        let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: meta.len(),
+            mtime_secs: mtime.as_secs(),
+            mtime_nanos: mtime.subsec_nanos(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFile {
+    fingerprint: Fingerprint,
+    info: FileInfo,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheData {
+    #[serde(default)]
+    schema: u32,
+    version: String,
+    settings: String,
+    /// Working-tree files by absolute path.
+    files: HashMap<PathBuf, CachedFile>,
+    /// Results for `--rev` scans by git blob id and language (see
+    /// [`ScanCache::get_blob`]); paths are re-stamped on use.
+    blobs: HashMap<String, FileInfo>,
+}
+
+/// Cached `FileInfo`s of one scan root.
+///
+/// The whole cache is discarded when [`CACHE_SCHEMA`], the loc-rs version or
+/// the settings that shape a `FileInfo` (function extraction and any
+/// query-driven extractors) differ from the run that wrote it.
+pub struct ScanCache {
+    path: PathBuf,
+    previous: CacheData,
+    current: CacheData,
+}
+
+impl ScanCache {
+    /// Load the cache for `root` from `dir`, written under the same
+    /// `settings`. A missing, unreadable or outdated cache yields an empty one.
+    pub fn load(dir: &Path, root: &Path, settings: &str) -> Self {
+        let path = dir.join(format!(
+            "scan-{:016x}.json",
+            fnv1a(root.as_os_str().as_encoded_bytes())
+        ));
+        let current = CacheData {
+            schema: CACHE_SCHEMA,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            settings: settings.to_string(),
+            ..Default::default()
+        };
+
+        let previous = File::open(&path)
+            .ok()
+            .and_then(|f| serde_json::from_reader::<_, CacheData>(BufReader::new(f)).ok())
+            .filter(|old| {
+                old.schema == current.schema
+                    && old.version == current.version
+                    && old.settings == current.settings
+            })
+            .unwrap_or_default();
+
+        Self {
+            path,
+            previous,
+            current,
+        }
+    }
+
+    /// The cached result for `path`, if its fingerprint is unchanged.
+    pub fn get_file(&self, path: &Path, fingerprint: &Fingerprint) -> Option<&FileInfo> {
+        self.previous
+            .files
+            .get(path)
+            .filter(|c| c.fingerprint == *fingerprint)
+            .map(|c| &c.info)
+    }
+
+    /// The cached result for blob `oid` counted as `language`, reported under
+    /// `path`. The same content is counted differently under another
+    /// extension, or as a `.h` header parsed with the other grammar.
+    pub fn get_blob(&self, oid: &str, language: &str, path: &Path) -> Option<FileInfo> {
+        self.previous
+            .blobs
+            .get(&blob_key(oid, language))
+            .map(|fi| FileInfo {
+                path: path.to_path_buf(),
+                ..fi.clone()
+            })
+    }
+
+    pub fn put_file(&mut self, fingerprint: Fingerprint, info: FileInfo) {
+        self.current
+            .files
+            .insert(info.path.clone(), CachedFile { fingerprint, info });
+    }
+
+    pub fn put_blob(&mut self, oid: &str, language: &str, info: FileInfo) {
+        self.current.blobs.insert(blob_key(oid, language), info);
+    }
+
+    /// Write the cache back. Entries not seen in this run are kept while
+    /// their file still exists, so a filtered scan (`-t`) does not evict the rest.
+    pub fn save(mut self) -> Result<()> {
+        for (path, cached) in self.previous.files {
+            if !self.current.files.contains_key(&path) && path.exists() {
+                self.current.files.insert(path, cached);
+            }
+        }
+        for (key, info) in self.previous.blobs {
+            self.current.blobs.entry(key).or_insert(info);
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Cannot create {}", parent.display()))?;
+        }
+        // Write to a temporary file first so an interrupted run never leaves a torn cache
+        let tmp = self.path.with_extension("json.tmp");
+        let f = File::create(&tmp).with_context(|| format!("Cannot create {}", tmp.display()))?;
+        let mut writer = BufWriter::new(f);
+        serde_json::to_writer(&mut writer, &self.current)
+            .with_context(|| "Failed to serialize scan cache")?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Cannot write {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+fn blob_key(oid: &str, language: &str) -> String {
+    format!("{}:{}", oid, language)
+}
+
+/// FNV-1a, used for cache file names because it is stable across Rust releases.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cache_roundtrip_and_invalidation() {
+        let cache_dir = tempdir().unwrap();
+        let root = Path::new("/project");
+        let fp = Fingerprint {
+            size: 10,
+            mtime_secs: 1,
+            mtime_nanos: 2,
+        };
+        let fi = FileInfo::new(root.join("a.rs"), 3, 2, 1, 0, false, None);
+
+        let mut cache = ScanCache::load(cache_dir.path(), root, "functions=true");
+        assert!(cache.get_file(&fi.path, &fp).is_none());
+        cache.put_file(fp, fi.clone());
+        cache.put_blob("abc123", "rs", fi.clone());
+        cache.save().unwrap();
+
+        let cache = ScanCache::load(cache_dir.path(), root, "functions=true");
+        assert_eq!(cache.get_file(&fi.path, &fp).unwrap().code, 2);
+        let touched = Fingerprint {
+            mtime_nanos: 3,
+            ..fp
+        };
+        assert!(cache.get_file(&fi.path, &touched).is_none());
+        let blob = cache.get_blob("abc123", "rs", &root.join("b.rs")).unwrap();
+        assert_eq!(blob.path, root.join("b.rs"));
+        // The same content under another language is counted afresh
+        assert!(cache.get_blob("abc123", "py", &root.join("b.py")).is_none());
+
+        // Different extraction settings start from scratch
+        let cache = ScanCache::load(cache_dir.path(), root, "functions=false");
+        assert!(cache.get_blob("abc123", "rs", &fi.path).is_none());
+
+        // So does a cache written before the current schema
+        let cache = ScanCache::load(cache_dir.path(), root, "functions=true");
+        let mut old: serde_json::Value =
+            serde_json::from_slice(&fs::read(&cache.path).unwrap()).unwrap();
+        old.as_object_mut().unwrap().remove("schema");
+        fs::write(&cache.path, old.to_string()).unwrap();
+        let cache = ScanCache::load(cache_dir.path(), root, "functions=true");
+        assert!(cache.get_file(&fi.path, &fp).is_none());
+    }
+}
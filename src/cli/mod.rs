@@ -3,6 +3,8 @@
 
 use clap::Parser;
 
+use crate::thresholds::Thresholds;
+
 /// LOC — Advanced Lines of Code counter
 ///
 /// A fast, feature-rich LOC tool with function extraction, git integration,
@@ -27,6 +29,13 @@ EXAMPLES:
   loc -e stats.csv -f          Export CSV with function data
   loc --warn-size 500          Warn about files > 500 lines
   loc --git-dates              Use git log for last-modified dates
+  loc --compare base.json      Show what changed since a previous JSON export
+  loc --max-complexity 15      Fail (exit 2) if any function is more complex
+  loc --hotspots=20            Top 20 files that change often and are complex
+  loc --blame -e owners.csv    Per-author ownership and bus factor
+  loc --rev v1.0.0 -d          Count the v1.0.0 tag without checking it out
+  loc --history --history-interval 1m -e growth.html
+                               Chart monthly LOC across git history
   loc src/ -d -t rust -f -e out.json
 
 SUPPORTED LANGUAGES:
@@ -63,7 +72,7 @@ pub struct Args {
     #[arg(short = 't', long = "type", value_name = "LANG", num_args = 1..)]
     pub file_types: Vec<String>,
 
-    /// Export results to file (.json, .jsonl, or .csv)
+    /// Export results to file (.json, .jsonl, .csv, .html, or .sarif)
     #[arg(short = 'e', long = "export", value_name = "FILE")]
     pub export: Option<String>,
 
@@ -79,6 +88,14 @@ pub struct Args {
     #[arg(long = "no-parallel")]
     pub no_parallel: bool,
 
+    /// Reuse results for unchanged files from the on-disk scan cache
+    #[arg(long = "cache", conflicts_with = "no_cache")]
+    pub cache: bool,
+
+    /// Ignore the scan cache, even if `cache = true` is set in config.toml
+    #[arg(long = "no-cache")]
+    pub no_cache: bool,
+
     /// Include hidden files and directories (skipped by default)
     #[arg(short = 'H', long = "include-hidden")]
     pub include_hidden: bool,
@@ -86,4 +103,86 @@ pub struct Args {
     /// Show recursive directory tree (off by default)
     #[arg(long = "tree")]
     pub tree: bool,
+
+    /// Compare against a previous JSON export and show per-file/per-extension deltas
+    #[arg(long = "compare", value_name = "BASELINE")]
+    pub compare: Option<String>,
+
+    /// Attribute code lines to authors with `git blame` (ownership and bus factor)
+    #[arg(long = "blame")]
+    pub blame: bool,
+
+    /// Scan a git commit, tag or branch from the object database instead of the working tree
+    #[arg(long = "rev", value_name = "REV")]
+    pub rev: Option<String>,
+
+    /// Rank files by git churn × complexity and show the top N (`--hotspots=N`, default 10; enables -f)
+    #[arg(
+        long = "hotspots",
+        value_name = "N",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "10"
+    )]
+    pub hotspots: Option<usize>,
+
+    /// Walk git history and report LOC per sampled commit instead of scanning the working tree
+    #[arg(long = "history")]
+    pub history: bool,
+
+    /// With --history: measure every Nth first-parent commit
+    #[arg(
+        long = "history-every",
+        value_name = "N",
+        default_value_t = 1,
+        requires = "history"
+    )]
+    pub history_every: usize,
+
+    /// With --history: at most one commit per interval (e.g. 7d, 2w, 1m, 1y)
+    #[arg(
+        long = "history-interval",
+        value_name = "INTERVAL",
+        requires = "history",
+        conflicts_with = "history_every"
+    )]
+    pub history_interval: Option<String>,
+
+    /// Gate: fail if any file exceeds this many lines
+    #[arg(long = "max-file-lines", value_name = "LINES")]
+    pub max_file_lines: Option<usize>,
+
+    /// Gate: fail if any function exceeds this many lines (enables -f)
+    #[arg(long = "max-function-lines", value_name = "LINES")]
+    pub max_function_lines: Option<usize>,
+
+    /// Gate: fail if any function exceeds this cyclomatic complexity (enables -f)
+    #[arg(long = "max-complexity", value_name = "N")]
+    pub max_complexity: Option<u32>,
+
+    /// Gate: fail if any function exceeds this cognitive complexity (enables -f)
+    #[arg(long = "max-cognitive-complexity", value_name = "N")]
+    pub max_cognitive_complexity: Option<u32>,
+
+    /// Gate: fail if a file's comment/(code+comment) ratio is below this (0.0–1.0)
+    #[arg(long = "min-comment-ratio", value_name = "RATIO")]
+    pub min_comment_ratio: Option<f64>,
+
+    /// Gate: fail if the code under a directory, subdirectories included, exceeds this many lines
+    #[arg(long = "max-dir-lines", value_name = "LINES")]
+    pub max_dir_lines: Option<usize>,
+}
+
+impl Args {
+    /// Quality gates given on the command line (unset ones fall back to `config.toml`).
+    pub fn thresholds(&self) -> Thresholds {
+        Thresholds {
+            max_file_lines: self.max_file_lines,
+            max_function_lines: self.max_function_lines,
+            max_complexity: self.max_complexity,
+            max_cognitive_complexity: self.max_cognitive_complexity,
+            min_comment_ratio: self.min_comment_ratio,
+            max_dir_lines: self.max_dir_lines,
+        }
+    }
 }
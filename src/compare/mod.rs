@@ -0,0 +1,389 @@
+// Author: kelexine (https://github.com/kelexine)
+// compare/mod.rs — Baseline loading and scan-to-scan deltas
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Breakdown, FileInfo, ScanResult};
+
+/// Line, function and complexity totals for one file, extension or scan.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Metrics {
+    pub files: usize,
+    pub lines: usize,
+    pub code: usize,
+    pub comment: usize,
+    pub blank: usize,
+    pub functions: usize,
+    pub complexity: u64,
+}
+
+impl Metrics {
+    pub fn of_file(fi: &FileInfo) -> Self {
+        Self {
+            files: 1,
+            lines: fi.lines,
+            code: fi.code,
+            comment: fi.comment,
+            blank: fi.blank,
+            functions: fi.function_count(),
+            complexity: fi.total_complexity() as u64,
+        }
+    }
+
+    pub fn add(&mut self, other: &Metrics) {
+        self.files += other.files;
+        self.lines += other.lines;
+        self.code += other.code;
+        self.comment += other.comment;
+        self.blank += other.blank;
+        self.functions += other.functions;
+        self.complexity += other.complexity;
+    }
+}
+
+/// Signed difference `after - before` of two `Metrics`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MetricsDelta {
+    pub files: i64,
+    pub lines: i64,
+    pub code: i64,
+    pub comment: i64,
+    pub blank: i64,
+    pub functions: i64,
+    pub complexity: i64,
+}
+
+impl MetricsDelta {
+    pub fn between(before: &Metrics, after: &Metrics) -> Self {
+        let d = |b: u64, a: u64| a as i64 - b as i64;
+        Self {
+            files: d(before.files as u64, after.files as u64),
+            lines: d(before.lines as u64, after.lines as u64),
+            code: d(before.code as u64, after.code as u64),
+            comment: d(before.comment as u64, after.comment as u64),
+            blank: d(before.blank as u64, after.blank as u64),
+            functions: d(before.functions as u64, after.functions as u64),
+            complexity: d(before.complexity, after.complexity),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+    Unchanged,
+}
+
+/// Per-file comparison entry. `path` is relative to the scan root.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDelta {
+    pub path: PathBuf,
+    pub kind: ChangeKind,
+    pub before: Metrics,
+    pub after: Metrics,
+    pub delta: MetricsDelta,
+}
+
+/// Per-extension comparison entry, keyed like `Breakdown`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtensionDelta {
+    pub extension: String,
+    pub before: Metrics,
+    pub after: Metrics,
+    pub delta: MetricsDelta,
+}
+
+/// Result of comparing the current scan against a baseline.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanDiff {
+    pub baseline: PathBuf,
+    pub total_before: Metrics,
+    pub total_after: Metrics,
+    pub total_delta: MetricsDelta,
+    /// Sorted by extension name.
+    pub extensions: Vec<ExtensionDelta>,
+    /// Sorted by path; unchanged files are omitted.
+    pub files: Vec<FileDelta>,
+}
+
+impl ScanDiff {
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.files.iter().filter(|f| f.kind == kind).count()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Baseline loading
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct BaselineReport {
+    #[serde(default)]
+    metadata: BaselineMetadata,
+    #[serde(default)]
+    breakdown: Breakdown,
+    files: Vec<FileInfo>,
+}
+
+#[derive(Deserialize, Default)]
+struct BaselineMetadata {
+    root: Option<PathBuf>,
+    #[serde(default)]
+    function_extraction_enabled: bool,
+}
+
+/// A previously exported JSON report, parsed back into a `ScanResult`.
+pub struct Baseline {
+    pub result: ScanResult,
+    pub function_extraction_enabled: bool,
+}
+
+/// Load a report written by `export_json` (`loc -e baseline.json`).
+///
+/// Reports written before the `root` metadata field existed fall back to the
+/// deepest directory shared by all file paths.
+pub fn load_baseline(path: &Path) -> Result<Baseline> {
+    let f = File::open(path).with_context(|| format!("Cannot open {}", path.display()))?;
+    let report: BaselineReport = serde_json::from_reader(BufReader::new(f))
+        .with_context(|| format!("{} is not a loc JSON export", path.display()))?;
+
+    let root = report
+        .metadata
+        .root
+        .unwrap_or_else(|| common_ancestor(&report.files));
+
+    Ok(Baseline {
+        result: ScanResult {
+            root,
+            files: report.files,
+            breakdown: report.breakdown,
+        },
+        function_extraction_enabled: report.metadata.function_extraction_enabled,
+    })
+}
+
+fn common_ancestor(files: &[FileInfo]) -> PathBuf {
+    let mut iter = files.iter().filter_map(|f| f.path.parent());
+    let Some(first) = iter.next() else {
+        return PathBuf::new();
+    };
+    let mut common = first.to_path_buf();
+    for parent in iter {
+        while !parent.starts_with(&common) {
+            if !common.pop() {
+                return PathBuf::new();
+            }
+        }
+    }
+    common
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Comparison
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn relative_map(result: &ScanResult) -> HashMap<PathBuf, &FileInfo> {
+    result
+        .files
+        .iter()
+        .filter(|f| !f.is_binary)
+        .map(|f| {
+            let rel = f.path.strip_prefix(&result.root).unwrap_or(&f.path);
+            (rel.to_path_buf(), f)
+        })
+        .collect()
+}
+
+/// Compare `current` against `baseline`, matching files by path relative to each scan root.
+pub fn compare(baseline: &Baseline, baseline_path: &Path, current: &ScanResult) -> ScanDiff {
+    let before = relative_map(&baseline.result);
+    let after = relative_map(current);
+
+    let paths: BTreeSet<&PathBuf> = before.keys().chain(after.keys()).collect();
+
+    let mut files = Vec::new();
+    let mut ext_before: BTreeMap<String, Metrics> = BTreeMap::new();
+    let mut ext_after: BTreeMap<String, Metrics> = BTreeMap::new();
+    let mut total_before = Metrics::default();
+    let mut total_after = Metrics::default();
+
+    for path in paths {
+        let b = before.get(path).copied();
+        let a = after.get(path).copied();
+
+        let mb = b.map(Metrics::of_file).unwrap_or_default();
+        let ma = a.map(Metrics::of_file).unwrap_or_default();
+
+        if let Some(fi) = b {
+            ext_before.entry(fi.breakdown_key()).or_default().add(&mb);
+            total_before.add(&mb);
+        }
+        if let Some(fi) = a {
+            ext_after.entry(fi.breakdown_key()).or_default().add(&ma);
+            total_after.add(&ma);
+        }
+
+        let delta = MetricsDelta::between(&mb, &ma);
+        let kind = match (b, a) {
+            (None, Some(_)) => ChangeKind::Added,
+            (Some(_), None) => ChangeKind::Removed,
+            _ if delta.is_zero() => ChangeKind::Unchanged,
+            _ => ChangeKind::Modified,
+        };
+
+        if kind != ChangeKind::Unchanged {
+            files.push(FileDelta {
+                path: path.clone(),
+                kind,
+                before: mb,
+                after: ma,
+                delta,
+            });
+        }
+    }
+
+    let ext_keys: BTreeSet<&String> = ext_before.keys().chain(ext_after.keys()).collect();
+    let extensions = ext_keys
+        .into_iter()
+        .map(|ext| {
+            let b = ext_before.get(ext).copied().unwrap_or_default();
+            let a = ext_after.get(ext).copied().unwrap_or_default();
+            ExtensionDelta {
+                extension: ext.clone(),
+                before: b,
+                after: a,
+                delta: MetricsDelta::between(&b, &a),
+            }
+        })
+        .collect();
+
+    ScanDiff {
+        baseline: baseline_path.to_path_buf(),
+        total_before,
+        total_after,
+        total_delta: MetricsDelta::between(&total_before, &total_after),
+        extensions,
+        files,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FunctionInfo;
+
+    fn file(path: &str, lines: usize, code: usize, complexities: &[u32]) -> FileInfo {
+        let functions = complexities
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| FunctionInfo {
+                name: format!("f{}", i),
+                parent: None,
+                qualified_name: String::new(),
+                line_start: 1,
+                line_end: 2,
+                parameters: vec![],
+                is_async: false,
+                is_method: false,
+                is_class: false,
+                docstring: None,
+                decorators: vec![],
+                complexity: c,
+                cognitive_complexity: 0,
+                halstead: Default::default(),
+                max_nesting: 0,
+            })
+            .collect();
+        FileInfo::new(
+            PathBuf::from(path),
+            lines,
+            code,
+            0,
+            lines - code,
+            false,
+            None,
+        )
+        .with_functions(functions)
+    }
+
+    fn result(root: &str, files: Vec<FileInfo>) -> ScanResult {
+        ScanResult {
+            root: PathBuf::from(root),
+            files,
+            breakdown: Breakdown::new(),
+        }
+    }
+
+    #[test]
+    fn test_compare_classifies_files_and_sums_deltas() {
+        let baseline = Baseline {
+            result: result(
+                "/old/checkout",
+                vec![
+                    file("/old/checkout/src/a.rs", 10, 8, &[2]),
+                    file("/old/checkout/src/gone.py", 5, 5, &[]),
+                    file("/old/checkout/src/same.rs", 3, 3, &[1]),
+                ],
+            ),
+            function_extraction_enabled: true,
+        };
+        let current = result(
+            "/new/checkout",
+            vec![
+                file("/new/checkout/src/a.rs", 14, 12, &[2, 3]),
+                file("/new/checkout/src/new.rs", 7, 6, &[1]),
+                file("/new/checkout/src/same.rs", 3, 3, &[1]),
+            ],
+        );
+
+        let diff = compare(&baseline, Path::new("base.json"), &current);
+
+        assert_eq!(diff.count(ChangeKind::Added), 1);
+        assert_eq!(diff.count(ChangeKind::Removed), 1);
+        assert_eq!(diff.count(ChangeKind::Modified), 1);
+        assert_eq!(diff.files.len(), 3, "unchanged files are omitted");
+
+        let a = diff
+            .files
+            .iter()
+            .find(|f| f.path == Path::new("src/a.rs"))
+            .unwrap();
+        assert_eq!(a.delta.lines, 4);
+        assert_eq!(a.delta.functions, 1);
+        assert_eq!(a.delta.complexity, 3);
+
+        assert_eq!(diff.total_delta.lines, 24 - 18);
+        assert_eq!(diff.total_delta.files, 0);
+
+        let py = diff
+            .extensions
+            .iter()
+            .find(|e| e.extension == "py")
+            .unwrap();
+        assert_eq!(py.delta.files, -1);
+        assert_eq!(py.delta.lines, -5);
+    }
+
+    #[test]
+    fn test_common_ancestor_fallback() {
+        let files = vec![
+            file("/repo/src/a.rs", 1, 1, &[]),
+            file("/repo/src/nested/b.rs", 1, 1, &[]),
+            file("/repo/tests/c.rs", 1, 1, &[]),
+        ];
+        assert_eq!(common_ancestor(&files), PathBuf::from("/repo"));
+    }
+}
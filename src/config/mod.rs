@@ -1,30 +1,73 @@
 // Author: kelexine (https://github.com/kelexine)
 // config/mod.rs — Global configuration loader
 
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
 use serde::Deserialize;
 
+use crate::extractors::{HeaderLanguage, tree_sitter::TreeSitterExtractor};
+use crate::thresholds::Thresholds;
+
 /// Represents the structure of the global `config.toml`.
 #[derive(Deserialize, Default, Debug)]
 pub struct GlobalConfig {
     pub warn_size: Option<usize>,
     pub default_types: Option<Vec<String>>,
     pub always_extract_functions: Option<bool>,
+    /// Use the persistent scan cache by default.
+    pub cache: Option<bool>,
+    /// Parse `.h` headers as `c` or `cpp` instead of deciding per project.
+    pub c_headers: Option<HeaderLanguage>,
+    /// Quality gates from the `[thresholds]` table.
+    #[serde(default)]
+    pub thresholds: Thresholds,
+    /// Query-driven extractors from `[[extractors]]` tables.
+    #[serde(default)]
+    pub extractors: Vec<QueryExtractorConfig>,
+}
+
+/// An `[[extractors]]` table: extract functions from `extensions` by running
+/// the tree-sitter `query` file against a grammar compiled into loc-rs.
+#[derive(Deserialize, Debug, Clone)]
+pub struct QueryExtractorConfig {
+    /// One of [`crate::extractors::tree_sitter::GRAMMARS`], e.g. `rust`.
+    pub grammar: String,
+    /// Extensions handled by this extractor instead of the built-in one.
+    pub extensions: Vec<String>,
+    /// The `.scm` query file, relative to the config directory unless absolute.
+    pub query: PathBuf,
+}
+
+impl QueryExtractorConfig {
+    /// Read the query file and compile it against the grammar.
+    pub fn load(&self) -> Result<TreeSitterExtractor> {
+        let path = match config_dir() {
+            Some(dir) if self.query.is_relative() => dir.join(&self.query),
+            _ => self.query.clone(),
+        };
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read query file {}", path.display()))?;
+        TreeSitterExtractor::for_grammar(&self.grammar, &source)
+            .with_context(|| format!("In query file {}", path.display()))
+    }
+}
+
+/// The `loc-rs` directory under the platform config directory.
+fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("loc-rs"))
 }
 
 impl GlobalConfig {
     /// Attempt to load the global configuration, returning a default instance if it fails or missing.
     pub fn load() -> Self {
-        if let Some(mut path) = dirs::config_dir() {
-            path.push("loc-rs");
-            path.push("config.toml");
-
-            if path.exists()
-                && let Ok(content) = std::fs::read_to_string(&path)
-            {
-                match toml::from_str(&content) {
-                    Ok(config) => return config,
-                    Err(e) => eprintln!("[WARNING] Failed to parse {}: {}", path.display(), e),
-                }
+        if let Some(path) = config_dir().map(|dir| dir.join("config.toml"))
+            && path.exists()
+            && let Ok(content) = std::fs::read_to_string(&path)
+        {
+            match toml::from_str(&content) {
+                Ok(config) => return config,
+                Err(e) => eprintln!("[WARNING] Failed to parse {}: {}", path.display(), e),
             }
         }
         Self::default()
@@ -41,6 +84,8 @@ mod tests {
         warn_size = 500
         default_types = ["rust", "python"]
         always_extract_functions = true
+        cache = true
+        c_headers = "c"
         "#;
         let config: GlobalConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(config.warn_size, Some(500));
@@ -49,6 +94,8 @@ mod tests {
             Some(vec!["rust".to_string(), "python".to_string()])
         );
         assert_eq!(config.always_extract_functions, Some(true));
+        assert_eq!(config.cache, Some(true));
+        assert_eq!(config.c_headers, Some(HeaderLanguage::C));
     }
 
     #[test]
@@ -58,5 +105,39 @@ mod tests {
         assert_eq!(config.warn_size, None);
         assert_eq!(config.default_types, None);
         assert_eq!(config.always_extract_functions, None);
+        assert!(config.thresholds.is_empty());
+    }
+
+    #[test]
+    fn test_parse_global_config_thresholds() {
+        let toml_str = r#"
+        [thresholds]
+        max_file_lines = 800
+        max_complexity = 12
+        min_comment_ratio = 0.05
+        "#;
+        let config: GlobalConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.thresholds.max_file_lines, Some(800));
+        assert_eq!(config.thresholds.max_complexity, Some(12));
+        assert_eq!(config.thresholds.min_comment_ratio, Some(0.05));
+        assert_eq!(config.thresholds.max_function_lines, None);
+    }
+
+    #[test]
+    fn test_parse_global_config_extractors() {
+        let toml_str = r#"
+        [[extractors]]
+        grammar = "rust"
+        extensions = ["rs"]
+        query = "queries/rust.scm"
+        "#;
+        let config: GlobalConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.extractors.len(), 1);
+        assert_eq!(config.extractors[0].grammar, "rust");
+        assert_eq!(config.extractors[0].extensions, vec!["rs"]);
+        assert_eq!(
+            config.extractors[0].query,
+            PathBuf::from("queries/rust.scm")
+        );
     }
 }
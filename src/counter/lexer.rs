@@ -0,0 +1,495 @@
+// Author: kelexine (https://github.com/kelexine)
+// counter/lexer.rs — Per-line lexer that tells code, comments and string literals apart
+
+use super::LineKind;
+use crate::language::{CommentSpec, StringSpec};
+
+/// Where the lexer is at the start of the next byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    Code,
+    /// Inside `depth` levels of block comments; `start` is the marker that
+    /// opens a nested level. `doc` marks a documentation block.
+    Block {
+        start: &'static str,
+        end: &'static str,
+        depth: usize,
+        doc: bool,
+    },
+    /// Inside a string literal; `doc` marks a docstring, which counts as documentation.
+    Str {
+        close: &'static str,
+        escapes: bool,
+        multiline: bool,
+        doc: bool,
+    },
+    /// Inside a Rust raw string closed by `"` and this many `#`.
+    Raw {
+        hashes: usize,
+    },
+    /// Inside a heredoc body, until a line starting with `tag`.
+    Heredoc {
+        tag: String,
+    },
+}
+
+/// Classifies lines one at a time, carrying block comments and multi-line
+/// strings across lines.
+pub(super) struct LineLexer<'a> {
+    spec: &'a CommentSpec,
+    state: State,
+    /// Heredoc opened on the current line; its body starts on the next one.
+    pending_heredoc: Option<String>,
+}
+
+impl<'a> LineLexer<'a> {
+    pub(super) fn new(spec: &'a CommentSpec) -> Self {
+        Self {
+            spec,
+            state: State::Code,
+            pending_heredoc: None,
+        }
+    }
+
+    /// Classify one line (without its line terminator).
+    pub(super) fn classify(&mut self, line: &str) -> LineKind {
+        if line.trim().is_empty() {
+            return match self.state {
+                State::Block { doc: true, .. } | State::Str { doc: true, .. } => LineKind::Doc,
+                State::Block { .. } => LineKind::Comment,
+                _ => LineKind::Blank,
+            };
+        }
+
+        let mut code = false;
+        let mut comment = false;
+        let mut doc = false;
+        let bytes = line.as_bytes();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let rest = &bytes[i..];
+            match self.state {
+                State::Code => {
+                    if rest[0].is_ascii_whitespace() {
+                        i += 1;
+                    } else if let Some((start, end)) = doc_block_start(self.spec.doc_multi, rest) {
+                        doc = true;
+                        self.state = State::Block {
+                            start: nest_marker(self.spec, start, end),
+                            end,
+                            depth: 1,
+                            doc: true,
+                        };
+                        i += start.len();
+                    } else if let Some(&(start, end)) = self
+                        .spec
+                        .multi
+                        .iter()
+                        .find(|(start, _)| rest.starts_with(start.as_bytes()))
+                    {
+                        comment = true;
+                        self.state = State::Block {
+                            start,
+                            end,
+                            depth: 1,
+                            doc: false,
+                        };
+                        i += start.len();
+                    } else if starts_with_any(rest, self.spec.doc_single) {
+                        doc = true;
+                        break;
+                    } else if starts_with_any(rest, self.spec.single) {
+                        comment = true;
+                        break;
+                    } else if let Some(len) =
+                        self.open_string(rest, i, bytes, !code && !comment && !doc)
+                    {
+                        if matches!(self.state, State::Str { doc: true, .. }) {
+                            doc = true;
+                        } else {
+                            code = true;
+                        }
+                        i += len;
+                    } else {
+                        code = true;
+                        i += 1;
+                    }
+                }
+                State::Block {
+                    start,
+                    end,
+                    depth,
+                    doc: in_doc,
+                } => {
+                    if in_doc {
+                        doc = true;
+                    } else {
+                        comment = true;
+                    }
+                    if self.spec.nested && rest.starts_with(start.as_bytes()) {
+                        self.state = State::Block {
+                            start,
+                            end,
+                            depth: depth + 1,
+                            doc: in_doc,
+                        };
+                        i += start.len();
+                    } else if rest.starts_with(end.as_bytes()) {
+                        self.state = if depth > 1 {
+                            State::Block {
+                                start,
+                                end,
+                                depth: depth - 1,
+                                doc: in_doc,
+                            }
+                        } else {
+                            State::Code
+                        };
+                        i += end.len();
+                    } else {
+                        i += 1;
+                    }
+                }
+                State::Str {
+                    close,
+                    escapes,
+                    doc: in_doc,
+                    ..
+                } => {
+                    let visible = !rest[0].is_ascii_whitespace();
+                    if escapes && rest[0] == b'\\' {
+                        i += 2;
+                    } else if rest.starts_with(close.as_bytes()) {
+                        self.state = State::Code;
+                        i += close.len();
+                    } else {
+                        i += 1;
+                    }
+                    if visible {
+                        if in_doc {
+                            doc = true;
+                        } else {
+                            code = true;
+                        }
+                    }
+                }
+                State::Raw { hashes } => {
+                    code = true;
+                    if rest[0] == b'"'
+                        && rest.len() > hashes
+                        && rest[1..=hashes].iter().all(|&b| b == b'#')
+                    {
+                        self.state = State::Code;
+                        i += 1 + hashes;
+                    } else {
+                        i += 1;
+                    }
+                }
+                State::Heredoc { ref tag } => {
+                    code = true;
+                    if starts_with_tag(line.trim_start(), tag) {
+                        self.state = State::Code;
+                    }
+                    break;
+                }
+            }
+        }
+
+        // Single-line literals end with their line, terminated or not
+        if matches!(
+            self.state,
+            State::Str {
+                multiline: false,
+                ..
+            }
+        ) {
+            self.state = State::Code;
+        }
+        if let Some(tag) = self.pending_heredoc.take()
+            && self.state == State::Code
+        {
+            self.state = State::Heredoc { tag };
+        }
+
+        match (code, comment || doc) {
+            (true, true) => LineKind::Mixed,
+            (false, true) if doc => LineKind::Doc,
+            (false, true) => LineKind::Comment,
+            _ => LineKind::Code,
+        }
+    }
+
+    /// Try to open a string literal at `rest` (`bytes[at..]`). Returns the
+    /// number of bytes consumed and updates the state.
+    fn open_string(
+        &mut self,
+        rest: &[u8],
+        at: usize,
+        bytes: &[u8],
+        line_start: bool,
+    ) -> Option<usize> {
+        let after_ident = at > 0 && is_ident_byte(bytes[at - 1]);
+        for string in self.spec.strings {
+            match *string {
+                StringSpec::Quoted {
+                    open,
+                    close,
+                    escapes,
+                    multiline,
+                } if rest.starts_with(open.as_bytes()) => {
+                    self.state = State::Str {
+                        close,
+                        escapes,
+                        multiline,
+                        doc: self.spec.docstrings && multiline && line_start,
+                    };
+                    return Some(open.len());
+                }
+                StringSpec::Char if rest[0] == b'\'' => {
+                    if let Some(len) = char_literal(rest) {
+                        return Some(len);
+                    }
+                }
+                StringSpec::RawHash if !after_ident => {
+                    let prefix = if rest.starts_with(b"br") {
+                        2
+                    } else if rest.starts_with(b"r") {
+                        1
+                    } else {
+                        continue;
+                    };
+                    let hashes = rest[prefix..].iter().take_while(|&&b| b == b'#').count();
+                    if rest.get(prefix + hashes) == Some(&b'"') {
+                        self.state = State::Raw { hashes };
+                        return Some(prefix + hashes + 1);
+                    }
+                }
+                StringSpec::Heredoc if rest.starts_with(b"<<") => {
+                    if let Some((len, tag)) = heredoc_tag(rest) {
+                        self.pending_heredoc = Some(tag);
+                        return Some(len);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// The documentation block opened at the start of `rest`, if any. A doc
+/// marker that closes straight away (`/**/`) is an ordinary empty comment.
+fn doc_block_start(
+    pairs: &[(&'static str, &'static str)],
+    rest: &[u8],
+) -> Option<(&'static str, &'static str)> {
+    pairs.iter().copied().find(|(start, end)| {
+        rest.starts_with(start.as_bytes()) && !rest[start.len() - 1..].starts_with(end.as_bytes())
+    })
+}
+
+/// The marker that nests inside a doc block: the ordinary block opener with
+/// the same terminator (`/*` inside `/** */`), or the doc opener itself.
+fn nest_marker(spec: &CommentSpec, start: &'static str, end: &'static str) -> &'static str {
+    spec.multi
+        .iter()
+        .find(|(_, e)| *e == end)
+        .map_or(start, |(s, _)| s)
+}
+
+fn starts_with_any(rest: &[u8], markers: &[&str]) -> bool {
+    markers.iter().any(|m| rest.starts_with(m.as_bytes()))
+}
+
+/// Length of a `'x'` / `'\n'` / `'\u{1F600}'` literal at the start of `rest`.
+fn char_literal(rest: &[u8]) -> Option<usize> {
+    if rest.get(1) == Some(&b'\\') {
+        let close = rest.iter().skip(3).take(10).position(|&b| b == b'\'')?;
+        return Some(close + 4);
+    }
+    // One (possibly multi-byte) character, then the closing quote
+    let width = match rest.get(1)? {
+        b'\'' => return None,
+        b if *b < 0x80 => 1,
+        b if *b >= 0xF0 => 4,
+        b if *b >= 0xE0 => 3,
+        _ => 2,
+    };
+    (rest.get(1 + width) == Some(&b'\'')).then_some(width + 2)
+}
+
+/// Parse `<<TAG`, `<<-TAG`, `<<~TAG`, `<<<TAG` and quoted variants at the
+/// start of `rest`, returning the bytes consumed and the tag.
+fn heredoc_tag(rest: &[u8]) -> Option<(usize, String)> {
+    let mut i = 2;
+    if matches!(rest.get(i), Some(b'<' | b'-' | b'~')) {
+        i += 1;
+    }
+    let quote = match rest.get(i) {
+        Some(&q @ (b'\'' | b'"')) => {
+            i += 1;
+            Some(q)
+        }
+        _ => None,
+    };
+    let start = i;
+    if !rest
+        .get(i)
+        .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_')
+    {
+        return None;
+    }
+    while rest.get(i).is_some_and(|&b| is_ident_byte(b)) {
+        i += 1;
+    }
+    let tag = String::from_utf8_lossy(&rest[start..i]).into_owned();
+    if let Some(q) = quote {
+        if rest.get(i) != Some(&q) {
+            return None;
+        }
+        i += 1;
+    }
+    Some((i, tag))
+}
+
+/// Whether `line` is a heredoc terminator: the tag, not followed by more of an identifier.
+fn starts_with_tag(line: &str, tag: &str) -> bool {
+    line.strip_prefix(tag)
+        .is_some_and(|after| !after.bytes().next().is_some_and(is_ident_byte))
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::COMMENT_REGISTRY;
+    use LineKind::*;
+
+    fn kinds(ext: &str, src: &str) -> Vec<LineKind> {
+        let mut lexer = LineLexer::new(&COMMENT_REGISTRY[ext]);
+        src.lines().map(|l| lexer.classify(l)).collect()
+    }
+
+    #[test]
+    fn test_markers_inside_strings_are_code() {
+        assert_eq!(
+            kinds(
+                ".js",
+                "let url = \"http://x\"; /* start\nstill comment */\nlet s = '/*';"
+            ),
+            [Mixed, Comment, Code]
+        );
+        assert_eq!(kinds(".rs", "x(); // trailing\n// whole"), [Mixed, Comment]);
+        assert_eq!(kinds(".c", "char c = '\"'; // q"), [Mixed]);
+    }
+
+    #[test]
+    fn test_multiline_strings_and_raw_strings() {
+        assert_eq!(
+            kinds(
+                ".rs",
+                "let s = r#\"\n// not a comment \" still\n\"#;\nfn f<'a>(x: &'a str) {} // c"
+            ),
+            [Code, Code, Code, Mixed]
+        );
+        assert_eq!(
+            kinds(".ts", "const t = `\n/* in template ${x} */\n`; // done"),
+            [Code, Code, Mixed]
+        );
+        assert_eq!(kinds(".go", "s := `a\n// raw`"), [Code, Code]);
+        assert_eq!(
+            kinds(
+                ".dart",
+                "var p = r'C:\\'; // dir\nvar s = '''\n/* not */\n''';"
+            ),
+            [Mixed, Code, Code, Code]
+        );
+    }
+
+    #[test]
+    fn test_python_docstrings_and_triple_quoted_code() {
+        let src = "def f():\n    \"\"\"Doc\n\n    more\"\"\"\n    x = \"\"\"\n# not comment\n\"\"\"\n    return x  # done";
+        assert_eq!(
+            kinds(".py", src),
+            [Code, Doc, Doc, Doc, Code, Code, Code, Mixed]
+        );
+    }
+
+    #[test]
+    fn test_heredocs() {
+        let src = "cat <<'EOF' # note\n# inside\nEOF\n# real comment";
+        assert_eq!(kinds(".sh", src), [Mixed, Code, Code, Comment]);
+        assert_eq!(
+            kinds(".php", "$a = <<<EOT\n// text\nEOT;\n$b = 1 << 2;"),
+            [Code, Code, Code, Code]
+        );
+        assert_eq!(
+            kinds(".rb", "x = <<~SQL\n  # text\n  SQL\n"),
+            [Code, Code, Code]
+        );
+    }
+
+    #[test]
+    fn test_escapes_and_unterminated_strings() {
+        assert_eq!(kinds(".c", "s = \"a\\\" // b\"; // c"), [Mixed]);
+        // A single-line string left open does not swallow the next line
+        assert_eq!(kinds(".c", "s = \"oops\n// comment"), [Code, Comment]);
+    }
+
+    #[test]
+    fn test_nested_block_comments() {
+        let src = "/* outer /* inner */
+still outer */ x();
+y();";
+        assert_eq!(kinds(".rs", src), [Comment, Mixed, Code]);
+        assert_eq!(kinds(".dart", src), [Comment, Mixed, Code]);
+        // C block comments do not nest: the first `*/` closes
+        assert_eq!(kinds(".c", src), [Comment, Code, Code]);
+        assert_eq!(
+            kinds(
+                ".hs",
+                "{- a {- b -}
+-} main = pure ()"
+            ),
+            [Comment, Mixed]
+        );
+        assert_eq!(
+            kinds(
+                ".nim",
+                "#[ a #[ b ]#
+]#
+echo 1 # c
+##[ doc ]##"
+            ),
+            [Comment, Comment, Mixed, Doc]
+        );
+        assert_eq!(
+            kinds(
+                ".lua",
+                "--[==[ a ]] b
+]==] x = 1"
+            ),
+            [Comment, Mixed]
+        );
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let src = "/// Adds.\n//! Crate.\n/** Doc\n\n */\n/**/ x();\nf(); /** tail */";
+        assert_eq!(kinds(".rs", src), [Doc, Doc, Doc, Doc, Doc, Mixed, Mixed]);
+        assert_eq!(
+            kinds(".java", "/**\n * A /* nested? */\n */\n/* plain\n\n*/"),
+            [Doc, Doc, Code, Comment, Comment, Comment]
+        );
+        assert_eq!(
+            kinds(".hs", "-- | Doc\n-- plain\n{-| a {- b -} -}"),
+            [Doc, Comment, Doc]
+        );
+        assert_eq!(kinds(".nim", "## doc\n# plain"), [Doc, Comment]);
+        assert_eq!(kinds(".lua", "--- doc\n-- plain"), [Doc, Comment]);
+    }
+}
@@ -1,25 +1,39 @@
 // Author: kelexine (https://github.com/kelexine)
 // counter.rs — File discovery, line counting, and parallel processing
 
+mod lexer;
+mod regions;
+
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 use std::time::UNIX_EPOCH;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, TimeZone, Utc};
 use rayon::prelude::*;
 
+use crate::cache::{Fingerprint, ScanCache};
 use crate::cli::Args;
-use crate::extractors;
+use crate::export::ExportFormat;
+use crate::extractors::{self, Extractor, HeaderLanguage, tree_sitter::TreeSitterExtractor};
+use crate::git::{self, BlobReader};
 use crate::language::{BINARY_EXTENSIONS, EXCLUDED_DIRS};
-use crate::models::{Breakdown, FileInfo, ScanResult};
+use crate::models::{Breakdown, ExtensionStats, FileInfo, FunctionInfo, ScanResult};
+use crate::thresholds::Thresholds;
+use lexer::LineLexer;
 
 /// Configuration for a scan run.
 #[derive(Clone)]
 pub struct ScanConfig {
     pub target_dir: PathBuf,
     pub allowed_extensions: Option<HashSet<String>>,
+    /// Language filters passed to [`ScanConfig::languages`] that name no known
+    /// language; they are still applied as bare extensions. Left for the
+    /// caller to report.
+    pub unknown_languages: Vec<String>,
     pub warn_size: Option<usize>,
     pub use_git_dates: bool,
     pub parallel: bool,
@@ -28,19 +42,59 @@ pub struct ScanConfig {
     pub custom_ignore: HashSet<String>,
     pub include_hidden: bool,
     pub git_dates_cache: Option<HashMap<PathBuf, DateTime<Utc>>>,
+    /// Quality gates checked by the caller once the scan finishes.
+    pub thresholds: Thresholds,
+    /// Scan this git revision from the object database instead of the working tree.
+    pub rev: Option<String>,
+    /// Directory of the persistent scan cache; `None` disables caching.
+    pub cache_dir: Option<PathBuf>,
+    /// Query-driven extractors by extension (`.rs`), used instead of the built-in ones.
+    pub query_extractors: HashMap<String, Arc<TreeSitterExtractor>>,
+    /// Grammar for `.h` headers; `Auto` is settled from the scanned files.
+    pub header_language: HeaderLanguage,
 }
 
 impl ScanConfig {
-    pub fn from_args(args: &Args) -> Result<Self> {
-        let target_dir = Path::new(&args.directory)
+    /// Create a configuration for scanning `target_dir` with default settings:
+    /// no language filter, parallel processing on, function extraction off.
+    ///
+    /// Resolves the directory, detects whether it lives inside a git work tree
+    /// and loads its `.locignore`. Does not read the global `config.toml`.
+    pub fn new(target_dir: impl AsRef<Path>) -> Result<Self> {
+        let target_dir = target_dir.as_ref();
+        let target_dir = target_dir
             .canonicalize()
-            .with_context(|| format!("Cannot resolve directory: {}", args.directory))?;
+            .with_context(|| format!("Cannot resolve directory: {}", target_dir.display()))?;
 
         if !target_dir.is_dir() {
             anyhow::bail!("Not a directory: {}", target_dir.display());
         }
 
         let is_git_repo = check_git_repo(&target_dir);
+        let custom_ignore = load_locignore(&target_dir);
+
+        Ok(Self {
+            target_dir,
+            allowed_extensions: None,
+            unknown_languages: Vec::new(),
+            warn_size: None,
+            use_git_dates: false,
+            parallel: true,
+            extract_functions: false,
+            is_git_repo,
+            custom_ignore,
+            include_hidden: false,
+            git_dates_cache: None,
+            thresholds: Thresholds::default(),
+            rev: None,
+            cache_dir: None,
+            query_extractors: HashMap::new(),
+            header_language: HeaderLanguage::Auto,
+        })
+    }
+
+    /// Build a configuration from parsed CLI arguments, layering in the global `config.toml`.
+    pub fn from_args(args: &Args) -> Result<Self> {
         let global_config = crate::config::GlobalConfig::load();
 
         // Build allowed extension set from language filter flags
@@ -51,46 +105,253 @@ impl ScanConfig {
             types_to_use = default_types.clone();
         }
 
-        let allowed_extensions = if types_to_use.is_empty() {
-            None
-        } else {
-            let mut exts = HashSet::new();
-            for lang in &types_to_use {
-                let resolved = crate::language::resolve_extensions(lang);
-                if resolved.is_empty()
-                    || (resolved.len() == 1 && resolved[0] == format!(".{}", lang))
-                {
-                    // Check if it actually resolves to something known
-                    eprintln!("[WARNING] Unknown language filter: {}", lang);
-                }
-                exts.extend(resolved);
-            }
-            Some(exts)
-        };
-
-        let custom_ignore = load_locignore(&target_dir);
         let warn_size = args.warn_size.or(global_config.warn_size);
+        // SARIF and hotspots report function-level findings, so they need extraction too
+        let exports_sarif = args
+            .export
+            .as_deref()
+            .and_then(|p| ExportFormat::from_path(Path::new(p)))
+            == Some(ExportFormat::Sarif);
         let extract_functions = args.functions
             || args.func_analysis
+            || exports_sarif
+            || args.hotspots.is_some()
             || global_config.always_extract_functions.unwrap_or(false);
+        let thresholds = args.thresholds().or(&global_config.thresholds);
+        let use_cache = !args.no_cache && (args.cache || global_config.cache.unwrap_or(false));
+
+        let mut config = Self::new(&args.directory)?;
+        for entry in &global_config.extractors {
+            match entry.load() {
+                Ok(extractor) => config = config.query_extractor(&entry.extensions, extractor),
+                Err(e) => eprintln!(
+                    "[WARNING] Skipping extractor for {}: {:#}",
+                    entry.grammar, e
+                ),
+            }
+        }
 
-        Ok(Self {
-            target_dir,
-            allowed_extensions,
-            warn_size,
-            use_git_dates: args.git_dates,
-            parallel: !args.no_parallel,
-            extract_functions,
-            is_git_repo,
-            custom_ignore,
-            include_hidden: args.include_hidden,
-            git_dates_cache: None,
-        })
+        Ok(config
+            .languages(&types_to_use)
+            .warn_size(warn_size)
+            .git_dates(args.git_dates)
+            .parallel(!args.no_parallel)
+            .extract_functions(extract_functions)
+            .include_hidden(args.include_hidden)
+            .thresholds(thresholds)
+            .rev(args.rev.clone())
+            .header_language(global_config.c_headers.unwrap_or_default())
+            .cache_dir(if use_cache {
+                crate::cache::default_dir()
+            } else {
+                None
+            }))
+    }
+
+    /// Restrict the scan to the given languages, aliases or raw extensions
+    /// (see [`crate::language::resolve_extensions`]). An empty list clears the filter.
+    /// Names that are not a known language end up in `unknown_languages`.
+    pub fn languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut exts = HashSet::new();
+        self.unknown_languages.clear();
+        for lang in languages {
+            let lang = lang.as_ref();
+            let resolved = crate::language::resolve_extensions(lang);
+            // A name that fell through to a bare extension no language uses
+            let bare = resolved.len() == 1 && resolved[0] == format!(".{}", lang.to_lowercase());
+            if resolved.is_empty() || (bare && !crate::language::is_known_extension(&resolved[0])) {
+                self.unknown_languages.push(lang.to_string());
+            }
+            exts.extend(resolved);
+        }
+        self.allowed_extensions = if exts.is_empty() { None } else { Some(exts) };
+        self
+    }
+
+    /// Flag files whose line count exceeds `lines`.
+    pub fn warn_size(mut self, lines: Option<usize>) -> Self {
+        self.warn_size = lines;
+        self
+    }
+
+    /// Use `git log` for last-modified dates instead of filesystem mtimes.
+    pub fn git_dates(mut self, enabled: bool) -> Self {
+        self.use_git_dates = enabled;
+        self
+    }
+
+    /// Toggle Rayon parallel file processing.
+    pub fn parallel(mut self, enabled: bool) -> Self {
+        self.parallel = enabled;
+        self
+    }
+
+    /// Run the tree-sitter extractors and populate `FileInfo::functions`.
+    pub fn extract_functions(mut self, enabled: bool) -> Self {
+        self.extract_functions = enabled;
+        self
+    }
+
+    /// Include hidden files and directories (this also bypasses `git ls-files`).
+    pub fn include_hidden(mut self, enabled: bool) -> Self {
+        self.include_hidden = enabled;
+        self
+    }
+
+    /// Set the quality gates. Function-level limits turn on function extraction.
+    pub fn thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.extract_functions |= thresholds.needs_functions();
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Scan a commit, tag or branch instead of the working tree (`None` = working tree).
+    pub fn rev(mut self, rev: Option<String>) -> Self {
+        self.rev = rev;
+        self
+    }
+
+    /// Reuse results for unchanged files across runs, persisted under `dir`
+    /// (see [`crate::cache::default_dir`]). `None` turns the cache off.
+    pub fn cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.cache_dir = dir;
+        self
+    }
+
+    /// Parse `.h` headers as C or C++, or decide from the project (the default).
+    pub fn header_language(mut self, language: HeaderLanguage) -> Self {
+        self.header_language = language;
+        self
+    }
+
+    /// Extract functions from files with these extensions (`"rs"` or `".rs"`)
+    /// with `extractor` instead of the built-in extractor.
+    pub fn query_extractor<I, S>(mut self, extensions: I, extractor: TreeSitterExtractor) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let extractor = Arc::new(extractor);
+        for ext in extensions {
+            let ext = ext.as_ref().trim_start_matches('.').to_lowercase();
+            self.query_extractors
+                .insert(format!(".{}", ext), Arc::clone(&extractor));
+        }
+        self
+    }
+
+    /// What the scan cache must match: function extraction, the header
+    /// language and the queries in use.
+    fn cache_settings(&self) -> String {
+        let mut queries: Vec<_> = self
+            .query_extractors
+            .iter()
+            .map(|(ext, e)| {
+                format!(
+                    ";{}={:016x}",
+                    ext,
+                    crate::cache::fnv1a(e.query_source().as_bytes())
+                )
+            })
+            .collect();
+        queries.sort();
+        format!(
+            "functions={};headers={:?}{}",
+            self.extract_functions,
+            self.header_language,
+            queries.concat()
+        )
+    }
+
+    /// Whether `path` passes the language filter (always true without one).
+    pub(crate) fn allows_extension(&self, path: &Path) -> bool {
+        let Some(allowed) = &self.allowed_extensions else {
+            return true;
+        };
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| format!(".{}", e.to_lowercase()))
+            .unwrap_or_default();
+        allowed.contains(&ext)
     }
 }
 
 /// Run the full scan and return a ScanResult.
+///
+/// With [`ScanConfig::rev`] set, files are listed with `git ls-tree` and read
+/// from the object database; the working tree is never touched.
+///
+/// With [`ScanConfig::cache_dir`] set, files whose size and mtime (or blob id)
+/// are unchanged since the last cached run are not read again.
 pub fn run_scan(config: &ScanConfig) -> Result<ScanResult> {
+    let mut runner_config = config.clone();
+    if runner_config.use_git_dates && runner_config.is_git_repo {
+        let rev = config.rev.as_deref().unwrap_or("HEAD");
+        runner_config.git_dates_cache = Some(get_all_git_dates(&runner_config.target_dir, rev));
+    }
+
+    // Opened by the scan once `.h` headers are resolved, since the cache key
+    // depends on the header language actually used
+    let mut cache = None;
+    let mut file_infos = match config.rev {
+        Some(ref rev) => scan_revision(rev, &mut runner_config, &mut cache)?,
+        None => scan_working_tree(&mut runner_config, &mut cache),
+    };
+
+    if let Some(cache) = cache
+        && let Err(e) = cache.save()
+    {
+        eprintln!("[WARN] Could not write scan cache: {}", e);
+    }
+    file_infos.sort_by(|a, b| a.path.cmp(&b.path));
+
+    // Build breakdown
+    let mut breakdown: Breakdown = std::collections::HashMap::new();
+    for fi in &file_infos {
+        if fi.is_binary {
+            continue;
+        }
+        for (key, stats) in fi.breakdown_stats() {
+            breakdown.entry(key).or_default().add(&stats);
+        }
+    }
+
+    Ok(ScanResult {
+        root: config.target_dir.clone(),
+        files: file_infos,
+        breakdown,
+    })
+}
+
+/// The scan cache for `config`, if enabled. Call after `.h` headers are resolved.
+fn open_cache(config: &ScanConfig) -> Option<ScanCache> {
+    config
+        .cache_dir
+        .as_ref()
+        .map(|dir| ScanCache::load(dir, &config.target_dir, &config.cache_settings()))
+}
+
+/// What a cached blob result depends on besides its content: the extension,
+/// and for `.h` headers the grammar they are parsed with.
+fn blob_language(path: &Path, config: &ScanConfig) -> String {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if ext == "h" {
+        format!("h/{:?}", config.header_language)
+    } else {
+        ext
+    }
+}
+
+fn scan_working_tree(config: &mut ScanConfig, cache: &mut Option<ScanCache>) -> Vec<FileInfo> {
     let files = if config.is_git_repo && !config.include_hidden {
         get_git_files(&config.target_dir)
     } else {
@@ -103,67 +364,132 @@ pub fn run_scan(config: &ScanConfig) -> Result<ScanResult> {
 
     let mut sorted_files = files;
     sorted_files.sort_unstable();
+    config.header_language = config
+        .header_language
+        .detect(sorted_files.iter().map(PathBuf::as_path));
+    let config = &*config;
+    *cache = open_cache(config);
+    let cache = cache.as_mut();
+
+    let cached = cache.as_deref();
+    let process = |path: &PathBuf| {
+        let fingerprint = cached.and_then(|_| Fingerprint::of(path));
+        if let Some(cache) = cached
+            && let Some(fp) = fingerprint
+            && let Some(fi) = cache.get_file(path, &fp)
+        {
+            return reuse_cached(fi, config).map(|fi| (fi, fingerprint));
+        }
+        match process_file(path, config) {
+            Ok(opt) => opt.map(|fi| (fi, fingerprint)),
+            Err(e) => {
+                eprintln!("[WARN] Skipped {}: {}", path.display(), e);
+                None
+            }
+        }
+    };
 
-    let mut runner_config = config.clone();
-    if runner_config.use_git_dates && runner_config.is_git_repo {
-        runner_config.git_dates_cache = Some(get_all_git_dates(&runner_config.target_dir));
+    let results: Vec<(FileInfo, Option<Fingerprint>)> =
+        if config.parallel && sorted_files.len() > 50 {
+            sorted_files.par_iter().filter_map(process).collect()
+        } else {
+            sorted_files.iter().filter_map(process).collect()
+        };
+
+    if let Some(cache) = cache {
+        for (fi, fingerprint) in &results {
+            if let Some(fp) = fingerprint {
+                cache.put_file(*fp, fi.clone());
+            }
+        }
     }
+    results.into_iter().map(|(fi, _)| fi).collect()
+}
 
-    let file_infos: Vec<FileInfo> = if runner_config.parallel && sorted_files.len() > 50 {
-        sorted_files
-            .par_iter()
-            .filter_map(|path| match process_file(path, &runner_config) {
-                Ok(opt) => opt,
-                Err(e) => {
-                    eprintln!("[WARN] Skipped {}: {}", path.display(), e);
-                    None
-                }
-            })
-            .collect()
-    } else {
-        sorted_files
-            .iter()
-            .filter_map(|path| match process_file(path, &runner_config) {
-                Ok(opt) => opt,
-                Err(e) => {
-                    eprintln!("[WARN] Skipped {}: {}", path.display(), e);
-                    None
-                }
-            })
-            .collect()
-    };
+/// A cached result, re-checked against the current filters and with a fresh
+/// last-modified date (which depends on `--git-dates`, not on the content).
+fn reuse_cached(fi: &FileInfo, config: &ScanConfig) -> Option<FileInfo> {
+    if !config.allows_extension(&fi.path) || (fi.is_binary && config.allowed_extensions.is_some()) {
+        return None;
+    }
+    Some(FileInfo {
+        last_modified: last_modified(&fi.path, config),
+        ..fi.clone()
+    })
+}
 
-    let mut file_infos = file_infos;
-    file_infos.sort_by(|a, b| a.path.cmp(&b.path));
+fn scan_revision(
+    rev: &str,
+    config: &mut ScanConfig,
+    cache: &mut Option<ScanCache>,
+) -> Result<Vec<FileInfo>> {
+    if !config.is_git_repo {
+        anyhow::bail!(
+            "--rev needs a git repository: {}",
+            config.target_dir.display()
+        );
+    }
+    let commit = git::resolve_rev(&config.target_dir, rev)?;
+    let entries = git::ls_tree(&config.target_dir, &commit)?;
+    config.header_language = config
+        .header_language
+        .detect(entries.iter().map(|e| e.path.as_path()));
+    let config = &*config;
+    *cache = open_cache(config);
+    let mut cache = cache.as_mut();
+
+    // Blobs come out of a single `git cat-file` process, so reading is
+    // sequential; counting and extraction run in parallel afterwards.
+    let git_date = |path: &Path| {
+        config
+            .git_dates_cache
+            .as_ref()
+            .and_then(|cache| cache.get(path).copied())
+    };
 
-    // Build breakdown
-    let mut breakdown: Breakdown = std::collections::HashMap::new();
-    for fi in &file_infos {
-        if fi.is_binary {
+    let mut reader = BlobReader::new(&config.target_dir)?;
+    let mut file_infos = Vec::new();
+    let mut blobs = Vec::new();
+    for entry in entries {
+        if !config.allows_extension(&entry.path) {
             continue;
         }
-        let ext = if fi.extension().is_empty() {
-            fi.path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("(no ext)")
-                .to_string()
-        } else {
-            fi.extension().to_string()
-        };
-        let stats = breakdown.entry(ext).or_default();
-        stats.lines += fi.lines;
-        stats.code += fi.code;
-        stats.comment += fi.comment;
-        stats.blank += fi.blank;
-        stats.files += 1;
-        stats.functions += fi.function_count();
+        let path = config.target_dir.join(&entry.path);
+        let language = blob_language(&path, config);
+        if let Some(fi) = cache
+            .as_deref()
+            .and_then(|c| c.get_blob(&entry.oid, &language, &path))
+        {
+            if !(fi.is_binary && config.allowed_extensions.is_some()) {
+                let last_modified = git_date(&path);
+                file_infos.push(FileInfo {
+                    last_modified,
+                    ..fi
+                });
+            }
+            continue;
+        }
+        let bytes = reader.read(&entry.oid)?;
+        blobs.push((path, entry.oid, bytes));
     }
 
-    Ok(ScanResult {
-        files: file_infos,
-        breakdown,
-    })
+    let process = |(path, oid, bytes): &(PathBuf, String, Vec<u8>)| {
+        process_blob(path, bytes, config, git_date(path)).map(|fi| (oid.clone(), fi))
+    };
+
+    let processed: Vec<(String, FileInfo)> = if config.parallel && blobs.len() > 50 {
+        blobs.par_iter().filter_map(process).collect()
+    } else {
+        blobs.iter().filter_map(process).collect()
+    };
+
+    for (oid, fi) in processed {
+        if let Some(cache) = cache.as_deref_mut() {
+            cache.put_blob(&oid, &blob_language(&fi.path, config), fi.clone());
+        }
+        file_infos.push(fi);
+    }
+    Ok(file_infos)
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -176,31 +502,43 @@ fn process_file(path: &Path, config: &ScanConfig) -> Result<Option<FileInfo>> {
     }
 
     // Extension filter
-    if let Some(allowed) = &config.allowed_extensions {
-        let ext = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| format!(".{}", e.to_lowercase()))
-            .unwrap_or_default();
-        if !allowed.contains(&ext) {
-            return Ok(None);
-        }
+    if !config.allows_extension(path) {
+        return Ok(None);
     }
 
-    let is_binary = is_binary_file(path);
+    // The file is read once; sniffing, decoding, counting and extraction all
+    // share this buffer. Known binary extensions and unreadable files are
+    // never decoded.
+    let bytes = if has_binary_extension(path) {
+        None
+    } else {
+        std::fs::read(path).ok()
+    };
+    let is_binary = bytes
+        .as_deref()
+        .is_none_or(|bytes| is_binary_content(path, bytes));
 
     // Skip binary files if we're type-filtering
     if is_binary && config.allowed_extensions.is_some() {
         return Ok(None);
     }
 
-    let (total, code, comment, blank) = if is_binary {
-        (0, 0, 0, 0)
-    } else {
-        analyze_file(path)
+    let last_modified = last_modified(path, config);
+    let content = match bytes {
+        Some(ref bytes) if !is_binary => Some(decode(bytes)),
+        _ => None,
     };
 
-    let last_modified = if config.use_git_dates {
+    Ok(Some(build_file_info(
+        path,
+        content.as_deref(),
+        last_modified,
+        config,
+    )))
+}
+
+fn last_modified(path: &Path, config: &ScanConfig) -> Option<DateTime<Utc>> {
+    if config.use_git_dates {
         if let Some(ref cache) = config.git_dates_cache {
             cache.get(path).copied()
         } else {
@@ -208,150 +546,320 @@ fn process_file(path: &Path, config: &ScanConfig) -> Result<Option<FileInfo>> {
         }
     } else {
         get_fs_last_modified(path)
+    }
+}
+
+/// Like `process_file`, for content that is already in memory (a git blob).
+/// `path` is only used for its extension and as the reported location.
+pub(crate) fn process_blob(
+    path: &Path,
+    bytes: &[u8],
+    config: &ScanConfig,
+    last_modified: Option<DateTime<Utc>>,
+) -> Option<FileInfo> {
+    if !config.allows_extension(path) {
+        return None;
+    }
+
+    let is_binary = is_binary_content(path, bytes);
+    if is_binary && config.allowed_extensions.is_some() {
+        return None;
+    }
+
+    let content = (!is_binary).then(|| decode(bytes));
+    Some(build_file_info(
+        path,
+        content.as_deref(),
+        last_modified,
+        config,
+    ))
+}
+
+/// Count lines and extract functions from `content`; `None` marks a binary file.
+fn build_file_info(
+    path: &Path,
+    content: Option<&str>,
+    last_modified: Option<DateTime<Utc>>,
+    config: &ScanConfig,
+) -> FileInfo {
+    let Some(content) = content else {
+        return FileInfo::new(path.to_path_buf(), 0, 0, 0, 0, true, last_modified);
     };
 
-    let mut fi = FileInfo::new(
+    let (counts, embedded) = analyze_file(path, content);
+    let mut embedded: HashMap<String, ExtensionStats> = embedded
+        .into_iter()
+        .map(|(key, counts)| (key, counts.into()))
+        .collect();
+    let fi = FileInfo::new(
         path.to_path_buf(),
-        total,
-        code,
-        comment,
-        blank,
-        is_binary,
+        counts.total,
+        counts.code,
+        counts.comment,
+        counts.blank,
+        false,
         last_modified,
-    );
+    )
+    .with_mixed(counts.mixed)
+    .with_doc(counts.doc);
+
+    let fi = if config.extract_functions {
+        let mut functions = extract_file_functions(path, content, config);
+        for (key, found) in extract_script_functions(path, content, config) {
+            embedded.entry(key).or_default().functions += found.len();
+            functions.extend(found);
+        }
+        functions.sort_by_key(|f| f.line_start);
+        fi.with_functions(functions)
+    } else {
+        fi
+    };
+    fi.with_embedded(embedded)
+}
 
-    if config.extract_functions && !is_binary {
-        let functions = extract_file_functions(path);
-        fi = fi.with_functions(functions);
-    }
+/// How a single line is counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    Code,
+    Comment,
+    Blank,
+    /// Code and a comment on the same line; counted as code.
+    Mixed,
+    /// Documentation comment (or docstring); counted as comment.
+    Doc,
+}
 
-    Ok(Some(fi))
+/// Line counts of one file. `mixed` lines are included in `code`, `doc`
+/// lines in `comment`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct LineCounts {
+    total: usize,
+    code: usize,
+    comment: usize,
+    blank: usize,
+    mixed: usize,
+    doc: usize,
 }
 
-fn analyze_file(path: &Path) -> (usize, usize, usize, usize) {
-    let content = match std::fs::read_to_string(path) {
-        Ok(s) => s,
-        Err(_) => return (0, 0, 0, 0),
-    };
+impl LineCounts {
+    fn add(&mut self, kind: LineKind) {
+        self.total += 1;
+        match kind {
+            LineKind::Code => self.code += 1,
+            LineKind::Comment => self.comment += 1,
+            LineKind::Blank => self.blank += 1,
+            LineKind::Mixed => {
+                self.code += 1;
+                self.mixed += 1;
+            }
+            LineKind::Doc => {
+                self.comment += 1;
+                self.doc += 1;
+            }
+        }
+    }
+}
 
-    let ext = path
+impl From<LineCounts> for ExtensionStats {
+    fn from(counts: LineCounts) -> Self {
+        ExtensionStats {
+            lines: counts.total,
+            code: counts.code,
+            comment: counts.comment,
+            blank: counts.blank,
+            mixed: counts.mixed,
+            doc: counts.doc,
+            ..Default::default()
+        }
+    }
+}
+
+/// Count the lines of `content`, using the comment and string syntax of
+/// `path`'s extension. `path` does not need to exist on disk.
+///
+/// Returns the counts of the whole file, and those of its regions in other
+/// languages (a `.vue` file's `<script>`) keyed like `Breakdown`.
+fn analyze_file(path: &Path, content: &str) -> (LineCounts, HashMap<String, LineCounts>) {
+    let host = path
         .extension()
         .and_then(|e| e.to_str())
         .map(|e| format!(".{}", e.to_lowercase()))
         .unwrap_or_default();
+    let mut counts = LineCounts::default();
+    let mut embedded: HashMap<String, LineCounts> = HashMap::new();
+
+    for_each_line_kind(path, content, |ext, kind| {
+        counts.add(kind);
+        if ext != host {
+            let key = ext.trim_start_matches('.').to_string();
+            embedded.entry(key).or_default().add(kind);
+        }
+    });
 
-    let spec = crate::language::COMMENT_REGISTRY.get(ext.as_str());
+    (counts, embedded)
+}
 
-    let mut total = 0;
-    let mut code = 0;
-    let mut comment = 0;
-    let mut blank = 0;
+/// Classify every line of `content`, in order, the same way `analyze_file` counts them.
+pub(crate) fn classify_lines(path: &Path, content: &str) -> Vec<LineKind> {
+    let mut kinds = Vec::new();
+    for_each_line_kind(path, content, |_, kind| kinds.push(kind));
+    kinds
+}
 
-    let mut in_multi_comment = false;
+/// Classify the lines of `content`, region by region (see [`regions::split`]),
+/// passing each line's kind along with the extension it is counted as.
+fn for_each_line_kind(path: &Path, content: &str, mut emit: impl FnMut(&str, LineKind)) {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| format!(".{}", e.to_lowercase()))
+        .unwrap_or_default();
 
-    for line in content.lines() {
-        total += 1;
-        let trimmed = line.trim();
+    // lines() ignores the trailing empty line, so files with and without a
+    // final newline count the same
+    let lines: Vec<&str> = content.lines().collect();
+    for region in regions::split(&ext, &lines) {
+        classify_region(&region.ext, &lines[region.start..region.end], |kind| {
+            emit(&region.ext, kind)
+        });
+    }
+}
 
-        if trimmed.is_empty() {
-            if in_multi_comment {
-                comment += 1;
-            } else {
-                blank += 1;
-            }
+fn classify_region(ext: &str, lines: &[&str], mut emit: impl FnMut(LineKind)) {
+    let spec = crate::language::COMMENT_REGISTRY.get(ext);
+    let mut lexer = spec.map(LineLexer::new);
+    let doc_before = spec.map_or(&[][..], |s| s.doc_before);
+    // Comment lines held back until we know whether a declaration follows them
+    let mut held = 0;
+
+    for line in lines {
+        let kind = match lexer {
+            Some(ref mut lexer) => lexer.classify(line),
+            None if line.trim().is_empty() => LineKind::Blank,
+            None => LineKind::Code,
+        };
+        if doc_before.is_empty() {
+            emit(kind);
             continue;
         }
-
-        if let Some(s) = spec {
-            if in_multi_comment {
-                comment += 1;
-                if let Some((_, end)) = s.multi
-                    && trimmed.contains(end)
-                {
-                    in_multi_comment = false;
-                }
-                continue;
-            }
-
-            if let Some((start, end)) = s.multi
-                && trimmed.starts_with(start)
-            {
-                comment += 1;
-                if !trimmed.contains(end) || trimmed.find(start) == trimmed.find(end) {
-                    in_multi_comment = true;
-                }
-                continue;
-            }
-
-            if let Some(single) = s.single
-                && trimmed.starts_with(single)
-            {
-                comment += 1;
-                continue;
-            }
+        if kind == LineKind::Comment {
+            held += 1;
+            continue;
         }
-
-        code += 1;
+        let documents = matches!(kind, LineKind::Code | LineKind::Mixed)
+            && doc_before
+                .iter()
+                .any(|kw| line.trim_start().starts_with(kw));
+        for _ in 0..std::mem::take(&mut held) {
+            emit(if documents {
+                LineKind::Doc
+            } else {
+                LineKind::Comment
+            });
+        }
+        emit(kind);
     }
-
-    // Handle files that don't end with a newline (lines() ignores trailing empty line)
-    if content.ends_with('\n') {
-        // Correct, lines() gave us the right count
-    } else if !content.is_empty() {
-        // lines() also gave us the right count for a single line with no newline
+    for _ in 0..held {
+        emit(LineKind::Comment);
     }
+}
 
-    (total, code, comment, blank)
+/// Binary check for content that is already in memory (e.g. a git blob).
+fn is_binary_content(path: &Path, content: &[u8]) -> bool {
+    has_binary_extension(path) || looks_binary(&content[..content.len().min(8192)])
 }
 
-fn is_binary_file(path: &Path) -> bool {
-    // Check extension first (fast path)
+fn has_binary_extension(path: &Path) -> bool {
     let ext = path
         .extension()
         .and_then(|e| e.to_str())
         .map(|e| format!(".{}", e.to_lowercase()))
         .unwrap_or_default();
+    BINARY_EXTENSIONS.contains(ext.as_str())
+}
+
+/// Null-byte sniff of a file's leading bytes, ignoring UTF-16/32 BOMs.
+fn looks_binary(buf: &[u8]) -> bool {
+    let n = buf.len();
 
-    if BINARY_EXTENSIONS.contains(ext.as_str()) {
-        return true;
+    // BOM Check for UTF-16/32 to avoid false positive on null bytes
+    if n >= 2 && ((buf[0] == 0xFE && buf[1] == 0xFF) || (buf[0] == 0xFF && buf[1] == 0xFE)) {
+        return false; // UTF-16
+    }
+    if n >= 4
+        && ((buf[0] == 0x00 && buf[1] == 0x00 && buf[2] == 0xFE && buf[3] == 0xFF)
+            || (buf[0] == 0xFF && buf[1] == 0xFE && buf[2] == 0x00 && buf[3] == 0x00))
+    {
+        return false; // UTF-32
     }
 
-    // Read first 8 KiB and look for null bytes
-    let mut buf = [0u8; 8192];
-    match std::fs::File::open(path) {
-        Ok(mut f) => {
-            use std::io::Read;
-            let n = f.read(&mut buf).unwrap_or(0);
+    buf.contains(&0u8)
+}
 
-            // BOM Check for UTF-16/32 to avoid false positive on null bytes
-            if n >= 2 && ((buf[0] == 0xFE && buf[1] == 0xFF) || (buf[0] == 0xFF && buf[1] == 0xFE))
-            {
-                return false; // UTF-16
-            }
-            if n >= 4
-                && ((buf[0] == 0x00 && buf[1] == 0x00 && buf[2] == 0xFE && buf[3] == 0xFF)
-                    || (buf[0] == 0xFF && buf[1] == 0xFE && buf[2] == 0x00 && buf[3] == 0x00))
-            {
-                return false; // UTF-32
-            }
+/// Text of a non-binary file: UTF-16 with a byte order mark is transcoded,
+/// anything else is read as UTF-8 with invalid sequences replaced.
+fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    let utf16 = |bytes: &[u8], from: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|c| from([c[0], c[1]])).collect();
+        Cow::Owned(String::from_utf16_lossy(&units))
+    };
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        // Not UTF-32LE, whose BOM starts with the same two bytes
+        [0xFF, 0xFE, rest @ ..] if !rest.starts_with(&[0, 0]) => utf16(rest, u16::from_le_bytes),
+        _ => String::from_utf8_lossy(bytes),
+    }
+}
 
-            buf[..n].contains(&0u8)
-        }
-        Err(_) => true,
+/// Run the extractor for `path`'s language over `content`, preferring a
+/// query-driven one registered for its extension.
+fn extract_file_functions(path: &Path, content: &str, config: &ScanConfig) -> Vec<FunctionInfo> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| format!(".{}", e.to_lowercase()))
+        .unwrap_or_default();
+    if let Some(extractor) = config.query_extractors.get(&ext) {
+        return extractor.extract(content);
+    }
+    if ext == ".h" {
+        return config.header_language.extractor(content).extract(content);
+    }
+    match extractors::get_extractor(path) {
+        Some(ext) => ext.extract(content),
+        None => vec![],
     }
 }
 
-fn extract_file_functions(path: &Path) -> Vec<crate::models::FunctionInfo> {
-    match std::fs::read_to_string(path) {
-        Ok(content) => {
-            if let Some(ext) = extractors::get_extractor(path) {
-                ext.extract(&content)
-            } else {
-                vec![]
+/// Functions of the `<script>` blocks of a markup file, by the `Breakdown`
+/// key of the script's language, numbered from the top of the file.
+fn extract_script_functions(
+    path: &Path,
+    content: &str,
+    config: &ScanConfig,
+) -> Vec<(String, Vec<FunctionInfo>)> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| format!(".{}", e.to_lowercase()))
+        .unwrap_or_default();
+    let lines: Vec<&str> = content.lines().collect();
+
+    regions::split(&ext, &lines)
+        .into_iter()
+        .filter(|region| region.script)
+        .map(|region| {
+            let key = region.ext.trim_start_matches('.');
+            let script = region.source(&lines);
+            let mut functions = extract_file_functions(&path.with_extension(key), &script, config);
+            for f in &mut functions {
+                f.line_start += region.start;
+                f.line_end += region.start;
             }
-        }
-        Err(_) => vec![],
-    }
+            (key.to_string(), functions)
+        })
+        .collect()
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -369,7 +877,13 @@ fn check_git_repo(dir: &Path) -> bool {
 
 fn get_git_files(dir: &Path) -> Vec<PathBuf> {
     let output = Command::new("git")
-        .args(["ls-files", "-z", "--cached", "--others", "--exclude-standard"])
+        .args([
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ])
         .current_dir(dir)
         .output();
 
@@ -432,10 +946,10 @@ fn get_manual_files(
         .collect()
 }
 
-fn get_all_git_dates(root: &Path) -> HashMap<PathBuf, DateTime<Utc>> {
+fn get_all_git_dates(root: &Path, rev: &str) -> HashMap<PathBuf, DateTime<Utc>> {
     let mut map = std::collections::HashMap::new();
     let output = Command::new("git")
-        .args(["log", "--format=commit %ct", "--name-only"])
+        .args(["log", "--format=commit %ct", "--name-only", rev, "--"])
         .current_dir(root)
         .output();
 
@@ -477,11 +991,17 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
 
+    fn is_binary_file(path: &std::path::Path) -> bool {
+        is_binary_content(path, &fs::read(path).unwrap())
+    }
+
     /// Thin shim so tests can assert on raw line counts without
     /// exposing a public `count_lines` API. Delegates to `analyze_file`
-    /// and returns the total-lines field (index 0 of the tuple).
+    /// and returns the total-lines field.
     fn count_lines(path: &std::path::Path) -> usize {
-        analyze_file(path).0
+        analyze_file(path, &fs::read_to_string(path).unwrap())
+            .0
+            .total
     }
 
     #[test]
@@ -566,6 +1086,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decode_text_encodings() {
+        assert_eq!(decode(b"\xEF\xBB\xBFfn a() {}"), "fn a() {}");
+        assert_eq!(decode(&[0xFE, 0xFF, 0x00, 0x61, 0x00, 0x0A]), "a\n");
+        assert_eq!(decode(&[0xFF, 0xFE, 0x61, 0x00, 0x0A, 0x00]), "a\n");
+        assert_eq!(decode(b"caf\xE9"), "caf\u{FFFD}");
+    }
+
+    #[test]
+    fn test_go_doc_comments_attach_to_declarations() {
+        let src = "// Package x does things.\npackage x\n\n// note\n\n// Add adds.\n// More.\nfunc Add() {}\n// trailing\n";
+        let (counts, _) = analyze_file(Path::new("x.go"), src);
+        assert_eq!(counts.comment, 5);
+        assert_eq!(counts.doc, 3);
+        assert_eq!(counts.total, 9);
+    }
+
+    #[test]
+    fn test_embedded_regions_counted_and_extracted() {
+        let dir = tempdir().unwrap();
+        let config = ScanConfig::new(dir.path()).unwrap().extract_functions(true);
+        let src = "<template>\n  <!-- greeting -->\n  <p>{{ msg }}</p>\n</template>\n<script>\n// Says hi.\nexport function greet(name) {\n  return `hi ${name}`;\n}\n</script>\n<style>\n/* red */\np { color: red; }\n</style>\n";
+        let fi = build_file_info(Path::new("App.vue"), Some(src), None, &config);
+        assert_eq!(fi.lines, 14);
+
+        let js = &fi.embedded["js"];
+        assert_eq!((js.lines, js.comment, js.functions), (4, 1, 1));
+        let css = &fi.embedded["css"];
+        assert_eq!((css.lines, css.comment), (2, 1));
+
+        // Numbered from the top of the file, not of the script
+        assert_eq!(fi.functions[0].name, "greet");
+        assert_eq!(fi.functions[0].line_start, 7);
+
+        let stats: HashMap<_, _> = fi.breakdown_stats().into_iter().collect();
+        let vue = &stats["vue"];
+        assert_eq!(
+            (vue.lines, vue.comment, vue.files, vue.functions),
+            (8, 1, 1, 0)
+        );
+        assert_eq!(stats["js"].files, 0);
+    }
+
     #[test]
     fn test_manual_files_with_ignore() {
         let dir = tempdir().unwrap();
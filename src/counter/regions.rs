@@ -0,0 +1,373 @@
+// Author: kelexine (https://github.com/kelexine)
+// counter/regions.rs — Splitting multi-language files into per-language line regions
+
+use crate::language::{LANGUAGE_MAP, resolve_extensions};
+
+/// A run of consecutive lines counted under one language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Region {
+    /// Extension the lines are counted as, with the leading dot.
+    pub ext: String,
+    /// First line of the region (0-based) and one past its last.
+    pub start: usize,
+    pub end: usize,
+    /// A `<script>` block, whose functions are extracted on their own.
+    pub script: bool,
+}
+
+impl Region {
+    /// The region's lines for parsing, with the `<script>` tag a first line
+    /// shares with code, and the `</script>` tag of a last line, blanked out.
+    pub fn source(&self, lines: &[&str]) -> String {
+        let last = self.end - 1;
+        (self.start..self.end)
+            .map(|i| {
+                let mut line = lines[i].to_string();
+                let lower = line.to_ascii_lowercase();
+                if i == self.start
+                    && let Some(at) = lower.find("<script")
+                    && let Some(len) = lower[at..].find('>')
+                {
+                    line.replace_range(at..=at + len, &" ".repeat(len + 1));
+                }
+                if i == last
+                    && let Some(at) = lower.find("</script")
+                {
+                    line.truncate(at);
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Split the `lines` of a file with extension `ext` into regions, in order.
+/// Every line belongs to exactly one region:
+///
+/// - HTML, Vue and Svelte: the lines inside `<script>` and `<style>` blocks
+///   are counted as the block's `lang`/`type` (`.js` and `.css` by default)
+/// - PHP: lines entirely outside `<?php ... ?>` are HTML, split further
+///   into script and style blocks like an HTML file
+/// - Markdown: the lines of a fenced code block are counted as its info
+///   string's language, when loc-rs knows it
+///
+/// Tags and fences on lines of their own stay with the host file; a tag
+/// sharing its line with code goes with the code. Any other file is one
+/// region.
+pub(crate) fn split(ext: &str, lines: &[&str]) -> Vec<Region> {
+    match ext {
+        ".html" | ".htm" | ".vue" | ".svelte" => markup(ext, lines),
+        ".php" | ".php3" | ".php4" | ".php5" | ".phtml" => php(ext, lines),
+        ".md" | ".markdown" | ".mdx" => markdown(ext, lines),
+        _ if lines.is_empty() => vec![],
+        _ => vec![Region {
+            ext: ext.to_string(),
+            start: 0,
+            end: lines.len(),
+            script: false,
+        }],
+    }
+}
+
+/// Add line `line` to the last region if it continues it, or start a new one.
+fn push(regions: &mut Vec<Region>, ext: &str, line: usize, script: bool) {
+    if let Some(last) = regions.last_mut()
+        && last.ext == ext
+        && last.script == script
+        && last.end == line
+    {
+        last.end += 1;
+        return;
+    }
+    regions.push(Region {
+        ext: ext.to_string(),
+        start: line,
+        end: line + 1,
+        script,
+    });
+}
+
+fn markup(host: &str, lines: &[&str]) -> Vec<Region> {
+    let mut regions = Vec::new();
+    // The block we are inside: its extension and closing tag
+    let mut block: Option<(&str, &str)> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let lower = line.to_ascii_lowercase();
+        match block {
+            Some((ext, close)) => {
+                let script = close == "</script";
+                match lower.find(close) {
+                    // `init(); </script>` is still script
+                    Some(at) if !lower[..at].trim().is_empty() => {
+                        push(&mut regions, ext, i, script)
+                    }
+                    Some(_) => push(&mut regions, host, i, false),
+                    None => {
+                        push(&mut regions, ext, i, script);
+                        continue;
+                    }
+                }
+                block = None;
+            }
+            None => match opening_block(&lower) {
+                // So is `<script>init();`
+                Some((ext, close, true)) => {
+                    block = Some((ext, close));
+                    push(&mut regions, ext, i, close == "</script");
+                }
+                opened => {
+                    block = opened.map(|(ext, close, _)| (ext, close));
+                    push(&mut regions, host, i, false);
+                }
+            },
+        }
+    }
+    regions
+}
+
+/// The `<script>` or `<style>` block opened on `line` (lowercased) and left
+/// open at its end: the extension of its contents, its closing tag and
+/// whether code follows the opening tag on the same line. Blocks closed on
+/// the same line, and scripts that are not code (`type` `text/template` and
+/// the like), yield `None`.
+fn opening_block(line: &str) -> Option<(&'static str, &'static str, bool)> {
+    for (open, close) in [("<script", "</script"), ("<style", "</style")] {
+        let Some(at) = line.find(open) else {
+            continue;
+        };
+        let rest = &line[at + open.len()..];
+        if !rest.starts_with(|c: char| c.is_whitespace() || c == '>') {
+            continue;
+        }
+        let tag_end = rest.find('>')?;
+        if rest[tag_end..].contains(close) {
+            return None;
+        }
+        let attrs = &rest[..tag_end];
+        let lang = attribute(attrs, "lang").or_else(|| attribute(attrs, "type"));
+        let ext = if open == "<script" {
+            match lang.unwrap_or("js") {
+                "js" | "javascript" | "module" | "text/javascript" | "application/javascript" => {
+                    ".js"
+                }
+                "ts" | "typescript" | "text/typescript" => ".ts",
+                "tsx" => ".tsx",
+                "jsx" => ".jsx",
+                "application/json" | "application/ld+json" | "importmap" => ".json",
+                _ => return None,
+            }
+        } else {
+            match lang.unwrap_or("css") {
+                "css" | "text/css" | "postcss" => ".css",
+                "scss" => ".scss",
+                "sass" => ".sass",
+                "less" => ".less",
+                _ => return None,
+            }
+        };
+        let inline = !rest[tag_end + 1..].trim().is_empty();
+        return Some((ext, close, inline));
+    }
+    None
+}
+
+/// The value of attribute `name` in the attributes of a tag, quoted or not.
+fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let (at, _) = attrs.match_indices(name).find(|&(at, _)| {
+        attrs[..at].ends_with(char::is_whitespace)
+            && attrs[at + name.len()..].trim_start().starts_with('=')
+    })?;
+    let value = attrs[at + name.len()..].trim_start()[1..].trim_start();
+    match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next(),
+        _ => value.split(|c: char| c.is_whitespace() || c == '/').next(),
+    }
+}
+
+fn php(host: &str, lines: &[&str]) -> Vec<Region> {
+    let mut in_php = false;
+    // Per line: whether any of it is PHP, and the markup around the PHP
+    let mut is_php = Vec::with_capacity(lines.len());
+    let mut outside = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        let mut any = in_php;
+        let mut markup_text = String::new();
+        let mut rest = *line;
+        loop {
+            let found = if in_php {
+                rest.find("?>").map(|at| (at, at + 2))
+            } else {
+                // `<?xml` declarations are markup
+                rest.match_indices("<?")
+                    .find(|&(at, _)| !rest[at + 2..].starts_with("xml"))
+                    .map(|(at, _)| (at, at + 2))
+            };
+            let Some((at, next)) = found else {
+                if !in_php {
+                    markup_text.push_str(rest);
+                }
+                break;
+            };
+            if !in_php {
+                markup_text.push_str(&rest[..at]);
+            }
+            in_php = !in_php;
+            any = true;
+            rest = &rest[next..];
+        }
+        is_php.push(any);
+        outside.push(markup_text);
+    }
+
+    // The markup is split like an HTML file, so its script and style blocks
+    // count as JS and CSS; a line is PHP if any of it is
+    let outside: Vec<&str> = outside.iter().map(String::as_str).collect();
+    let mut regions = Vec::new();
+    for region in markup(".html", &outside) {
+        for (i, &php) in is_php
+            .iter()
+            .enumerate()
+            .take(region.end)
+            .skip(region.start)
+        {
+            if php {
+                push(&mut regions, host, i, false);
+            } else {
+                push(&mut regions, &region.ext, i, region.script);
+            }
+        }
+    }
+    regions
+}
+
+fn markdown(host: &str, lines: &[&str]) -> Vec<Region> {
+    let mut regions = Vec::new();
+    // The open fence (its character and length) and the extension of its contents
+    let mut fence: Option<(char, usize, Option<String>)> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        let run = |c: char| trimmed.chars().take_while(|&t| t == c).count();
+        match &fence {
+            Some((c, len, _)) if run(*c) >= *len && trimmed[run(*c)..].trim().is_empty() => {
+                fence = None;
+                push(&mut regions, host, i, false);
+            }
+            Some((_, _, ext)) => push(&mut regions, ext.as_deref().unwrap_or(host), i, false),
+            None => {
+                // Four spaces of indentation make an indented code block instead
+                if line.len() - trimmed.len() < 4
+                    && let Some(c) = ['`', '~'].into_iter().find(|&c| run(c) >= 3)
+                {
+                    let len = run(c);
+                    fence = Some((c, len, fence_language(&trimmed[len..])));
+                }
+                push(&mut regions, host, i, false);
+            }
+        }
+    }
+    regions
+}
+
+/// Extension of the language named by a fence's info string (```` ```rust ````,
+/// ```` ```js title="x" ````, ```` ```{.python} ````), if it is a known one.
+fn fence_language(info: &str) -> Option<String> {
+    let word = info
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | '{' | '}' | '.'))
+        .find(|w| !w.is_empty())?;
+    let ext = resolve_extensions(word).into_iter().next()?;
+    LANGUAGE_MAP
+        .values()
+        .any(|exts| exts.contains(&ext.as_str()))
+        .then_some(ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(ext: &str, src: &str) -> Vec<(String, usize, usize)> {
+        let lines: Vec<&str> = src.lines().collect();
+        split(ext, &lines)
+            .into_iter()
+            .map(|r| (r.ext, r.start, r.end))
+            .collect()
+    }
+
+    fn span(ext: &str, start: usize, end: usize) -> (String, usize, usize) {
+        (ext.to_string(), start, end)
+    }
+
+    #[test]
+    fn test_split_single_file_components() {
+        let src = "<template>\n  <p>{{ n }}</p>\n</template>\n<script setup lang=\"ts\">\nconst n = 1\n</script>\n<style scoped>\np { color: red }\n</style>";
+        assert_eq!(
+            spans(".vue", src),
+            [
+                span(".vue", 0, 4),
+                span(".ts", 4, 5),
+                span(".vue", 5, 7),
+                span(".css", 7, 8),
+                span(".vue", 8, 9),
+            ]
+        );
+        let lines: Vec<&str> = src.lines().collect();
+        assert!(split(".vue", &lines)[1].script);
+
+        // Inline scripts and templates stay with the host
+        let src = "<script>go()</script>\n<script type=\"text/template\">\n<b>x</b>\n</script>";
+        assert_eq!(spans(".html", src), [span(".html", 0, 4)]);
+
+        // Tags sharing a line with code go with the code
+        let src = "<div>\n<script>init();\nrun();\n  done(); </script>\n</div>";
+        assert_eq!(
+            spans(".html", src),
+            [span(".html", 0, 1), span(".js", 1, 4), span(".html", 4, 5)]
+        );
+        let lines: Vec<&str> = src.lines().collect();
+        let script = &split(".html", &lines)[1];
+        assert_eq!(script.source(&lines), "        init();\nrun();\n  done(); ");
+    }
+
+    #[test]
+    fn test_split_php_and_html() {
+        let src = "<?php\n$x = 1;\n?>\n<ul>\n  <li><?= $x ?></li>\n</ul>";
+        assert_eq!(
+            spans(".php", src),
+            [
+                span(".php", 0, 3),
+                span(".html", 3, 4),
+                span(".php", 4, 5),
+                span(".html", 5, 6)
+            ]
+        );
+        assert_eq!(spans(".php", "<?php\necho 1;"), [span(".php", 0, 2)]);
+
+        // The markup of a template has its own script and style blocks
+        let src = "<?php $n = 1; ?>\n<script>\nvar n = <?= $n ?>;\ngo(n);\n</script>\n<style>\nb { }\n</style>";
+        assert_eq!(
+            spans(".php", src),
+            [
+                span(".php", 0, 1),
+                span(".html", 1, 2),
+                span(".php", 2, 3),
+                span(".js", 3, 4),
+                span(".html", 4, 6),
+                span(".css", 6, 7),
+                span(".html", 7, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_markdown_fences() {
+        let src = "# Title\n```rust\nfn main() {}\n```\n~~~~\nplain\n~~~~\n```console\n$ ls\n```";
+        assert_eq!(
+            spans(".md", src),
+            [span(".md", 0, 2), span(".rs", 2, 3), span(".md", 3, 10)]
+        );
+    }
+}
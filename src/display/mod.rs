@@ -5,7 +5,12 @@ use colored::*;
 use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::blame::{BlameReport, Ownership};
+use crate::compare::{ChangeKind, MetricsDelta, ScanDiff};
+use crate::history::History;
+use crate::hotspots::Hotspot;
 use crate::models::{Breakdown, FileInfo, ScanResult};
+use crate::thresholds::Violation;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Helpers
@@ -24,6 +29,21 @@ fn fmt_num(n: usize) -> String {
     result.chars().rev().collect()
 }
 
+fn fmt_delta(n: i64) -> ColoredString {
+    let s = if n > 0 {
+        format!("+{}", fmt_num(n as usize))
+    } else if n < 0 {
+        format!("-{}", fmt_num(n.unsigned_abs() as usize))
+    } else {
+        "0".to_string()
+    };
+    match n.signum() {
+        1 => s.green(),
+        -1 => s.red(),
+        _ => s.dimmed(),
+    }
+}
+
 fn fmt_percent(part: usize, total: usize) -> String {
     if total == 0 {
         return "  0.00%".to_string();
@@ -266,29 +286,31 @@ fn display_breakdown(breakdown: &Breakdown, total_lines: usize, has_functions: b
     println!();
 
     let mut sorted: Vec<_> = breakdown.iter().collect();
-    sorted.sort_by(|a, b| b.1.lines.cmp(&a.1.lines));
+    sorted.sort_by_key(|b| std::cmp::Reverse(b.1.lines));
 
     if has_functions {
         println!(
-            "  {:<18} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "  {:<18} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
             "Extension".dimmed(),
             "Code".dimmed(),
             "Comment".dimmed(),
+            "Doc".dimmed(),
             "Blank".dimmed(),
             "Functions".dimmed(),
             "Share".dimmed()
         );
-        println!("  {}", "─".repeat(74).bright_black());
+        println!("  {}", "─".repeat(85).bright_black());
     } else {
         println!(
-            "  {:<18} {:>10} {:>10} {:>10} {:>10}",
+            "  {:<18} {:>10} {:>10} {:>10} {:>10} {:>10}",
             "Extension".dimmed(),
             "Code".dimmed(),
             "Comment".dimmed(),
+            "Doc".dimmed(),
             "Blank".dimmed(),
             "Share".dimmed()
         );
-        println!("  {}", "─".repeat(62).bright_black());
+        println!("  {}", "─".repeat(73).bright_black());
     }
 
     for (ext, stats) in &sorted {
@@ -303,20 +325,22 @@ fn display_breakdown(breakdown: &Breakdown, total_lines: usize, has_functions: b
 
         if has_functions {
             println!(
-                "  {:<18} {:>10} {:>10} {:>10} {:>10} {:>10}",
+                "  {:<18} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
                 ext_colored,
                 fmt_num(stats.code).bold(),
                 fmt_num(stats.comment).magenta(),
+                fmt_num(stats.doc).cyan(),
                 fmt_num(stats.blank).dimmed(),
                 fmt_num(stats.functions),
                 fmt_percent(stats.lines, total_lines).bright_black(),
             );
         } else {
             println!(
-                "  {:<18} {:>10} {:>10} {:>10} {:>10}",
+                "  {:<18} {:>10} {:>10} {:>10} {:>10} {:>10}",
                 ext_colored,
                 fmt_num(stats.code).bold(),
                 fmt_num(stats.comment).magenta(),
+                fmt_num(stats.doc).cyan(),
                 fmt_num(stats.blank).dimmed(),
                 fmt_percent(stats.lines, total_lines).bright_black(),
             );
@@ -347,6 +371,17 @@ pub fn display_function_analysis(result: &ScanResult, root: &Path) {
     display_overall_stats(result, &files_with_fns);
     display_largest_functions(&files_with_fns, root);
     display_complex_functions(&files_with_fns, root);
+    display_least_maintainable(&files_with_fns, root);
+    display_ranked(
+        &files_with_fns,
+        root,
+        "Deepest Nested Functions:",
+        "Depth",
+        |f| f.max_nesting as usize,
+    );
+    display_ranked(&files_with_fns, root, "Most Parameters:", "Params", |f| {
+        f.parameters.len()
+    });
     display_top_files(&files_with_fns, root);
 
     println!("{}", "=".repeat(90));
@@ -370,7 +405,15 @@ fn display_overall_stats(result: &ScanResult, files_with_fns: &[&FileInfo]) {
     println!("{}", "Overall Statistics:".bold());
     println!("  Total Functions/Methods : {}", fmt_num(total_fns));
     println!("  Total Classes/Structs   : {}", fmt_num(total_cls));
-    println!("  Average Function Length : {:.1} lines\n", avg_len);
+    println!("  Average Function Length : {:.1} lines", avg_len);
+    println!(
+        "  Documented Functions    : {:.1}%",
+        result.documented_percent()
+    );
+    if let Some(mi) = result.maintainability_index() {
+        println!("  Maintainability Index   : {:.1} / 100", mi);
+    }
+    println!();
 }
 
 fn display_largest_functions(files_with_fns: &[&FileInfo], root: &Path) {
@@ -383,7 +426,7 @@ fn display_largest_functions(files_with_fns: &[&FileInfo], root: &Path) {
                 .map(move |f| (fi.path.as_path(), f))
         })
         .collect();
-    all_fns.sort_by(|a, b| b.1.line_count().cmp(&a.1.line_count()));
+    all_fns.sort_by_key(|b| std::cmp::Reverse(b.1.line_count()));
 
     if all_fns.is_empty() {
         return;
@@ -412,7 +455,7 @@ fn display_largest_functions(files_with_fns: &[&FileInfo], root: &Path) {
 
         println!(
             "{:<42} {:<32} {:>8} {}",
-            truncate(&func.name, 40),
+            truncate(func.full_name(), 40),
             truncate(&rel, 30),
             fmt_num(func.line_count()),
             complexity_str
@@ -427,7 +470,7 @@ fn display_complex_functions(files_with_fns: &[&FileInfo], root: &Path) {
         .flat_map(|fi| {
             fi.functions
                 .iter()
-                .filter(|f| !f.is_class && f.complexity > 10)
+                .filter(|f| !f.is_class && (f.complexity > 10 || f.cognitive_complexity > 15))
                 .map(move |f| (fi.path.as_path(), f))
         })
         .collect();
@@ -436,10 +479,18 @@ fn display_complex_functions(files_with_fns: &[&FileInfo], root: &Path) {
         return;
     }
 
-    complex_fns.sort_by(|a, b| b.1.complexity.cmp(&a.1.complexity));
-    println!("{}", "High Complexity Functions (>10):".bold());
-    println!("{:<42} {:<32} {:>12}", "Function", "File", "Complexity");
-    println!("{}", "-".repeat(86));
+    complex_fns.sort_by(|a, b| {
+        (b.1.complexity, b.1.cognitive_complexity).cmp(&(a.1.complexity, a.1.cognitive_complexity))
+    });
+    println!(
+        "{}",
+        "High Complexity Functions (cyclomatic >10 or cognitive >15):".bold()
+    );
+    println!(
+        "{:<42} {:<32} {:>12} {:>11}",
+        "Function", "File", "Complexity", "Cognitive"
+    );
+    println!("{}", "-".repeat(98));
 
     for (path, func) in complex_fns.iter().take(15) {
         let rel = path
@@ -447,11 +498,113 @@ fn display_complex_functions(files_with_fns: &[&FileInfo], root: &Path) {
             .map(|p| p.display().to_string())
             .unwrap_or_else(|_| path.display().to_string());
 
+        // Only the metric over its limit is highlighted
+        let over = |text: String, flagged: bool| if flagged { text.red() } else { text.normal() };
         println!(
-            "{:<42} {:<32} {}",
-            truncate(&func.name, 40),
+            "{:<42} {:<32} {} {}",
+            truncate(func.full_name(), 40),
             truncate(&rel, 30),
-            format!("{:>12}", func.complexity).red()
+            over(format!("{:>12}", func.complexity), func.complexity > 10),
+            over(
+                format!("{:>11}", func.cognitive_complexity),
+                func.cognitive_complexity > 15
+            )
+        );
+    }
+    println!();
+}
+
+fn display_least_maintainable(files_with_fns: &[&FileInfo], root: &Path) {
+    let mut all_fns: Vec<_> = files_with_fns
+        .iter()
+        .flat_map(|fi| {
+            fi.functions
+                .iter()
+                .filter(|f| !f.is_class)
+                .map(move |f| (fi.path.as_path(), f, f.maintainability_index()))
+        })
+        .collect();
+
+    if all_fns.is_empty() {
+        return;
+    }
+
+    all_fns.sort_by(|a, b| a.2.total_cmp(&b.2));
+    println!("{}", "Least Maintainable Functions:".bold());
+    println!(
+        "{:<42} {:<32} {:>8} {:>10} {:>12}",
+        "Function", "File", "MI", "Volume", "Effort"
+    );
+    println!("{}", "-".repeat(108));
+
+    for (path, func, mi) in all_fns.iter().take(10) {
+        let rel = path
+            .strip_prefix(root)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| path.display().to_string());
+
+        // Visual Studio's bands: red below 10, yellow below 20
+        let mi_str = format!("{:>8.1}", mi);
+        let mi_str = if *mi < 10.0 {
+            mi_str.red()
+        } else if *mi < 20.0 {
+            mi_str.yellow()
+        } else {
+            mi_str.green()
+        };
+
+        println!(
+            "{:<42} {:<32} {} {:>10.0} {:>12.0}",
+            truncate(func.full_name(), 40),
+            truncate(&rel, 30),
+            mi_str,
+            func.halstead.volume(),
+            func.halstead.effort()
+        );
+    }
+    println!();
+}
+
+/// Top 10 non-class functions by `metric`, skipping those where it is 0.
+fn display_ranked(
+    files_with_fns: &[&FileInfo],
+    root: &Path,
+    title: &str,
+    column: &str,
+    metric: impl Fn(&crate::models::FunctionInfo) -> usize,
+) {
+    let mut ranked: Vec<_> = files_with_fns
+        .iter()
+        .flat_map(|fi| {
+            fi.functions
+                .iter()
+                .filter(|f| !f.is_class)
+                .map(move |f| (fi.path.as_path(), f))
+        })
+        .map(|(path, f)| (path, f, metric(f)))
+        .filter(|(_, _, value)| *value > 0)
+        .collect();
+
+    if ranked.is_empty() {
+        return;
+    }
+
+    ranked.sort_by_key(|b| std::cmp::Reverse(b.2));
+    println!("{}", title.bold());
+    println!("{:<42} {:<32} {:>8}", "Function", "File", column);
+    println!("{}", "-".repeat(84));
+
+    for (path, func, value) in ranked.iter().take(10) {
+        let rel = path
+            .strip_prefix(root)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| path.display().to_string());
+
+        println!(
+            "{:<42} {:<32} {:>8}",
+            truncate(func.full_name(), 40),
+            truncate(&rel, 30),
+            value
         );
     }
     println!();
@@ -472,10 +625,11 @@ fn display_top_files(files_with_fns: &[&FileInfo], root: &Path) {
 
         println!("{}", rel.cyan());
         println!(
-            "  Functions: {}, Classes: {}, Avg length: {:.1} lines",
+            "  Functions: {}, Classes: {}, Avg length: {:.1} lines, Documented: {:.0}%",
             fi.function_count(),
             fi.class_count(),
-            fi.avg_function_length()
+            fi.avg_function_length(),
+            fi.documented_percent()
         );
 
         for func in fi.functions.iter().take(5) {
@@ -520,6 +674,343 @@ fn display_top_files(files_with_fns: &[&FileInfo], root: &Path) {
     }
 }
 
+pub fn display_comparison(diff: &ScanDiff) {
+    println!(
+        "{} {}",
+        "Changes vs Baseline:".bold().underline(),
+        diff.baseline.display().to_string().dimmed()
+    );
+    println!();
+
+    let t = &diff.total_delta;
+    println!(
+        "  Lines      : {:>10} → {:<10} ({})",
+        fmt_num(diff.total_before.lines),
+        fmt_num(diff.total_after.lines),
+        fmt_delta(t.lines)
+    );
+    println!(
+        "  Code / Comment / Blank : {} / {} / {}",
+        fmt_delta(t.code),
+        fmt_delta(t.comment),
+        fmt_delta(t.blank)
+    );
+    println!(
+        "  Functions  : {}   Complexity : {}",
+        fmt_delta(t.functions),
+        fmt_delta(t.complexity)
+    );
+    println!(
+        "  Files      : {} added, {} removed, {} modified",
+        diff.count(ChangeKind::Added).to_string().green(),
+        diff.count(ChangeKind::Removed).to_string().red(),
+        diff.count(ChangeKind::Modified).to_string().yellow()
+    );
+    println!();
+
+    let changed_exts: Vec<_> = diff
+        .extensions
+        .iter()
+        .filter(|e| !e.delta.is_zero())
+        .collect();
+    if !changed_exts.is_empty() {
+        let mut sorted = changed_exts;
+        sorted.sort_by_key(|e| std::cmp::Reverse(e.delta.lines.unsigned_abs()));
+
+        println!(
+            "  {:<18} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "Extension".dimmed(),
+            "Files".dimmed(),
+            "Lines".dimmed(),
+            "Code".dimmed(),
+            "Comment".dimmed(),
+            "Blank".dimmed(),
+            "Functions".dimmed(),
+            "Complexity".dimmed()
+        );
+        println!("  {}", "─".repeat(94).bright_black());
+        for e in sorted {
+            print_delta_row(&e.extension, 18, &e.delta);
+        }
+        println!();
+    }
+
+    if !diff.files.is_empty() {
+        let mut files: Vec<_> = diff.files.iter().collect();
+        files.sort_by_key(|f| std::cmp::Reverse(f.delta.lines.unsigned_abs()));
+
+        println!("{}", "Top Changed Files:".bold());
+        println!(
+            "  {:<42} {:<9} {:>10} {:>10} {:>10} {:>10}",
+            "File".dimmed(),
+            "Change".dimmed(),
+            "Lines".dimmed(),
+            "Code".dimmed(),
+            "Functions".dimmed(),
+            "Complexity".dimmed()
+        );
+        println!("  {}", "─".repeat(98).bright_black());
+        for f in files.iter().take(20) {
+            let kind = match f.kind {
+                ChangeKind::Added => "added".green(),
+                ChangeKind::Removed => "removed".red(),
+                ChangeKind::Modified => "modified".yellow(),
+                ChangeKind::Unchanged => "unchanged".dimmed(),
+            };
+            println!(
+                "  {:<42} {:<9} {:>10} {:>10} {:>10} {:>10}",
+                truncate(&f.path.display().to_string(), 40),
+                kind,
+                fmt_delta(f.delta.lines),
+                fmt_delta(f.delta.code),
+                fmt_delta(f.delta.functions),
+                fmt_delta(f.delta.complexity)
+            );
+        }
+        if files.len() > 20 {
+            println!("  {} and {} more ...", "~".dimmed(), files.len() - 20);
+        }
+        println!();
+    }
+}
+
+fn print_delta_row(label: &str, width: usize, d: &MetricsDelta) {
+    println!(
+        "  {:<width$} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        label,
+        fmt_delta(d.files),
+        fmt_delta(d.lines),
+        fmt_delta(d.code),
+        fmt_delta(d.comment),
+        fmt_delta(d.blank),
+        fmt_delta(d.functions),
+        fmt_delta(d.complexity),
+        width = width
+    );
+}
+
+pub fn display_violations(violations: &[Violation], root: &Path) {
+    if violations.is_empty() {
+        println!("{}", "[PASS] All quality gates passed.".green().bold());
+        println!();
+        return;
+    }
+
+    println!(
+        "{}",
+        format!("[FAIL] {} quality gate violation(s):", violations.len())
+            .red()
+            .bold()
+    );
+    for v in violations {
+        let rel = v
+            .path
+            .strip_prefix(root)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| v.path.display().to_string());
+        let location = match v.line {
+            Some(line) => format!("{}:{}", rel, line),
+            None => rel,
+        };
+        println!(
+            "  {}  {}  {}",
+            location.cyan(),
+            format!("[{}]", v.rule.id()).yellow(),
+            v.message
+        );
+    }
+    println!();
+}
+
+pub fn display_hotspots(hotspots: &[Hotspot], root: &Path) {
+    println!(
+        "{} {}",
+        "Hotspots:".bold().underline(),
+        "(churn × complexity)".dimmed()
+    );
+    println!();
+
+    if hotspots.is_empty() {
+        println!("  {}", "No committed files to rank.".dimmed());
+        println!();
+        return;
+    }
+
+    println!(
+        "  {:>3}  {:<40} {:>7} {:>8} {:>8} {:>6} {:>6} {:>6}",
+        "#".dimmed(),
+        "File".dimmed(),
+        "Score".dimmed(),
+        "Commits".dimmed(),
+        "Churn".dimmed(),
+        "Code".dimmed(),
+        "MaxCx".dimmed(),
+        "SumCx".dimmed()
+    );
+    println!("  {}", "─".repeat(92).bright_black());
+
+    for (i, h) in hotspots.iter().enumerate() {
+        let rel = h
+            .path
+            .strip_prefix(root)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| h.path.display().to_string());
+        let score = format!("{:.3}", h.score);
+        let score = if h.score >= 0.5 {
+            score.red().bold()
+        } else if h.score >= 0.2 {
+            score.yellow()
+        } else {
+            score.normal()
+        };
+        println!(
+            "  {:>3}  {:<40} {:>7} {:>8} {:>8} {:>6} {:>6} {:>6}",
+            i + 1,
+            truncate(&rel, 40).cyan(),
+            score,
+            fmt_num(h.commits),
+            fmt_num(h.churn),
+            fmt_num(h.code),
+            h.max_complexity,
+            h.total_complexity
+        );
+    }
+    println!();
+}
+
+pub fn display_blame(report: &BlameReport, root: &Path) {
+    let total = &report.total;
+    println!(
+        "{} {}",
+        "Code Ownership:".bold().underline(),
+        format!(
+            "{} code lines, {} authors, bus factor {}",
+            fmt_num(total.lines),
+            total.authors.len(),
+            total.bus_factor
+        )
+        .dimmed()
+    );
+    println!();
+
+    let ranked = total.ranked();
+    for (author, lines) in ranked.iter().take(10) {
+        println!(
+            "  {:<30} {:>10} {}",
+            truncate(author, 30).cyan(),
+            fmt_num(*lines),
+            fmt_percent(*lines, total.lines).dimmed()
+        );
+    }
+    if ranked.len() > 10 {
+        println!("  {} and {} more ...", "~".dimmed(), ranked.len() - 10);
+    }
+    println!();
+
+    // Top-level directories only; the export has every level
+    let top_dirs: Vec<_> = report
+        .directories
+        .iter()
+        .filter(|(d, _)| d.parent() == Some(root))
+        .map(|(d, own)| {
+            let name = d.strip_prefix(root).unwrap_or(d).display().to_string() + "/";
+            (name, own)
+        })
+        .collect();
+    print_ownership_table("Directory", top_dirs);
+
+    let exts: Vec<_> = report
+        .extensions
+        .iter()
+        .map(|(e, own)| (e.clone(), own))
+        .collect();
+    print_ownership_table("Extension", exts);
+}
+
+fn print_ownership_table(label: &str, mut rows: Vec<(String, &Ownership)>) {
+    if rows.is_empty() {
+        return;
+    }
+    rows.sort_by_key(|(_, own)| std::cmp::Reverse(own.lines));
+
+    println!(
+        "  {:<28} {:>10} {:<24} {:>8} {:>8} {:>6}",
+        label.dimmed(),
+        "Code".dimmed(),
+        "Top Owner".dimmed(),
+        "Share".dimmed(),
+        "Authors".dimmed(),
+        "Bus".dimmed()
+    );
+    println!("  {}", "─".repeat(90).bright_black());
+    for (name, own) in rows.iter().take(15) {
+        let (top, top_lines) = own.ranked().first().copied().unwrap_or(("-", 0));
+        let bus = own.bus_factor.to_string();
+        let bus = if own.bus_factor <= 1 {
+            bus.red().bold()
+        } else {
+            bus.normal()
+        };
+        println!(
+            "  {:<28} {:>10} {:<24} {:>8} {:>8} {:>6}",
+            truncate(name, 28),
+            fmt_num(own.lines),
+            truncate(top, 24),
+            fmt_percent(top_lines, own.lines),
+            own.authors.len(),
+            bus
+        );
+    }
+    if rows.len() > 15 {
+        println!("  {} and {} more ...", "~".dimmed(), rows.len() - 15);
+    }
+    println!();
+}
+
+pub fn display_history(history: &History) {
+    println!(
+        "{} {}",
+        "LOC History:".bold().underline(),
+        format!("{} revisions of {}", history.points.len(), history.rev).dimmed()
+    );
+    println!();
+
+    if history.points.is_empty() {
+        println!("  {}", "No commits found.".dimmed());
+        println!();
+        return;
+    }
+
+    println!(
+        "  {:<12} {:<10} {:>8} {:>12} {:>12} {:>12} {:>10}",
+        "Date".dimmed(),
+        "Commit".dimmed(),
+        "Files".dimmed(),
+        "Lines".dimmed(),
+        "Code".dimmed(),
+        "Comment".dimmed(),
+        "Δ Lines".dimmed()
+    );
+    println!("  {}", "─".repeat(82).bright_black());
+
+    let mut prev_lines = None;
+    for p in &history.points {
+        let delta = prev_lines.map(|prev| p.total.lines as i64 - prev as i64);
+        println!(
+            "  {:<12} {:<10} {:>8} {:>12} {:>12} {:>12} {:>10}",
+            p.date.format("%Y-%m-%d").to_string(),
+            p.commit[..p.commit.len().min(8)].yellow(),
+            fmt_num(p.total.files),
+            fmt_num(p.total.lines),
+            fmt_num(p.total.code),
+            fmt_num(p.total.comment),
+            delta.map(fmt_delta).unwrap_or_else(|| "-".dimmed())
+        );
+        prev_lines = Some(p.total.lines);
+    }
+    println!();
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Utility
 // ─────────────────────────────────────────────────────────────────────────────
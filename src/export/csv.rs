@@ -1,30 +1,68 @@
 // Author: kelexine (https://github.com/kelexine)
 // export/csv.rs — CSV export logic
 
+use super::ReportSections;
 use crate::models::ScanResult;
 use anyhow::{Context, Result};
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
-pub fn export_csv(result: &ScanResult, path: &Path, include_functions: bool) -> Result<()> {
+pub fn export_csv(
+    result: &ScanResult,
+    path: &Path,
+    include_functions: bool,
+    sections: &ReportSections,
+) -> Result<()> {
     let f = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
-    let mut wtr = csv::Writer::from_writer(BufWriter::new(f));
+    write_csv_report(result, sections, BufWriter::new(f), include_functions)?;
+
+    println!("[SUCCESS] Exported CSV → {}", path.display());
+    Ok(())
+}
+
+/// Write one CSV row per text file, with function columns when `include_functions` is set.
+pub fn write_csv<W: Write>(result: &ScanResult, writer: W, include_functions: bool) -> Result<()> {
+    write_csv_report(
+        result,
+        &ReportSections::default(),
+        writer,
+        include_functions,
+    )
+}
+
+/// Like [`write_csv`], appending ownership columns when a blame section is present.
+pub fn write_csv_report<W: Write>(
+    result: &ScanResult,
+    sections: &ReportSections,
+    writer: W,
+    include_functions: bool,
+) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(writer);
 
     // Header
-    if include_functions {
-        wtr.write_record([
+    let mut header = if include_functions {
+        vec![
             "Path",
             "Lines",
             "Extension",
             "Functions",
             "Classes",
             "Avg Fn Length",
+            "Documented %",
+            "Max Complexity",
+            "Max Cognitive",
+            "Maintainability",
             "Last Modified",
-        ])?;
+        ]
     } else {
-        wtr.write_record(["Path", "Lines", "Extension", "Last Modified"])?;
+        vec!["Path", "Lines", "Extension", "Last Modified"]
+    };
+    header.extend(["Code", "Comment", "Doc", "Blank"]);
+    if sections.blame.is_some() {
+        header.extend(["Top Author", "Top Author Share", "Authors", "Bus Factor"]);
     }
+    wtr.write_record(&header)?;
 
     for fi in result.files.iter().filter(|f| !f.is_binary) {
         let last_mod = fi
@@ -32,27 +70,45 @@ pub fn export_csv(result: &ScanResult, path: &Path, include_functions: bool) ->
             .map(|d| d.format("%Y-%m-%dT%H:%M:%SZ").to_string())
             .unwrap_or_default();
 
+        let mut row = vec![
+            fi.path.to_string_lossy().into_owned(),
+            fi.lines.to_string(),
+            fi.extension().to_string(),
+        ];
         if include_functions {
-            wtr.write_record([
-                fi.path.to_string_lossy().as_ref(),
-                &fi.lines.to_string(),
-                fi.extension(),
-                &fi.function_count().to_string(),
-                &fi.class_count().to_string(),
-                &format!("{:.2}", fi.avg_function_length()),
-                &last_mod,
-            ])?;
-        } else {
-            wtr.write_record([
-                fi.path.to_string_lossy().as_ref(),
-                &fi.lines.to_string(),
-                fi.extension(),
-                &last_mod,
-            ])?;
+            row.extend([
+                fi.function_count().to_string(),
+                fi.class_count().to_string(),
+                format!("{:.2}", fi.avg_function_length()),
+                format!("{:.2}", fi.documented_percent()),
+                fi.max_complexity().to_string(),
+                fi.max_cognitive_complexity().to_string(),
+                fi.maintainability_index()
+                    .map(|mi| format!("{:.2}", mi))
+                    .unwrap_or_default(),
+            ]);
         }
+        row.push(last_mod);
+        row.extend([
+            fi.code.to_string(),
+            fi.comment.to_string(),
+            fi.doc.to_string(),
+            fi.blank.to_string(),
+        ]);
+
+        if let Some(blame) = sections.blame {
+            let own = blame.files.get(&fi.path).cloned().unwrap_or_default();
+            let (top, top_lines) = own.ranked().first().copied().unwrap_or(("", 0));
+            row.extend([
+                top.to_string(),
+                format!("{:.4}", own.share(top_lines)),
+                own.authors.len().to_string(),
+                own.bus_factor.to_string(),
+            ]);
+        }
+        wtr.write_record(&row)?;
     }
 
     wtr.flush()?;
-    println!("[SUCCESS] Exported CSV → {}", path.display());
     Ok(())
 }
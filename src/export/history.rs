@@ -0,0 +1,261 @@
+// Author: kelexine (https://github.com/kelexine)
+// export/history.rs — Time-series export for `--history`
+
+use super::ExportFormat;
+use crate::history::History;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde_json::json;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+pub fn export_history(history: &History, output_path: &str) -> Result<()> {
+    let path = Path::new(output_path);
+    let format = match ExportFormat::from_path(path) {
+        Some(
+            f @ (ExportFormat::Json | ExportFormat::Jsonl | ExportFormat::Csv | ExportFormat::Html),
+        ) => f,
+        _ => anyhow::bail!(
+            "Unsupported history export format '{}'. Use .json, .jsonl, .csv, or .html",
+            path.extension().and_then(|e| e.to_str()).unwrap_or("?")
+        ),
+    };
+
+    let f = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
+    write_history(history, format, BufWriter::new(f))?;
+
+    println!("[SUCCESS] Exported history → {}", path.display());
+    Ok(())
+}
+
+/// Write the time series in `format`. SARIF has no time-series form and is rejected.
+pub fn write_history<W: Write>(history: &History, format: ExportFormat, writer: W) -> Result<()> {
+    match format {
+        ExportFormat::Json => write_history_json(history, writer),
+        ExportFormat::Jsonl => write_history_jsonl(history, writer),
+        ExportFormat::Csv => write_history_csv(history, writer),
+        ExportFormat::Html => write_history_html(history, writer),
+        ExportFormat::Sarif => anyhow::bail!("History cannot be exported as SARIF"),
+    }
+}
+
+fn history_value(history: &History) -> serde_json::Value {
+    json!({
+        "metadata": {
+            "root": history.root.to_string_lossy(),
+            "rev": history.rev,
+            "points": history.points.len(),
+            "timestamp": Utc::now().to_rfc3339(),
+            "generator": concat!("loc v", env!("CARGO_PKG_VERSION")),
+        },
+        "history": history.points,
+    })
+}
+
+/// Pretty-printed JSON: `metadata` plus a `history` array, oldest point first.
+pub fn write_history_json<W: Write>(history: &History, mut writer: W) -> Result<()> {
+    serde_json::to_writer_pretty(&mut writer, &history_value(history))
+        .with_context(|| "Failed to serialize history JSON")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// One JSON object per sampled revision, newline-delimited.
+pub fn write_history_jsonl<W: Write>(history: &History, mut writer: W) -> Result<()> {
+    for point in &history.points {
+        let line =
+            serde_json::to_string(point).with_context(|| "Failed to serialize history record")?;
+        writeln!(writer, "{}", line)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Long-format CSV: per revision, one `TOTAL` row followed by one row per extension.
+pub fn write_history_csv<W: Write>(history: &History, writer: W) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(writer);
+    wtr.write_record([
+        "Commit",
+        "Date",
+        "Extension",
+        "Files",
+        "Lines",
+        "Code",
+        "Comment",
+        "Blank",
+        "Functions",
+        "Complexity",
+    ])?;
+
+    for point in &history.points {
+        let date = point.date.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let rows = std::iter::once(("TOTAL", &point.total))
+            .chain(point.breakdown.iter().map(|(ext, m)| (ext.as_str(), m)));
+        for (ext, m) in rows {
+            wtr.write_record([
+                point.commit.as_str(),
+                &date,
+                ext,
+                &m.files.to_string(),
+                &m.lines.to_string(),
+                &m.code.to_string(),
+                &m.comment.to_string(),
+                &m.blank.to_string(),
+                &m.functions.to_string(),
+                &m.complexity.to_string(),
+            ])?;
+        }
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Self-contained HTML page with line charts of totals and the largest extensions.
+pub fn write_history_html<W: Write>(history: &History, mut writer: W) -> Result<()> {
+    let json_data = serde_json::to_string(&history_value(history))?;
+    let html_content = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>loc-rs | History</title>
+    <script src="https://cdn.jsdelivr.net/npm/chart.js"></script>
+    <style>
+        :root {{
+            --bg: #0f172a;
+            --card-bg: #1e293b;
+            --text-primary: #f8fafc;
+            --text-secondary: #94a3b8;
+            --accent: #38bdf8;
+            --border: #334155;
+        }}
+
+        * {{ box-sizing: border-box; margin: 0; padding: 0; }}
+        body {{
+            font-family: 'Inter', -apple-system, sans-serif;
+            background-color: var(--bg);
+            color: var(--text-primary);
+            line-height: 1.5;
+            padding: 2rem;
+        }}
+
+        .container {{ max-width: 1200px; margin: 0 auto; }}
+
+        header {{
+            display: flex;
+            justify-content: space-between;
+            align-items: center;
+            margin-bottom: 2rem;
+            padding-bottom: 1rem;
+            border-bottom: 1px solid var(--border);
+        }}
+
+        h1 {{ font-size: 1.5rem; font-weight: 700; color: var(--accent); }}
+        .timestamp {{ font-size: 0.875rem; color: var(--text-secondary); }}
+
+        .chart-container {{
+            background: var(--card-bg);
+            padding: 1.5rem;
+            border-radius: 0.75rem;
+            border: 1px solid var(--border);
+            height: 400px;
+            margin-bottom: 2rem;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <header>
+            <h1>loc-rs History</h1>
+            <div id="subtitle" class="timestamp"></div>
+        </header>
+
+        <div class="chart-container">
+            <canvas id="totalChart"></canvas>
+        </div>
+        <div class="chart-container">
+            <canvas id="extensionChart"></canvas>
+        </div>
+    </div>
+
+    <script>
+        const reportData = {data};
+        const points = reportData.history;
+        const palette = [
+            '#38bdf8', '#818cf8', '#c084fc', '#f472b6', '#fb7185',
+            '#fb923c', '#fbbf24', '#a3e635', '#4ade80', '#2dd4bf'
+        ];
+
+        document.getElementById('subtitle').textContent =
+            `${{points.length}} revisions of ${{reportData.metadata.rev}} · Generated: ` +
+            new Date(reportData.metadata.timestamp).toLocaleString();
+
+        const labels = points.map(p => new Date(p.date).toLocaleDateString());
+        const tooltip = {{
+            callbacks: {{ title: items => points[items[0].dataIndex].commit.slice(0, 10) + ' · ' + labels[items[0].dataIndex] }}
+        }};
+        const options = title => ({{
+            maintainAspectRatio: false,
+            interaction: {{ mode: 'index', intersect: false }},
+            scales: {{
+                x: {{ ticks: {{ color: '#94a3b8', maxTicksLimit: 12 }}, grid: {{ color: '#334155' }} }},
+                y: {{ ticks: {{ color: '#94a3b8' }}, grid: {{ color: '#334155' }}, beginAtZero: true }}
+            }},
+            plugins: {{
+                legend: {{ position: 'bottom', labels: {{ color: '#94a3b8' }} }},
+                title: {{ display: true, text: title, color: '#f8fafc', font: {{ size: 16 }} }},
+                tooltip: tooltip
+            }}
+        }});
+        const line = (label, data, i) => ({{
+            label: label,
+            data: data,
+            borderColor: palette[i % palette.length],
+            backgroundColor: palette[i % palette.length],
+            pointRadius: points.length > 60 ? 0 : 2,
+            tension: 0.2
+        }});
+
+        // Totals
+        new Chart(document.getElementById('totalChart'), {{
+            type: 'line',
+            data: {{
+                labels: labels,
+                datasets: [
+                    line('Lines', points.map(p => p.total.lines), 0),
+                    line('Code', points.map(p => p.total.code), 1),
+                    line('Comment', points.map(p => p.total.comment), 2),
+                    line('Blank', points.map(p => p.total.blank), 3)
+                ]
+            }},
+            options: options('Total Lines Over Time')
+        }});
+
+        // Largest extensions at the newest revision
+        const latest = points.length ? points[points.length - 1].breakdown : {{}};
+        const top = Object.keys(latest)
+            .sort((a, b) => latest[b].lines - latest[a].lines)
+            .slice(0, 8);
+        new Chart(document.getElementById('extensionChart'), {{
+            type: 'line',
+            data: {{
+                labels: labels,
+                datasets: top.map((ext, i) =>
+                    line(ext, points.map(p => p.breakdown[ext] ? p.breakdown[ext].lines : 0), i))
+            }},
+            options: options('Lines by Extension')
+        }});
+    </script>
+</body>
+</html>
+"#,
+        data = json_data
+    );
+
+    writer.write_all(html_content.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
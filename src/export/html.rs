@@ -1,6 +1,7 @@
 // Author: kelexine (https://github.com/kelexine)
 // export/html.rs — HTML visual report export logic
 
+use super::ReportSections;
 use super::json::file_to_value;
 use crate::models::ScanResult;
 use anyhow::{Context, Result};
@@ -10,16 +11,49 @@ use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
-pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) -> Result<()> {
+pub fn export_html(
+    result: &ScanResult,
+    path: &Path,
+    extract_functions: bool,
+    sections: &ReportSections,
+) -> Result<()> {
+    let f = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
+    write_html_report(result, sections, BufWriter::new(f), extract_functions)?;
+
+    println!("[SUCCESS] Exported HTML Visual Report → {}", path.display());
+    Ok(())
+}
+
+/// Render the self-contained HTML dashboard (Chart.js loaded from CDN).
+pub fn write_html<W: Write>(result: &ScanResult, writer: W, extract_functions: bool) -> Result<()> {
+    write_html_report(
+        result,
+        &ReportSections::default(),
+        writer,
+        extract_functions,
+    )
+}
+
+/// Like [`write_html`], rendering extra panels for any optional sections present.
+pub fn write_html_report<W: Write>(
+    result: &ScanResult,
+    sections: &ReportSections,
+    mut writer: W,
+    extract_functions: bool,
+) -> Result<()> {
     let text_files: Vec<_> = result.files.iter().filter(|f| !f.is_binary).collect();
 
     // Prepare the data to inject into JS
-    let data = json!({
+    let mut data = json!({
         "metadata": {
             "total_lines": result.total_lines(),
             "total_files": result.text_file_count(),
             "total_functions": result.total_functions(),
             "total_classes": result.total_classes(),
+            "total_doc": result.total_doc(),
+            "documented_functions_pct": (result.documented_percent() * 100.0).round() / 100.0,
+            "maintainability_index": result.maintainability_index().map(|mi| (mi * 100.0).round() / 100.0),
+            "root": result.root.to_string_lossy(),
             "timestamp": Utc::now().to_rfc3339(),
             "function_extraction_enabled": extract_functions,
             "generator": concat!("loc v", env!("CARGO_PKG_VERSION")),
@@ -27,6 +61,7 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
         "breakdown": result.breakdown,
         "files": text_files.iter().map(|f| file_to_value(f, extract_functions)).collect::<Vec<_>>(),
     });
+    sections.insert_into(&mut data);
 
     let json_data = serde_json::to_string(&data)?;
     let html_content = format!(
@@ -144,6 +179,12 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
         }}
         input[type="text"]:focus {{ border-color: var(--accent); }}
 
+        .section {{ margin-bottom: 2rem; }}
+        .section h2 {{ font-size: 1.125rem; margin-bottom: 1rem; color: var(--text-primary); }}
+        .delta-pos {{ color: var(--success); }}
+        .delta-neg {{ color: #ef4444; }}
+        .delta-zero {{ color: var(--text-secondary); }}
+
     </style>
 </head>
 <body>
@@ -170,6 +211,18 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
                 <span id="totalClasses" class="stat-value">-</span>
                 <span class="stat-label">Classes</span>
             </div>
+            <div class="stat-card">
+                <span id="totalDoc" class="stat-value">-</span>
+                <span class="stat-label">Doc Lines</span>
+            </div>
+            <div class="stat-card">
+                <span id="documentedPct" class="stat-value">-</span>
+                <span class="stat-label">Documented Functions</span>
+            </div>
+            <div class="stat-card">
+                <span id="maintainability" class="stat-value">-</span>
+                <span class="stat-label">Maintainability Index</span>
+            </div>
         </div>
 
         <div class="charts-row">
@@ -187,6 +240,8 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
                                 <th>Path</th>
                                 <th>Lines</th>
                                 <th id="complexityHeader">Max Complexity</th>
+                                <th>Max Cognitive</th>
+                                <th>MI</th>
                             </tr>
                         </thead>
                         <tbody id="fileTableBody"></tbody>
@@ -194,6 +249,39 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
                 </div>
             </div>
         </div>
+
+        <div id="comparisonSection" class="section" style="display: none;">
+            <h2 id="comparisonTitle">Changes vs Baseline</h2>
+            <div class="stats-grid" id="comparisonStats"></div>
+            <div class="table-container" style="margin-bottom: 1rem;">
+                <table>
+                    <thead>
+                        <tr><th>Extension</th><th>Files</th><th>Lines</th><th>Code</th><th>Comment</th><th>Blank</th><th>Functions</th><th>Complexity</th></tr>
+                    </thead>
+                    <tbody id="comparisonExtBody"></tbody>
+                </table>
+            </div>
+            <div class="table-container">
+                <table>
+                    <thead>
+                        <tr><th>Path</th><th>Change</th><th>Lines</th><th>Code</th><th>Functions</th><th>Complexity</th></tr>
+                    </thead>
+                    <tbody id="comparisonFileBody"></tbody>
+                </table>
+            </div>
+        </div>
+
+        <div id="hotspotsSection" class="section" style="display: none;">
+            <h2>Hotspots (churn × complexity)</h2>
+            <div class="table-container">
+                <table>
+                    <thead>
+                        <tr><th>#</th><th>Path</th><th>Score</th><th>Commits</th><th>Churn</th><th>Code</th><th>Max Complexity</th><th>Total Complexity</th></tr>
+                    </thead>
+                    <tbody id="hotspotsBody"></tbody>
+                </table>
+            </div>
+        </div>
     </div>
 
     <script>
@@ -205,6 +293,13 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
         document.getElementById('totalFiles').textContent = reportData.metadata.total_files.toLocaleString();
         document.getElementById('totalFunctions').textContent = reportData.metadata.total_functions.toLocaleString();
         document.getElementById('totalClasses').textContent = reportData.metadata.total_classes.toLocaleString();
+        document.getElementById('totalDoc').textContent = reportData.metadata.total_doc.toLocaleString();
+        document.getElementById('documentedPct').textContent = reportData.metadata.function_extraction_enabled
+            ? reportData.metadata.documented_functions_pct.toFixed(1) + '%'
+            : '-';
+        document.getElementById('maintainability').textContent = reportData.metadata.maintainability_index != null
+            ? reportData.metadata.maintainability_index.toFixed(1)
+            : '-';
 
         // Language Chart
         const breakdown = reportData.breakdown;
@@ -237,9 +332,14 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
         const tableBody = document.getElementById('fileTableBody');
         const files = reportData.files;
 
-        function getMaxComplexity(f) {{
+        function getMaxComplexity(f, key = 'complexity') {{
             if (!f.functions || f.functions.length === 0) return 0;
-            return Math.max(...f.functions.map(fn => fn.complexity));
+            return Math.max(...f.functions.map(fn => fn[key] || 0));
+        }}
+
+        function complexityBadge(n) {{
+            const cls = n > 15 ? 'high-complexity' : (n > 7 ? 'med-complexity' : 'low-complexity');
+            return `<span class="complexity-badge ${{cls}}">${{n > 0 ? n : '-'}}</span>`;
         }}
 
         function renderTable(filter = '') {{
@@ -249,14 +349,13 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
                 .sort((a, b) => b.lines - a.lines)
                 .slice(0, 50)
                 .forEach(f => {{
-                    const maxComp = getMaxComplexity(f);
-                    const compClass = maxComp > 15 ? 'high-complexity' : (maxComp > 7 ? 'med-complexity' : 'low-complexity');
-                    
                     const row = document.createElement('tr');
                     row.innerHTML = `
                         <td>${{f.path}}</td>
                         <td>${{f.lines.toLocaleString()}}</td>
-                        <td><span class="complexity-badge ${{compClass}}">${{maxComp > 0 ? maxComp : '-'}}</span></td>
+                        <td>${{complexityBadge(getMaxComplexity(f))}}</td>
+                        <td>${{complexityBadge(getMaxComplexity(f, 'cognitive_complexity'))}}</td>
+                        <td>${{f.maintainability_index != null ? f.maintainability_index.toFixed(1) : '-'}}</td>
                     `;
                     tableBody.appendChild(row);
                 }});
@@ -264,6 +363,64 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
 
         document.getElementById('fileSearch').addEventListener('input', (e) => renderTable(e.target.value));
         renderTable();
+
+        // Baseline comparison (--compare)
+        function fmtDelta(n) {{
+            const cls = n > 0 ? 'delta-pos' : (n < 0 ? 'delta-neg' : 'delta-zero');
+            const text = n > 0 ? '+' + n.toLocaleString() : n.toLocaleString();
+            return `<span class="${{cls}}">${{text}}</span>`;
+        }}
+
+        if (reportData.comparison) {{
+            const cmp = reportData.comparison;
+            document.getElementById('comparisonSection').style.display = 'block';
+            document.getElementById('comparisonTitle').textContent = 'Changes vs ' + cmp.baseline;
+
+            const stats = document.getElementById('comparisonStats');
+            [['lines', 'Lines'], ['code', 'Code'], ['functions', 'Functions'], ['complexity', 'Complexity']].forEach(([key, label]) => {{
+                const card = document.createElement('div');
+                card.className = 'stat-card';
+                card.innerHTML = `<span class="stat-value">${{fmtDelta(cmp.total_delta[key])}}</span><span class="stat-label">${{label}}</span>`;
+                stats.appendChild(card);
+            }});
+
+            const extBody = document.getElementById('comparisonExtBody');
+            cmp.extensions
+                .filter(e => Object.values(e.delta).some(v => v !== 0))
+                .sort((a, b) => Math.abs(b.delta.lines) - Math.abs(a.delta.lines))
+                .forEach(e => {{
+                    const d = e.delta;
+                    const row = document.createElement('tr');
+                    row.innerHTML = `<td>${{e.extension}}</td><td>${{fmtDelta(d.files)}}</td><td>${{fmtDelta(d.lines)}}</td><td>${{fmtDelta(d.code)}}</td><td>${{fmtDelta(d.comment)}}</td><td>${{fmtDelta(d.blank)}}</td><td>${{fmtDelta(d.functions)}}</td><td>${{fmtDelta(d.complexity)}}</td>`;
+                    extBody.appendChild(row);
+                }});
+
+            const fileBody = document.getElementById('comparisonFileBody');
+            cmp.files
+                .slice()
+                .sort((a, b) => Math.abs(b.delta.lines) - Math.abs(a.delta.lines))
+                .slice(0, 100)
+                .forEach(f => {{
+                    const d = f.delta;
+                    const row = document.createElement('tr');
+                    row.innerHTML = `<td>${{f.path}}</td><td>${{f.kind}}</td><td>${{fmtDelta(d.lines)}}</td><td>${{fmtDelta(d.code)}}</td><td>${{fmtDelta(d.functions)}}</td><td>${{fmtDelta(d.complexity)}}</td>`;
+                    fileBody.appendChild(row);
+                }});
+        }}
+
+        // Churn x complexity ranking (--hotspots)
+        if (reportData.hotspots) {{
+            document.getElementById('hotspotsSection').style.display = 'block';
+            const root = reportData.metadata.root + '/';
+            const body = document.getElementById('hotspotsBody');
+            reportData.hotspots.forEach((h, i) => {{
+                const cls = h.score >= 0.5 ? 'high-complexity' : (h.score >= 0.2 ? 'med-complexity' : 'low-complexity');
+                const path = h.path.startsWith(root) ? h.path.slice(root.length) : h.path;
+                const row = document.createElement('tr');
+                row.innerHTML = `<td>${{i + 1}}</td><td>${{path}}</td><td><span class="complexity-badge ${{cls}}">${{h.score.toFixed(3)}}</span></td><td>${{h.commits.toLocaleString()}}</td><td>${{h.churn.toLocaleString()}}</td><td>${{h.code.toLocaleString()}}</td><td>${{h.max_complexity}}</td><td>${{h.total_complexity}}</td>`;
+                body.appendChild(row);
+            }});
+        }}
     </script>
 </body>
 </html>
@@ -271,10 +428,7 @@ pub fn export_html(result: &ScanResult, path: &Path, extract_functions: bool) ->
         data = json_data
     );
 
-    let f = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
-    let mut writer = BufWriter::new(f);
     writer.write_all(html_content.as_bytes())?;
-
-    println!("[SUCCESS] Exported HTML Visual Report → {}", path.display());
+    writer.flush()?;
     Ok(())
 }
@@ -1,6 +1,7 @@
 // Author: kelexine (https://github.com/kelexine)
 // export/json.rs — JSON and JSONL export logic
 
+use super::ReportSections;
 use crate::models::{FileInfo, ScanResult};
 use anyhow::{Context, Result};
 use chrono::Utc;
@@ -9,14 +10,47 @@ use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
-pub fn export_json(result: &ScanResult, path: &Path, extract_functions: bool) -> Result<()> {
+pub fn export_json(
+    result: &ScanResult,
+    path: &Path,
+    extract_functions: bool,
+    sections: &ReportSections,
+) -> Result<()> {
+    let f = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
+    write_json_report(result, sections, BufWriter::new(f), extract_functions)?;
+
+    println!("[SUCCESS] Exported JSON → {}", path.display());
+    Ok(())
+}
+
+/// Serialize the full report (metadata, breakdown, files) as pretty-printed JSON.
+pub fn write_json<W: Write>(result: &ScanResult, writer: W, extract_functions: bool) -> Result<()> {
+    write_json_report(
+        result,
+        &ReportSections::default(),
+        writer,
+        extract_functions,
+    )
+}
+
+/// Like [`write_json`], with optional sections added as extra top-level keys.
+pub fn write_json_report<W: Write>(
+    result: &ScanResult,
+    sections: &ReportSections,
+    mut writer: W,
+    extract_functions: bool,
+) -> Result<()> {
     let text_files: Vec<_> = result.files.iter().filter(|f| !f.is_binary).collect();
-    let data = json!({
+    let mut data = json!({
         "metadata": {
             "total_lines": result.total_lines(),
             "total_files": result.text_file_count(),
             "total_functions": result.total_functions(),
             "total_classes": result.total_classes(),
+            "total_doc": result.total_doc(),
+            "documented_functions_pct": (result.documented_percent() * 100.0).round() / 100.0,
+            "maintainability_index": result.maintainability_index().map(round2),
+            "root": result.root.to_string_lossy(),
             "timestamp": Utc::now().to_rfc3339(),
             "function_extraction_enabled": extract_functions,
             "generator": concat!("loc v", env!("CARGO_PKG_VERSION"), " by kelexine (https://github.com/kelexine)"),
@@ -24,26 +58,29 @@ pub fn export_json(result: &ScanResult, path: &Path, extract_functions: bool) ->
         "breakdown": result.breakdown,
         "files": text_files.iter().map(|f| file_to_value(f, extract_functions)).collect::<Vec<_>>(),
     });
+    sections.insert_into(&mut data);
 
-    let f = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
-    serde_json::to_writer_pretty(BufWriter::new(f), &data)
-        .with_context(|| "Failed to serialize JSON")?;
-
-    println!("[SUCCESS] Exported JSON → {}", path.display());
+    serde_json::to_writer_pretty(&mut writer, &data).with_context(|| "Failed to serialize JSON")?;
+    writer.flush()?;
     Ok(())
 }
 
 pub fn export_jsonl(result: &ScanResult, path: &Path) -> Result<()> {
     let f = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
-    let mut writer = BufWriter::new(f);
+    write_jsonl(result, BufWriter::new(f))?;
+
+    println!("[SUCCESS] Exported JSONL → {}", path.display());
+    Ok(())
+}
 
+/// Write one JSON object per text file, newline-delimited.
+pub fn write_jsonl<W: Write>(result: &ScanResult, mut writer: W) -> Result<()> {
     for fi in result.files.iter().filter(|f| !f.is_binary) {
         let line = serde_json::to_string(&file_to_value(fi, true))
             .with_context(|| "Failed to serialize JSONL record")?;
         writeln!(writer, "{}", line)?;
     }
-
-    println!("[SUCCESS] Exported JSONL → {}", path.display());
+    writer.flush()?;
     Ok(())
 }
 
@@ -51,6 +88,11 @@ pub fn file_to_value(fi: &FileInfo, include_functions: bool) -> serde_json::Valu
     let mut obj = json!({
         "path": fi.path.to_string_lossy(),
         "lines": fi.lines,
+        "code": fi.code,
+        "comment": fi.comment,
+        "blank": fi.blank,
+        "mixed": fi.mixed,
+        "doc": fi.doc,
         "is_binary": fi.is_binary,
         "extension": fi.extension(),
         "last_modified": fi.last_modified.map(|d| d.to_rfc3339()),
@@ -60,22 +102,41 @@ pub fn file_to_value(fi: &FileInfo, include_functions: bool) -> serde_json::Valu
         obj["function_count"] = json!(fi.function_count());
         obj["class_count"] = json!(fi.class_count());
         obj["avg_function_length"] = json!((fi.avg_function_length() * 100.0).round() / 100.0);
+        obj["documented_functions_pct"] = json!((fi.documented_percent() * 100.0).round() / 100.0);
+        obj["halstead_volume"] = json!(round2(fi.halstead_volume()));
+        obj["halstead_effort"] = json!(round2(fi.halstead_effort()));
+        obj["maintainability_index"] = json!(fi.maintainability_index().map(round2));
         obj["functions"] = json!(
             fi.functions
                 .iter()
                 .map(|f| {
                     json!({
                         "name": f.name,
+                        "parent": f.parent,
+                        "qualified_name": f.full_name(),
                         "line_start": f.line_start,
                         "line_end": f.line_end,
                         "line_count": f.line_count(),
                         "parameters": f.parameters,
+                        "parameter_count": f.parameters.len(),
                         "is_async": f.is_async,
                         "is_method": f.is_method,
                         "is_class": f.is_class,
                         "docstring": f.truncated_docstring(),
                         "decorators": f.decorators,
                         "complexity": f.complexity,
+                        "cognitive_complexity": f.cognitive_complexity,
+                        "halstead": {
+                            "distinct_operators": f.halstead.distinct_operators,
+                            "distinct_operands": f.halstead.distinct_operands,
+                            "operators": f.halstead.operators,
+                            "operands": f.halstead.operands,
+                            "volume": round2(f.halstead.volume()),
+                            "difficulty": round2(f.halstead.difficulty()),
+                            "effort": round2(f.halstead.effort()),
+                        },
+                        "maintainability_index": round2(f.maintainability_index()),
+                        "max_nesting": f.max_nesting,
                     })
                 })
                 .collect::<Vec<_>>()
@@ -84,3 +145,7 @@ pub fn file_to_value(fi: &FileInfo, include_functions: bool) -> serde_json::Valu
 
     obj
 }
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
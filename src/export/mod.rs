@@ -2,18 +2,70 @@
 // export/mod.rs — Export dispatcher
 
 pub mod csv;
+pub mod history;
 pub mod html;
 pub mod json;
+pub mod sarif;
 
+use crate::blame::BlameReport;
+use crate::compare::ScanDiff;
+use crate::hotspots::Hotspot;
 use crate::models::ScanResult;
+use crate::thresholds::{Thresholds, Violation};
 use anyhow::Result;
+use serde_json::json;
+use std::io::Write;
 use std::path::Path;
 
+pub use self::csv::write_csv;
+pub use html::write_html;
+pub use json::{write_json, write_jsonl};
+pub use sarif::write_sarif;
+
+/// Optional report sections computed alongside a scan. JSON and HTML exports
+/// include whichever are present; CSV only picks up per-file blame columns and
+/// JSONL stays per-file only.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReportSections<'a> {
+    /// Delta against a baseline report (`--compare`).
+    pub comparison: Option<&'a ScanDiff>,
+    /// Quality-gate results; present (possibly empty) whenever thresholds were set.
+    pub violations: Option<&'a [Violation]>,
+    /// Top churn × complexity files (`--hotspots`), highest score first.
+    pub hotspots: Option<&'a [Hotspot]>,
+    /// Per-author code ownership (`--blame`).
+    pub blame: Option<&'a BlameReport>,
+    /// Configured quality gates. SARIF reports anything exceeding them as an error.
+    pub thresholds: Option<&'a Thresholds>,
+    /// Size warning limit (`--warn-size`). SARIF reports larger files as warnings.
+    pub warn_size: Option<usize>,
+}
+
+impl ReportSections<'_> {
+    /// Attach the present sections as top-level keys of a JSON report.
+    pub fn insert_into(&self, data: &mut serde_json::Value) {
+        if let Some(diff) = self.comparison {
+            data["comparison"] = json!(diff);
+        }
+        if let Some(violations) = self.violations {
+            data["violations"] = json!(violations);
+        }
+        if let Some(hotspots) = self.hotspots {
+            data["hotspots"] = json!(hotspots);
+        }
+        if let Some(blame) = self.blame {
+            data["blame"] = json!(blame);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExportFormat {
     Json,
     Jsonl,
     Csv,
     Html,
+    Sarif,
 }
 
 impl ExportFormat {
@@ -24,22 +76,62 @@ impl ExportFormat {
             "jsonl" => Some(Self::Jsonl),
             "csv" => Some(Self::Csv),
             "html" | "htm" => Some(Self::Html),
+            "sarif" => Some(Self::Sarif),
             _ => None,
         }
     }
 }
 
-pub fn export(result: &ScanResult, output_path: &str, extract_functions: bool) -> Result<()> {
+pub fn export(
+    result: &ScanResult,
+    output_path: &str,
+    extract_functions: bool,
+    sections: &ReportSections,
+) -> Result<()> {
     let path = Path::new(output_path);
 
     match ExportFormat::from_path(path) {
-        Some(ExportFormat::Json) => json::export_json(result, path, extract_functions),
+        Some(ExportFormat::Json) => json::export_json(result, path, extract_functions, sections),
         Some(ExportFormat::Jsonl) => json::export_jsonl(result, path),
-        Some(ExportFormat::Csv) => csv::export_csv(result, path, extract_functions),
-        Some(ExportFormat::Html) => html::export_html(result, path, extract_functions),
+        Some(ExportFormat::Csv) => csv::export_csv(result, path, extract_functions, sections),
+        Some(ExportFormat::Html) => html::export_html(result, path, extract_functions, sections),
+        Some(ExportFormat::Sarif) => sarif::export_sarif(result, path, sections),
         None => anyhow::bail!(
-            "Unsupported export format '{}'. Use .json, .jsonl, .csv, or .html",
+            "Unsupported export format '{}'. Use .json, .jsonl, .csv, .html, or .sarif",
             path.extension().and_then(|e| e.to_str()).unwrap_or("?")
         ),
     }
 }
+
+/// Write `result` in the given format to any writer (stdout, a buffer, a socket...).
+pub fn write_to<W: Write>(
+    result: &ScanResult,
+    format: ExportFormat,
+    writer: W,
+    extract_functions: bool,
+) -> Result<()> {
+    write_report(
+        result,
+        &ReportSections::default(),
+        format,
+        writer,
+        extract_functions,
+    )
+}
+
+/// Like [`write_to`], including any optional report sections.
+pub fn write_report<W: Write>(
+    result: &ScanResult,
+    sections: &ReportSections,
+    format: ExportFormat,
+    writer: W,
+    extract_functions: bool,
+) -> Result<()> {
+    match format {
+        ExportFormat::Json => json::write_json_report(result, sections, writer, extract_functions),
+        ExportFormat::Jsonl => json::write_jsonl(result, writer),
+        ExportFormat::Csv => csv::write_csv_report(result, sections, writer, extract_functions),
+        ExportFormat::Html => html::write_html_report(result, sections, writer, extract_functions),
+        ExportFormat::Sarif => sarif::write_sarif(result, sections, writer),
+    }
+}
@@ -0,0 +1,355 @@
+// Author: kelexine (https://github.com/kelexine)
+// export/sarif.rs — SARIF 2.1.0 export for code-scanning dashboards
+
+use super::ReportSections;
+use crate::models::ScanResult;
+use crate::thresholds::{self, Rule, Thresholds, Violation};
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Complexity flagged as a warning when no `max_complexity` gate is configured.
+pub const DEFAULT_MAX_COMPLEXITY: u32 = 10;
+/// Cognitive complexity flagged as a warning when no `max_cognitive_complexity` gate is configured.
+pub const DEFAULT_MAX_COGNITIVE_COMPLEXITY: u32 = 15;
+/// Function length flagged as a warning when no `max_function_lines` gate is configured.
+pub const DEFAULT_MAX_FUNCTION_LINES: usize = 100;
+
+const RULES: &[Rule] = &[
+    Rule::MaxFileLines,
+    Rule::MaxFunctionLines,
+    Rule::MaxComplexity,
+    Rule::MaxCognitiveComplexity,
+    Rule::MinCommentRatio,
+    Rule::MaxDirLines,
+];
+
+pub fn export_sarif(result: &ScanResult, path: &Path, sections: &ReportSections) -> Result<()> {
+    let f = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
+    write_sarif(result, sections, BufWriter::new(f))?;
+
+    println!("[SUCCESS] Exported SARIF → {}", path.display());
+    Ok(())
+}
+
+/// Write a SARIF 2.1.0 log with one result per oversized file and per
+/// overlong or over-complex function.
+///
+/// Configured gates (`sections.thresholds`) produce `error` results. Files over
+/// `warn_size` and functions over the built-in defaults produce `warning`s.
+pub fn write_sarif<W: Write>(
+    result: &ScanResult,
+    sections: &ReportSections,
+    mut writer: W,
+) -> Result<()> {
+    let gates = sections.thresholds.cloned().unwrap_or_default();
+    let effective = gates.clone().or(&Thresholds {
+        max_file_lines: sections.warn_size,
+        max_function_lines: Some(DEFAULT_MAX_FUNCTION_LINES),
+        max_complexity: Some(DEFAULT_MAX_COMPLEXITY),
+        max_cognitive_complexity: Some(DEFAULT_MAX_COGNITIVE_COMPLEXITY),
+        ..Default::default()
+    });
+
+    let active: Vec<Rule> = RULES
+        .iter()
+        .copied()
+        .filter(|r| limit_of(&effective, *r).is_some())
+        .collect();
+
+    let rules: Vec<_> = active
+        .iter()
+        .map(|r| rule_descriptor(*r, &effective, is_gate(&gates, *r)))
+        .collect();
+
+    // Directories are not artifacts SARIF can point at, so directory-level gates
+    // are reported as notifications on the invocation instead of results.
+    let mut results = Vec::new();
+    let mut notifications = Vec::new();
+    for v in thresholds::check(result, &effective) {
+        let rule_index = active.iter().position(|r| *r == v.rule).unwrap_or(0);
+        let gate = is_gate(&gates, v.rule);
+        if v.rule == Rule::MaxDirLines {
+            notifications.push(notification_object(&v, rule_index, gate, &result.root));
+        } else {
+            results.push(result_object(&v, rule_index, gate, &result.root));
+        }
+    }
+
+    let root_uri = format!(
+        "file://{}/",
+        result
+            .root
+            .to_string_lossy()
+            .replace('\\', "/")
+            .trim_end_matches('/')
+    );
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "loc-rs",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "originalUriBaseIds": {
+                "SRCROOT": { "uri": root_uri }
+            },
+            "invocations": [{
+                "executionSuccessful": true,
+                "toolExecutionNotifications": notifications,
+            }],
+            "results": results,
+        }]
+    });
+
+    serde_json::to_writer_pretty(&mut writer, &log).with_context(|| "Failed to serialize SARIF")?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn is_gate(gates: &Thresholds, rule: Rule) -> bool {
+    limit_of(gates, rule).is_some()
+}
+
+fn limit_of(t: &Thresholds, rule: Rule) -> Option<f64> {
+    match rule {
+        Rule::MaxFileLines => t.max_file_lines.map(|v| v as f64),
+        Rule::MaxFunctionLines => t.max_function_lines.map(|v| v as f64),
+        Rule::MaxComplexity => t.max_complexity.map(|v| v as f64),
+        Rule::MaxCognitiveComplexity => t.max_cognitive_complexity.map(|v| v as f64),
+        Rule::MinCommentRatio => t.min_comment_ratio,
+        Rule::MaxDirLines => t.max_dir_lines.map(|v| v as f64),
+    }
+}
+
+fn level(gate: bool) -> &'static str {
+    if gate { "error" } else { "warning" }
+}
+
+fn rule_descriptor(rule: Rule, effective: &Thresholds, gate: bool) -> serde_json::Value {
+    let limit = limit_of(effective, rule).unwrap_or_default();
+    let (name, short, full) = match rule {
+        Rule::MaxFileLines => (
+            "FileTooLarge",
+            "File exceeds the line limit",
+            format!(
+                "Files longer than {} lines are hard to navigate and review.",
+                limit
+            ),
+        ),
+        Rule::MaxFunctionLines => (
+            "FunctionTooLong",
+            "Function exceeds the length limit",
+            format!("Functions longer than {} lines should be split up.", limit),
+        ),
+        Rule::MaxComplexity => (
+            "FunctionTooComplex",
+            "Function exceeds the cyclomatic complexity limit",
+            format!(
+                "Functions with cyclomatic complexity above {} have too many paths to test.",
+                limit
+            ),
+        ),
+        Rule::MaxCognitiveComplexity => (
+            "FunctionTooHardToUnderstand",
+            "Function exceeds the cognitive complexity limit",
+            format!(
+                "Functions with cognitive complexity above {} nest or break their flow too much to read easily.",
+                limit
+            ),
+        ),
+        Rule::MinCommentRatio => (
+            "TooFewComments",
+            "File is under-commented",
+            format!(
+                "Files should have at least {:.1}% comment lines relative to code.",
+                limit * 100.0
+            ),
+        ),
+        Rule::MaxDirLines => (
+            "DirectoryTooLarge",
+            "Directory exceeds the line limit",
+            format!(
+                "Directories holding more than {} lines of code should be split into modules.",
+                limit
+            ),
+        ),
+    };
+
+    json!({
+        "id": rule.id(),
+        "name": name,
+        "shortDescription": { "text": short },
+        "fullDescription": { "text": full },
+        "defaultConfiguration": { "level": level(gate) },
+        "help": { "text": full },
+        "properties": { "limit": limit, "tags": ["maintainability"] },
+    })
+}
+
+fn result_object(v: &Violation, rule_index: usize, gate: bool, root: &Path) -> serde_json::Value {
+    let rel = v.path.strip_prefix(root).unwrap_or(&v.path);
+    let uri = rel.to_string_lossy().replace('\\', "/");
+
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": uri, "uriBaseId": "SRCROOT" },
+        }
+    });
+    // The comment ratio describes the whole file, so it gets no region.
+    // Oversized files are annotated at their first line.
+    let start_line = match v.rule {
+        Rule::MinCommentRatio => None,
+        _ => Some(v.line.unwrap_or(1)),
+    };
+    if let Some(start) = start_line {
+        let mut region = json!({ "startLine": start });
+        if let Some(end) = v.line_end {
+            region["endLine"] = json!(end);
+        }
+        location["physicalLocation"]["region"] = region;
+    }
+    if let Some(function) = &v.function {
+        location["logicalLocations"] = json!([{
+            "fullyQualifiedName": function,
+            "kind": "function",
+        }]);
+    }
+
+    json!({
+        "ruleId": v.rule.id(),
+        "ruleIndex": rule_index,
+        "level": level(gate),
+        "message": { "text": v.message },
+        "locations": [location],
+        "properties": { "actual": v.actual, "limit": v.limit },
+    })
+}
+
+fn notification_object(
+    v: &Violation,
+    rule_index: usize,
+    gate: bool,
+    root: &Path,
+) -> serde_json::Value {
+    let rel = v.path.strip_prefix(root).unwrap_or(&v.path);
+    let dir = match rel.to_string_lossy().replace('\\', "/") {
+        d if d.is_empty() => ".".to_string(),
+        d => d,
+    };
+
+    json!({
+        "associatedRule": { "id": v.rule.id(), "index": rule_index },
+        "level": level(gate),
+        "message": { "text": format!("{}: {}", dir, v.message) },
+        "properties": { "path": dir, "actual": v.actual, "limit": v.limit },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Breakdown, FileInfo, FunctionInfo};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_sarif_levels_and_locations() {
+        let func = FunctionInfo {
+            name: "tangled".into(),
+            parent: Some("Knot".into()),
+            qualified_name: "Knot::tangled".into(),
+            line_start: 5,
+            line_end: 40,
+            parameters: vec![],
+            is_async: false,
+            is_method: false,
+            is_class: false,
+            docstring: None,
+            decorators: vec![],
+            complexity: 12,
+            cognitive_complexity: 0,
+            halstead: Default::default(),
+            max_nesting: 0,
+        };
+        let fi = FileInfo::new(PathBuf::from("/r/src/a.rs"), 600, 550, 20, 30, false, None)
+            .with_functions(vec![func]);
+        let result = ScanResult {
+            root: PathBuf::from("/r"),
+            files: vec![fi],
+            breakdown: Breakdown::new(),
+        };
+        let gates = Thresholds {
+            max_complexity: Some(10),
+            min_comment_ratio: Some(0.1),
+            max_dir_lines: Some(500),
+            ..Default::default()
+        };
+        let sections = ReportSections {
+            warn_size: Some(500),
+            thresholds: Some(&gates),
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        write_sarif(&result, &sections, &mut buf).unwrap();
+        let log: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        let run = &log["runs"][0];
+        let results = run["results"].as_array().unwrap();
+
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(results.len(), 3);
+
+        let size = results
+            .iter()
+            .find(|r| r["ruleId"] == "max-file-lines")
+            .unwrap();
+        assert_eq!(size["level"], "warning");
+        let loc = &size["locations"][0]["physicalLocation"];
+        assert_eq!(loc["artifactLocation"]["uri"], "src/a.rs");
+        assert_eq!(loc["region"]["startLine"], 1);
+
+        let cc = results
+            .iter()
+            .find(|r| r["ruleId"] == "max-complexity")
+            .unwrap();
+        assert_eq!(cc["level"], "error");
+        let region = &cc["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 5);
+        assert_eq!(region["endLine"], 40);
+        let logical = &cc["locations"][0]["logicalLocations"][0];
+        assert_eq!(logical["fullyQualifiedName"], "Knot::tangled");
+
+        let idx = cc["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(run["tool"]["driver"]["rules"][idx]["id"], "max-complexity");
+
+        let ratio = results
+            .iter()
+            .find(|r| r["ruleId"] == "min-comment-ratio")
+            .unwrap();
+        let loc = &ratio["locations"][0]["physicalLocation"];
+        assert_eq!(loc["artifactLocation"]["uri"], "src/a.rs");
+        assert!(loc.get("region").is_none());
+
+        // Directory gates never become results pointing at a directory
+        assert!(!results.iter().any(|r| r["ruleId"] == "max-dir-lines"));
+        let notes = run["invocations"][0]["toolExecutionNotifications"]
+            .as_array()
+            .unwrap();
+        assert_eq!(notes.len(), 2);
+        assert!(
+            notes
+                .iter()
+                .all(|n| n["associatedRule"]["id"] == "max-dir-lines")
+        );
+        assert!(notes.iter().any(|n| n["properties"]["path"] == "src"));
+        assert!(notes.iter().any(|n| n["properties"]["path"] == "."));
+    }
+}
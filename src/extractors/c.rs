@@ -0,0 +1,270 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/c.rs — C function extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
+
+pub struct CExtractor;
+
+/// Doxygen comments.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["///", "//!"],
+    block: &[("/**", "*/"), ("/*!", "*/")],
+    skip: &[],
+};
+
+/// C decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "conditional_expression",
+    ],
+    arms: &["case_statement"],
+    binary: &["binary_expression"],
+    operators: &["&&", "||"],
+    nested: &["function_definition", "struct_specifier", "union_specifier"],
+    ifs: &["if_statement"],
+    elses: &["else_clause"],
+    structures: &[
+        "switch_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "conditional_expression",
+    ],
+    lambdas: &[],
+    jumps: &[("goto_statement", "statement_identifier")],
+    calls: &["call_expression"],
+};
+
+/// Aggregate types reported as classes, by the decorator they carry.
+const TYPES: &[(&str, &str)] = &[
+    ("struct_specifier", "struct"),
+    ("union_specifier", "union"),
+    ("enum_specifier", "enum"),
+];
+
+impl Extractor for CExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_c::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = Vec::new();
+
+        traverse(tree.root_node(), content, &lines, &mut functions);
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+fn traverse(node: Node, content: &str, lines: &[&str], functions: &mut Vec<FunctionInfo>) {
+    let kind = node.kind();
+
+    if kind == "function_definition" {
+        if let Some(info) = parse_function(node, content, lines) {
+            functions.push(in_scope(info, "", "::"));
+        }
+    } else if let Some(&(_, decorator)) = TYPES.iter().find(|(k, _)| *k == kind)
+        && let Some(info) = parse_type(node, content, lines, decorator)
+    {
+        functions.push(in_scope(info, "", "::"));
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        traverse(child, content, lines, functions);
+    }
+}
+
+/// The `function_declarator` under a definition's pointer and
+/// parenthesized declarators, e.g. in `char *(*name)(void)`.
+fn function_declarator(node: Node) -> Option<Node> {
+    let mut declarator = node.child_by_field_name("declarator")?;
+    while declarator.kind() != "function_declarator" {
+        declarator = match declarator.child_by_field_name("declarator") {
+            Some(inner) => inner,
+            // `parenthesized_declarator` has no field, only the wrapped declarator
+            None => declarator.named_child(0)?,
+        };
+    }
+    Some(declarator)
+}
+
+/// The identifier (or typedef name) a declarator finally names.
+fn declarator_name<'a>(mut node: Node, content: &'a str) -> Option<&'a str> {
+    while !matches!(node.kind(), "identifier" | "type_identifier") {
+        node = match node.child_by_field_name("declarator") {
+            Some(inner) => inner,
+            None => node.named_child(0)?,
+        };
+    }
+    node.utf8_text(content.as_bytes()).ok()
+}
+
+/// Storage class and inline specifiers, e.g. `static`, `inline`.
+fn specifiers(node: Node, content: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|c| c.kind() == "storage_class_specifier")
+        .filter_map(|c| c.utf8_text(content.as_bytes()).ok())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_function(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
+    let declarator = function_declarator(node)?;
+    let name = declarator_name(declarator.child_by_field_name("declarator")?, content)?.to_string();
+
+    // A K&R definition, `int add(a, b) int a; int b; { ... }`, only names its
+    // parameters in the list; their types follow as declarations.
+    let mut cursor = node.walk();
+    let old_style: Vec<String> = node
+        .named_children(&mut cursor)
+        .filter(|c| c.kind() == "declaration")
+        .filter_map(|c| c.utf8_text(content.as_bytes()).ok())
+        .map(|text| text.trim().trim_end_matches(';').trim().to_string())
+        .collect();
+    let parameters = if old_style.is_empty() {
+        declarator
+            .child_by_field_name("parameters")
+            .map(|list| parameter_list(list, content))
+            .unwrap_or_default()
+    } else {
+        old_style
+    };
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: false,
+        is_method: false,
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators: specifiers(node, content),
+        complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
+    })
+}
+
+/// A struct, union or enum with a body. Anonymous ones declared in a
+/// `typedef` take the typedef's name; plain references like `struct node *`
+/// are not definitions and are skipped.
+fn parse_type(node: Node, content: &str, lines: &[&str], decorator: &str) -> Option<FunctionInfo> {
+    node.child_by_field_name("body")?;
+    let name = match node.child_by_field_name("name") {
+        Some(name) => name.utf8_text(content.as_bytes()).ok()?,
+        None => {
+            let typedef = node.parent().filter(|p| p.kind() == "type_definition")?;
+            declarator_name(typedef.child_by_field_name("declarator")?, content)?
+        }
+    }
+    .to_string();
+
+    // The doc comment of a typedef'd type sits above the `typedef`
+    let outer = node
+        .parent()
+        .filter(|p| p.kind() == "type_definition")
+        .unwrap_or(node);
+    let start_line = outer.start_position().row + 1;
+    let end_line = outer.end_position().row + 1;
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters: vec![],
+        is_async: false,
+        is_method: false,
+        is_class: true,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators: vec![decorator.to_string()],
+        complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_c_functions() {
+        let content = "
+/** A list node. */
+typedef struct {
+    int value;
+} node_t;
+
+struct node *head;
+
+union word { int i; float f; };
+
+static inline int max(int a, int b) {
+    return a > b ? a : b;
+}
+
+int add(a, b)
+    int a;
+    int b;
+{
+    return a + b;
+}
+
+char *dup(const char *s) {
+    if (!s || !*s) {
+        return 0;
+    }
+    return 0;
+}
+";
+        let extractor = CExtractor;
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["node_t", "word", "max", "add", "dup"]);
+
+        let node = &fns[0];
+        assert!(node.is_class);
+        assert_eq!(node.decorators, vec!["struct"]);
+        assert_eq!(node.docstring.as_deref(), Some("A list node."));
+
+        let max = &fns[2];
+        assert_eq!(max.decorators, vec!["static", "inline"]);
+        assert_eq!(max.parameters, vec!["int a", "int b"]);
+        // ?:
+        assert_eq!(max.complexity, 2);
+
+        let add = &fns[3];
+        assert_eq!(add.parameters, vec!["int a", "int b"]);
+
+        let dup = &fns[4];
+        assert_eq!(dup.parameters, vec!["const char *s"]);
+        // if, ||
+        assert_eq!(dup.complexity, 3);
+    }
+}
@@ -1,28 +1,63 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/cpp.rs — C/C++ function extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
 
 pub struct CppExtractor;
 
+/// Doxygen comments, with `template <...>` lines in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["///", "//!"],
+    block: &[("/**", "*/"), ("/*!", "*/")],
+    skip: &["template"],
+};
+
+/// C/C++ decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "for_statement",
+        "for_range_loop",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    arms: &["case_statement"],
+    binary: &["binary_expression"],
+    operators: &["&&", "||", "and", "or"],
+    nested: &["function_definition", "class_specifier", "struct_specifier"],
+    ifs: &["if_statement"],
+    elses: &["else_clause"],
+    structures: &[
+        "switch_statement",
+        "for_statement",
+        "for_range_loop",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    lambdas: &["lambda_expression"],
+    jumps: &[("goto_statement", "statement_identifier")],
+    calls: &["call_expression"],
+};
+
 impl Extractor for CppExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&tree_sitter_cpp::LANGUAGE.into()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_cpp::LANGUAGE.into(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions, false);
+        traverse(tree.root_node(), content, &lines, &mut functions, false, "");
 
         functions.sort_by_key(|f| f.line_start);
         functions
@@ -35,25 +70,59 @@ fn traverse(
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
     in_class: bool,
+    scope: &str,
 ) {
     let kind = node.kind();
 
     if kind == "function_definition" {
         if let Some(info) = parse_function(node, content, lines, in_class) {
-            functions.push(info);
+            // An out-of-line `void Box::resize()` carries its own qualifier
+            let scope = match declarator_scope(node, content) {
+                Some(qualifier) => nest_scope(scope, "::", qualifier),
+                None => scope.to_string(),
+            };
+            functions.push(in_scope(info, &scope, "::"));
         }
     } else if (kind == "class_specifier" || kind == "struct_specifier")
         && let Some(info) = parse_class(node, content, lines)
     {
-        functions.push(info);
+        functions.push(in_scope(info, scope, "::"));
     }
 
     let is_class_body = kind == "field_declaration_list";
+    // Namespaces and classes scope whatever is declared inside them
+    let inner = match kind {
+        "namespace_definition" | "class_specifier" | "struct_specifier" => {
+            node.child_by_field_name("name")
+        }
+        _ => None,
+    }
+    .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+    .map(|name| nest_scope(scope, "::", name));
+    let scope = inner.as_deref().unwrap_or(scope);
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        traverse(child, content, lines, functions, in_class || is_class_body);
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            in_class || is_class_body,
+            scope,
+        );
+    }
+}
+
+/// The `Box` of a definition declared as `Box::resize`, if qualified.
+fn declarator_scope<'a>(node: Node, content: &'a str) -> Option<&'a str> {
+    let declarator = find_descendant(node, "function_declarator")?;
+    let name = declarator.child_by_field_name("declarator")?;
+    if name.kind() != "qualified_identifier" {
+        return None;
     }
+    let text = name.utf8_text(content.as_bytes()).ok()?;
+    text.rsplit_once("::").map(|(scope, _)| scope)
 }
 
 fn parse_function(
@@ -63,7 +132,7 @@ fn parse_function(
     is_method: bool,
 ) -> Option<FunctionInfo> {
     let mut name = String::new();
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
 
     // In tree-sitter-cpp, the function_definition has a declarator child.
     // That declarator might be a function_declarator, which in turn has an identifier (name)
@@ -74,10 +143,19 @@ fn parse_function(
             let mut inner_cursor = child.walk();
             for inner_child in child.children(&mut inner_cursor) {
                 let ikind = inner_child.kind();
-                if ikind == "identifier" || ikind == "field_identifier" || ikind == "destructor_name" {
-                    name = inner_child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+                if ikind == "identifier"
+                    || ikind == "field_identifier"
+                    || ikind == "destructor_name"
+                {
+                    name = inner_child
+                        .utf8_text(content.as_bytes())
+                        .unwrap_or("")
+                        .to_string();
+                } else if ikind == "qualified_identifier" {
+                    let text = inner_child.utf8_text(content.as_bytes()).unwrap_or("");
+                    name = text.rsplit("::").next().unwrap_or(text).to_string();
                 } else if ikind == "parameter_list" {
-                    params_str = inner_child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+                    parameters = parameter_list(inner_child, content);
                 }
             }
         }
@@ -90,10 +168,15 @@ fn parse_function(
         let mut inner_cursor = decl.walk();
         for inner_child in decl.children(&mut inner_cursor) {
             let ikind = inner_child.kind();
-            if (ikind == "identifier" || ikind == "field_identifier" || ikind == "destructor_name") && name.is_empty() {
-                name = inner_child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
-            } else if ikind == "parameter_list" && params_str.is_empty() {
-                params_str = inner_child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            if (ikind == "identifier" || ikind == "field_identifier" || ikind == "destructor_name")
+                && name.is_empty()
+            {
+                name = inner_child
+                    .utf8_text(content.as_bytes())
+                    .unwrap_or("")
+                    .to_string();
+            } else if ikind == "parameter_list" && parameters.is_empty() {
+                parameters = parameter_list(inner_child, content);
             }
         }
     }
@@ -105,41 +188,38 @@ fn parse_function(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
-
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async: false,
         is_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
-fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInfo> {
+fn parse_class(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
     let mut name = String::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         if child.kind() == "type_identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
             break;
         }
     }
@@ -153,15 +233,20 @@ fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInf
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters: vec![],
         is_async: false,
         is_method: false,
         is_class: true,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
     })
 }
 
@@ -185,31 +270,45 @@ mod tests {
     #[test]
     fn test_extract_cpp_functions() {
         let content = "
+/// Greets.
 void Hello() {}
+/* not documentation */
 int main(int argc, char** argv) { return 0; }
+namespace geo {
 class Box {
 public:
     void SetWidth(double wid) {}
 };
+}
+void geo::Box::Resize() {}
 ";
         let extractor = CppExtractor;
         let mut fns = extractor.extract(content);
         fns.sort_by(|a, b| a.name.cmp(&b.name));
-        
-        assert_eq!(fns.len(), 4);
-        
+
+        assert_eq!(fns.len(), 5);
+
         let c = fns.iter().find(|f| f.name == "Box").unwrap();
         assert!(c.is_class);
-        
+        assert_eq!(c.qualified_name, "geo::Box");
+
         let h = fns.iter().find(|f| f.name == "Hello").unwrap();
         assert!(!h.is_method);
-        
+        assert_eq!(h.qualified_name, "Hello");
+        assert_eq!(h.docstring.as_deref(), Some("Greets."));
+
         let m = fns.iter().find(|f| f.name == "main").unwrap();
         assert!(!m.is_method);
         assert_eq!(m.parameters, vec!["int argc", "char** argv"]);
-        
+        assert_eq!(m.docstring, None);
+
         let s = fns.iter().find(|f| f.name == "SetWidth").unwrap();
         assert!(s.is_method);
         assert_eq!(s.parameters, vec!["double wid"]);
+        assert_eq!(s.qualified_name, "geo::Box::SetWidth");
+
+        let r = fns.iter().find(|f| f.name == "Resize").unwrap();
+        assert_eq!(r.qualified_name, "geo::Box::Resize");
+        assert_eq!(r.parent.as_deref(), Some("geo::Box"));
     }
 }
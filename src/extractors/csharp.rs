@@ -0,0 +1,333 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/csharp.rs — C# function/class extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
+
+pub struct CSharpExtractor;
+
+/// `///` XML documentation, with attributes in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["///"],
+    block: &[("/**", "*/")],
+    skip: &["["],
+};
+
+/// C# decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "for_statement",
+        "foreach_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    arms: &["switch_section", "switch_expression_arm"],
+    binary: &["binary_expression"],
+    operators: &["&&", "||"],
+    nested: &[
+        "method_declaration",
+        "constructor_declaration",
+        "local_function_statement",
+        "class_declaration",
+        "struct_declaration",
+        "record_declaration",
+    ],
+    ifs: &["if_statement"],
+    elses: &[],
+    structures: &[
+        "switch_statement",
+        "switch_expression",
+        "for_statement",
+        "foreach_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    lambdas: &["lambda_expression", "anonymous_method_expression"],
+    jumps: &[("goto_statement", "identifier")],
+    calls: &["invocation_expression"],
+};
+
+/// Type declarations, and the decorator recorded for each besides `class`.
+const TYPES: &[(&str, Option<&str>)] = &[
+    ("class_declaration", None),
+    ("struct_declaration", Some("struct")),
+    ("record_declaration", Some("record")),
+    ("interface_declaration", Some("interface")),
+    ("enum_declaration", Some("enum")),
+];
+
+impl Extractor for CSharpExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_c_sharp::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = Vec::new();
+
+        // `namespace App.Models;` applies to the rest of the file
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let namespace = root
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "file_scoped_namespace_declaration")
+            .and_then(|n| n.child_by_field_name("name"))
+            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+            .unwrap_or("");
+
+        traverse(root, content, &lines, &mut functions, false, namespace);
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+fn traverse(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    functions: &mut Vec<FunctionInfo>,
+    in_class: bool,
+    scope: &str,
+) {
+    let kind = node.kind();
+    let type_kind = TYPES.iter().find(|(k, _)| *k == kind);
+
+    if matches!(
+        kind,
+        "method_declaration" | "constructor_declaration" | "local_function_statement"
+    ) {
+        let is_method = in_class && kind != "local_function_statement";
+        if let Some(info) = parse_method(node, content, lines, is_method) {
+            functions.push(in_scope(info, scope, "."));
+        }
+    } else if let Some(&(_, decorator)) = type_kind
+        && let Some(info) = parse_type(node, content, lines, decorator)
+    {
+        functions.push(in_scope(info, scope, "."));
+    }
+
+    let is_class_body = kind == "declaration_list" && in_type(node);
+    // Block namespaces, types and methods scope whatever is declared inside them
+    let inner = match kind {
+        "namespace_declaration" | "method_declaration" | "local_function_statement" => {
+            node.child_by_field_name("name")
+        }
+        _ if type_kind.is_some() => node.child_by_field_name("name"),
+        _ => None,
+    }
+    .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+    .map(|name| nest_scope(scope, ".", name));
+    let scope = inner.as_deref().unwrap_or(scope);
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            in_class || is_class_body,
+            scope,
+        );
+    }
+}
+
+/// Whether a declaration list is the body of a type rather than a namespace.
+fn in_type(body: Node) -> bool {
+    body.parent()
+        .is_some_and(|p| TYPES.iter().any(|(k, _)| *k == p.kind()))
+}
+
+/// Modifier keywords (`async`, `static`, ...) and attribute lists of a declaration.
+fn modifiers(node: Node, content: &str) -> (Vec<String>, Vec<String>) {
+    let mut keywords = Vec::new();
+    let mut attributes = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let text = child
+            .utf8_text(content.as_bytes())
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        match child.kind() {
+            "modifier" => keywords.push(text),
+            "attribute_list" => attributes.push(text),
+            _ => {}
+        }
+    }
+    (keywords, attributes)
+}
+
+/// The doc comment above `line_start` with its `<summary>` wrapper removed.
+fn xml_doc(lines: &[&str], line_start: usize) -> Option<String> {
+    let doc = doc_comment(lines, line_start, &DOCS)?;
+    let doc = doc.replace("<summary>", "").replace("</summary>", "");
+    let doc = doc
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!doc.is_empty()).then_some(doc)
+}
+
+fn parse_method(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    is_method: bool,
+) -> Option<FunctionInfo> {
+    let name = node
+        .child_by_field_name("name")?
+        .utf8_text(content.as_bytes())
+        .ok()?
+        .to_string();
+    let parameters = node
+        .child_by_field_name("parameters")
+        .map(|list| parameter_list(list, content))
+        .unwrap_or_default();
+    let (keywords, decorators) = modifiers(node, content);
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: keywords.iter().any(|k| k == "async"),
+        is_method,
+        is_class: false,
+        docstring: xml_doc(lines, start_line),
+        decorators,
+        complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
+    })
+}
+
+/// Classes, structs, records, interfaces and enums. A positional record's
+/// parameters are kept as its parameters.
+fn parse_type(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    decorator: Option<&str>,
+) -> Option<FunctionInfo> {
+    let name = node
+        .child_by_field_name("name")
+        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+        .unwrap_or("?")
+        .to_string();
+    let mut cursor = node.walk();
+    let parameters = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "parameter_list")
+        .map(|list| parameter_list(list, content))
+        .unwrap_or_default();
+    let (_, attributes) = modifiers(node, content);
+    let decorators = decorator
+        .map(str::to_string)
+        .into_iter()
+        .chain(attributes)
+        .collect();
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: false,
+        is_method: false,
+        is_class: true,
+        docstring: xml_doc(lines, start_line),
+        decorators,
+        complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_csharp_functions() {
+        let content = "
+namespace Shop.Orders
+{
+    public record Line(string Sku, int Qty);
+
+    public struct Money { }
+
+    public class OrderService
+    {
+        /// <summary>
+        /// Places an order.
+        /// </summary>
+        [HttpPost]
+        public async Task<int> PlaceAsync(Order order, CancellationToken ct)
+        {
+            if (order == null || ct.IsCancellationRequested) { return 0; }
+            return 1;
+        }
+
+        public OrderService() { }
+    }
+}
+";
+        let extractor = CSharpExtractor;
+        let fns = extractor.extract(content);
+
+        let line = fns.iter().find(|f| f.name == "Line").unwrap();
+        assert!(line.is_class);
+        assert!(line.decorators.contains(&"record".to_string()));
+        assert_eq!(line.parameters, vec!["string Sku", "int Qty"]);
+
+        let money = fns.iter().find(|f| f.name == "Money").unwrap();
+        assert!(money.decorators.contains(&"struct".to_string()));
+
+        let place = fns.iter().find(|f| f.name == "PlaceAsync").unwrap();
+        assert!(place.is_async);
+        assert!(place.is_method);
+        assert_eq!(
+            place.parameters,
+            vec!["Order order", "CancellationToken ct"]
+        );
+        assert_eq!(place.decorators, vec!["[HttpPost]"]);
+        assert_eq!(place.docstring.as_deref(), Some("Places an order."));
+        assert_eq!(place.qualified_name, "Shop.Orders.OrderService.PlaceAsync");
+        // if, ||
+        assert_eq!(place.complexity, 3);
+
+        let ctor = fns
+            .iter()
+            .find(|f| f.name == "OrderService" && !f.is_class)
+            .unwrap();
+        assert!(ctor.is_method);
+    }
+}
@@ -0,0 +1,439 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/dart.rs — Dart function/class extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead_of,
+    in_scope, max_nesting, nest_scope,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
+
+pub struct DartExtractor;
+
+/// `///` and `/** */` doc comments, with annotations in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["///"],
+    block: &[("/**", "*/")],
+    skip: &["@"],
+};
+
+/// Dart decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    arms: &["switch_statement_case", "switch_expression_case"],
+    binary: &[
+        "logical_and_expression",
+        "logical_or_expression",
+        "if_null_expression",
+    ],
+    operators: &["&&", "||", "??"],
+    nested: &["lambda_expression", "class_declaration"],
+    ifs: &["if_statement"],
+    elses: &[],
+    structures: &[
+        "switch_statement",
+        "switch_expression",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    lambdas: &["function_expression"],
+    jumps: &[],
+    calls: &[],
+};
+
+/// Type declarations, by the decorator they carry.
+const TYPES: &[(&str, &str)] = &[
+    ("class_declaration", "class"),
+    ("mixin_declaration", "mixin"),
+    ("extension_declaration", "extension"),
+    ("enum_declaration", "enum"),
+];
+
+/// Signatures that a `function_body` follows as a sibling.
+const SIGNATURES: &[&str] = &[
+    "function_signature",
+    "getter_signature",
+    "setter_signature",
+    "method_signature",
+];
+
+/// Class modifiers recorded as decorators.
+const MODIFIERS: &[&str] = &["abstract", "sealed", "base", "final", "interface"];
+
+impl Extractor for DartExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_dart::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = Vec::new();
+
+        traverse(tree.root_node(), content, &lines, &mut functions, false, "");
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+/// The grammar keeps a signature and its body as consecutive siblings (at the
+/// top level, in a class body, in a local function), so functions are found
+/// by pairing them rather than by a single node kind.
+fn traverse(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    functions: &mut Vec<FunctionInfo>,
+    in_class: bool,
+    scope: &str,
+) {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+
+    for (i, &child) in children.iter().enumerate() {
+        let kind = child.kind();
+
+        if SIGNATURES.contains(&kind)
+            && let Some(&body) = children.get(i + 1).filter(|b| b.kind() == "function_body")
+        {
+            match parse_function(child, body, &children[..i], content, lines, in_class) {
+                Some(info) => {
+                    let inner = nest_scope(scope, ".", &info.name);
+                    functions.push(in_scope(info, scope, "."));
+                    traverse(body, content, lines, functions, false, &inner);
+                }
+                None => traverse(body, content, lines, functions, false, scope),
+            }
+            continue;
+        }
+        // Already visited along with its signature
+        if kind == "function_body" && i > 0 && SIGNATURES.contains(&children[i - 1].kind()) {
+            continue;
+        }
+
+        if let Some(&(_, decorator)) = TYPES.iter().find(|(k, _)| *k == kind) {
+            let info = parse_type(child, decorator, content, lines);
+            let inner = nest_scope(scope, ".", &info.name);
+            functions.push(in_scope(info, scope, "."));
+            traverse(child, content, lines, functions, true, &inner);
+            continue;
+        }
+
+        traverse(child, content, lines, functions, in_class, scope);
+    }
+}
+
+/// The declared identifier: the `name` field, or the first identifier.
+fn declared_name<'a>(node: Node, content: &'a str) -> Option<&'a str> {
+    let name = node.child_by_field_name("name").or_else(|| {
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .find(|c| c.kind() == "identifier")
+    })?;
+    name.utf8_text(content.as_bytes()).ok()
+}
+
+/// Name of a signature. Constructors keep their named part (`Point.origin`,
+/// `factory Point.fromJson` giving `Point.fromJson`) and operators their
+/// symbol (`operator ==`).
+fn signature_name(signature: Node, content: &str) -> Option<String> {
+    let text = signature.utf8_text(content.as_bytes()).ok()?;
+    let head = text.split('(').next()?.trim();
+    if let Some(at) = head.find("operator") {
+        return Some(head[at..].split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+    if signature.kind().contains("constructor") {
+        return head.split_whitespace().last().map(str::to_string);
+    }
+    declared_name(signature, content).map(str::to_string)
+}
+
+/// One entry per parameter, with `[...]` and `{...}` optional parameters
+/// listed alongside the required ones.
+fn parameters(signature: Node, content: &str) -> Vec<String> {
+    let Some(list) = find_descendant(signature, "formal_parameter_list") else {
+        return vec![];
+    };
+    let mut params = Vec::new();
+    let mut cursor = list.walk();
+    for child in list.named_children(&mut cursor) {
+        if child.kind() == "optional_formal_parameters" {
+            params.extend(optional_parameters(child, content));
+        } else if let Ok(text) = child.utf8_text(content.as_bytes()) {
+            params.push(text.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+    }
+    params
+}
+
+/// The grammar leaves a default value as a sibling of its
+/// `formal_parameter`, so each parameter spans up to its default.
+fn optional_parameters(list: Node, content: &str) -> Vec<String> {
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    let mut cursor = list.walk();
+    for child in list.named_children(&mut cursor) {
+        if child.kind() == "formal_parameter" {
+            spans.push((child.start_byte(), child.end_byte()));
+        } else if !child.is_extra()
+            && let Some(last) = spans.last_mut()
+        {
+            last.1 = child.end_byte();
+        }
+    }
+    spans
+        .into_iter()
+        .map(|(start, end)| {
+            content[start..end]
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+fn find_descendant<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() == kind {
+            return Some(child);
+        }
+        if let Some(found) = find_descendant(child, kind) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// A signature and the `function_body` after it. `preceding` are the
+/// siblings before the signature, whose trailing annotations decorate it.
+fn parse_function(
+    node: Node,
+    body: Node,
+    preceding: &[Node],
+    content: &str,
+    lines: &[&str],
+    is_method: bool,
+) -> Option<FunctionInfo> {
+    // `method_signature` wraps the actual one
+    let signature = match node.kind() {
+        "method_signature" => node.named_child(0)?,
+        _ => node,
+    };
+    let name = signature_name(signature, content)?;
+    let parameters = parameters(signature, content);
+
+    let annotations = preceding
+        .iter()
+        .rev()
+        .take_while(|n| matches!(n.kind(), "annotation" | "marker_annotation"))
+        .count();
+    let annotations = &preceding[preceding.len() - annotations..];
+    let mut decorators: Vec<String> = annotations
+        .iter()
+        .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+        .map(|t| t.trim().to_string())
+        .collect();
+    let mut cursor = node.walk();
+    if node.children(&mut cursor).any(|c| c.kind() == "static") {
+        decorators.push("static".into());
+    }
+    match signature.kind() {
+        "getter_signature" => decorators.push("get".into()),
+        "setter_signature" => decorators.push("set".into()),
+        "factory_constructor_signature" | "redirecting_factory_constructor_signature" => {
+            decorators.push("factory".into())
+        }
+        _ => {}
+    }
+
+    // `async` and `async*` bodies; `sync*` generators are not asynchronous
+    let mut cursor = body.walk();
+    let is_async = body
+        .children(&mut cursor)
+        .any(|c| c.kind().starts_with("async"));
+
+    // Annotations are part of the declaration
+    let start_line = annotations.first().unwrap_or(&node).start_position().row + 1;
+    let end_line = body.end_position().row + 1;
+
+    let complexity = complexity(body, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(body, &name, content, &DECISIONS);
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async,
+        is_method,
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators,
+        complexity,
+        cognitive_complexity,
+        halstead: halstead_of(&[node, body], content),
+        max_nesting: max_nesting(body, &DECISIONS),
+    })
+}
+
+/// Classes, mixins, extensions and enums. An unnamed extension is named
+/// after the type it extends: `on String`.
+fn parse_type(node: Node, decorator: &str, content: &str, lines: &[&str]) -> FunctionInfo {
+    let name = declared_name(node, content)
+        .map(str::to_string)
+        .or_else(|| {
+            let text = node.utf8_text(content.as_bytes()).ok()?;
+            let head = text.split('{').next()?.trim().strip_prefix("extension")?;
+            Some(head.split_whitespace().collect::<Vec<_>>().join(" "))
+        })
+        .unwrap_or_else(|| "?".into());
+
+    let mut cursor = node.walk();
+    let mut decorators: Vec<String> = node
+        .children(&mut cursor)
+        .filter(|c| MODIFIERS.contains(&c.kind()))
+        .map(|c| c.kind().to_string())
+        .collect();
+    decorators.push(decorator.to_string());
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters: vec![],
+        is_async: false,
+        is_method: false,
+        is_class: true,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators,
+        complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_dart_functions() {
+        let content = "
+/// A counter.
+abstract class Counter {
+  int _value = 0;
+
+  /// Current value.
+  int get value => _value;
+
+  @override
+  Future<void> save(String path, {bool force = false}) async {
+    if (force || _value > 0) {
+      await write(path);
+    }
+  }
+}
+
+mixin Logging {
+  void log(String msg) => print(msg);
+}
+
+extension Shout on String {
+  String shout() => toUpperCase();
+}
+
+Stream<int> count(int n) async* {
+  for (var i = 0; i < n; i++) yield i;
+}
+
+void main() {
+  int twice(int x) => x * 2;
+  print(twice(1));
+}
+";
+        let extractor = DartExtractor;
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Counter",
+                "Counter.value",
+                "Counter.save",
+                "Logging",
+                "Logging.log",
+                "Shout",
+                "Shout.shout",
+                "count",
+                "main",
+                "main.twice",
+            ]
+        );
+
+        let counter = &fns[0];
+        assert!(counter.is_class);
+        assert_eq!(counter.decorators, vec!["abstract", "class"]);
+        assert_eq!(counter.docstring.as_deref(), Some("A counter."));
+
+        let value = &fns[1];
+        assert!(value.is_method);
+        assert_eq!(value.decorators, vec!["get"]);
+        assert_eq!(value.docstring.as_deref(), Some("Current value."));
+
+        let save = &fns[2];
+        assert!(save.is_async);
+        assert_eq!(save.decorators, vec!["@override"]);
+        assert_eq!(save.parameters, vec!["String path", "bool force = false"]);
+        assert_eq!((save.line_start, save.line_end), (9, 14));
+        // if, ||
+        assert_eq!(save.complexity, 3);
+
+        assert_eq!(fns[3].decorators, vec!["mixin"]);
+        assert_eq!(fns[5].decorators, vec!["extension"]);
+
+        let count = &fns[7];
+        assert!(count.is_async);
+        assert!(!count.is_method);
+        assert_eq!(count.complexity, 2);
+
+        assert!(!fns[9].is_method);
+    }
+
+    #[test]
+    fn test_dart_patterns_guards_and_operators() {
+        let content = "
+String label(Object? o, String? name, bool strict) {
+  switch (o) {
+    case int n when n > 0 && strict:
+      return 'positive';
+    case String s:
+      return s;
+    default:
+      return name ?? 'none';
+  }
+}
+";
+        let fns = DartExtractor.extract(content);
+        let label = fns.iter().find(|f| f.name == "label").unwrap();
+        // Two cases besides `default`, && in the guard, ??
+        assert_eq!(label.complexity, 5);
+    }
+}
@@ -0,0 +1,406 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/elixir.rs — Elixir function/module extraction via Tree-sitter
+
+use super::{Extractor, halstead, in_scope, nest_scope};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
+
+pub struct ElixirExtractor;
+
+/// Macros that define a function, recorded as a decorator unless plain `def`.
+const DEFS: &[&str] = &[
+    "def",
+    "defp",
+    "defmacro",
+    "defmacrop",
+    "defguard",
+    "defguardp",
+    "defdelegate",
+];
+
+/// Macros that define a module-like container.
+const MODULES: &[&str] = &["defmodule", "defprotocol", "defimpl"];
+
+/// Calls that branch once (`if`, `unless`).
+const BRANCHES: &[&str] = &["if", "unless"];
+
+/// Calls whose `->` clauses are alternatives, one point each.
+const CLAUSES: &[&str] = &["case", "cond", "receive", "with", "try"];
+
+/// Boolean operators that short-circuit.
+const OPERATORS: &[&str] = &["and", "or", "&&", "||"];
+
+impl Extractor for ElixirExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_elixir::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let mut functions = Vec::new();
+
+        traverse(tree.root_node(), content, &mut functions, "");
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+fn traverse(node: Node, content: &str, functions: &mut Vec<FunctionInfo>, scope: &str) {
+    let mut inner = None;
+
+    if node.kind() == "call"
+        && let Some(target) = call_target(node, content)
+    {
+        if DEFS.contains(&target) {
+            if let Some(info) = parse_function(node, content, target) {
+                functions.push(in_scope(info, scope, "."));
+            }
+        } else if MODULES.contains(&target)
+            && let Some(info) = parse_module(node, content, target)
+        {
+            inner = Some(nest_scope(scope, ".", &info.name));
+            functions.push(in_scope(info, scope, "."));
+        }
+    }
+
+    let scope = inner.as_deref().unwrap_or(scope);
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        traverse(child, content, functions, scope);
+    }
+}
+
+/// The macro or function a call invokes, e.g. `def` in `def run(x) do`.
+fn call_target<'a>(call: Node, content: &'a str) -> Option<&'a str> {
+    let target = call
+        .child_by_field_name("target")
+        .filter(|t| t.kind() == "identifier")?;
+    target.utf8_text(content.as_bytes()).ok()
+}
+
+/// The first argument of a call: the head of a definition.
+fn first_argument(call: Node) -> Option<Node> {
+    let mut cursor = call.walk();
+    let arguments = call
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "arguments")?;
+    arguments.named_child(0)
+}
+
+/// Name and parameters of a definition head: `run(a, b) when a > b`,
+/// `run(a, b)` or a bare `run`.
+fn function_head(head: Node, content: &str) -> Option<(String, Vec<String>)> {
+    let text = |n: Node| {
+        let raw = n.utf8_text(content.as_bytes()).unwrap_or("");
+        raw.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+    match head.kind() {
+        // The guard of `def run(a) when a > 0`
+        "binary_operator" if operator(head) == Some("when") => {
+            function_head(head.child_by_field_name("left")?, content)
+        }
+        "call" => {
+            let name = text(head.child_by_field_name("target")?);
+            let mut cursor = head.walk();
+            let parameters = head
+                .named_children(&mut cursor)
+                .find(|c| c.kind() == "arguments")
+                .map(|args| {
+                    let mut cursor = args.walk();
+                    args.named_children(&mut cursor).map(text).collect()
+                })
+                .unwrap_or_default();
+            Some((name, parameters))
+        }
+        "identifier" => Some((text(head), vec![])),
+        _ => None,
+    }
+}
+
+/// The `@doc` (or `@moduledoc`) attribute documenting a definition: the
+/// closest one among the attributes (`@spec`, `@impl`, ...) above `node`.
+/// `@doc false` hides a function and yields `None`.
+fn doc_attribute(node: Node, content: &str, attribute: &str) -> Option<String> {
+    let mut sibling = node.prev_named_sibling();
+    while let Some(attr) = sibling.filter(|s| s.kind() == "unary_operator") {
+        let text = attr.utf8_text(content.as_bytes()).ok()?.trim();
+        if let Some(rest) = text
+            .strip_prefix('@')
+            .and_then(|t| t.strip_prefix(attribute))
+            && rest.starts_with(char::is_whitespace)
+        {
+            return strip_quotes(rest.trim());
+        }
+        sibling = attr.prev_named_sibling();
+    }
+    None
+}
+
+/// The text of a string literal, `"..."` or `"""..."""`, trimmed per line.
+fn strip_quotes(literal: &str) -> Option<String> {
+    let body = ["\"\"\"", "\""]
+        .iter()
+        .find_map(|q| literal.strip_prefix(q)?.strip_suffix(q))?;
+    let doc = body
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+fn parse_function(node: Node, content: &str, keyword: &str) -> Option<FunctionInfo> {
+    let head = first_argument(node)?;
+    let (name, parameters) = function_head(head, content)?;
+    let scores = scores(node, head, &name, content);
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let decorators = (keyword != "def")
+        .then(|| keyword.to_string())
+        .into_iter()
+        .collect();
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: false,
+        is_method: false,
+        is_class: false,
+        docstring: doc_attribute(node, content, "doc"),
+        decorators,
+        complexity: scores.complexity,
+        cognitive_complexity: scores.cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: scores.max_nesting,
+    })
+}
+
+/// `defmodule`, `defprotocol` and `defimpl`; the latter two are recorded as
+/// decorators.
+fn parse_module(node: Node, content: &str, keyword: &str) -> Option<FunctionInfo> {
+    let name = first_argument(node)?
+        .utf8_text(content.as_bytes())
+        .ok()?
+        .to_string();
+
+    // `@moduledoc` is the first thing inside the module's `do` block
+    let mut cursor = node.walk();
+    let docstring = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "do_block")
+        .and_then(|block| {
+            let mut cursor = block.walk();
+            let first = block.named_children(&mut cursor).next()?;
+            let text = first.utf8_text(content.as_bytes()).ok()?.trim();
+            strip_quotes(text.strip_prefix("@moduledoc")?.trim())
+        });
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters: vec![],
+        is_async: false,
+        is_method: false,
+        is_class: true,
+        docstring,
+        decorators: keyword
+            .strip_prefix("def")
+            .filter(|k| *k != "module")
+            .map(str::to_string)
+            .into_iter()
+            .collect(),
+        complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    })
+}
+
+/// Complexity metrics of one definition.
+#[derive(Default)]
+struct Scores {
+    complexity: u32,
+    cognitive_complexity: u32,
+    max_nesting: u32,
+}
+
+/// Score the definition `def` named `name`, whose head is `head`. Control
+/// flow in Elixir is made of macro calls rather than dedicated syntax, so
+/// instead of a [`super::DecisionNodes`] table one walk scores:
+///
+/// - cyclomatic: `if`/`unless` calls, each non-wildcard clause of `case`,
+///   `cond`, `receive`, `with` and `try`, and short-circuit operators.
+///   Anonymous `fn` clauses are not decisions
+/// - cognitive: each of those calls plus its nesting depth, the `else` of an
+///   `if`/`unless`, each run of one boolean operator, and recursive calls
+/// - nesting: the depth of those calls and of anonymous `fn`s
+fn scores(def: Node, head: Node, name: &str, content: &str) -> Scores {
+    let mut scores = Scores {
+        complexity: 1,
+        ..Default::default()
+    };
+    walk(def, head, 0, name, content, &mut scores);
+    scores
+}
+
+fn walk(node: Node, head: Node, nesting: u32, name: &str, content: &str, scores: &mut Scores) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        // The head is a call to `name` too, and may carry a guard
+        if child == head {
+            continue;
+        }
+        let mut child_nesting = nesting;
+        match child.kind() {
+            "call" => match call_target(child, content) {
+                // Nested definitions are scored on their own
+                Some(t) if DEFS.contains(&t) || MODULES.contains(&t) => continue,
+                Some(t) if BRANCHES.contains(&t) || CLAUSES.contains(&t) => {
+                    if BRANCHES.contains(&t) {
+                        scores.complexity += 1;
+                    }
+                    scores.cognitive_complexity += 1 + nesting;
+                    child_nesting += 1;
+                }
+                Some(t) if t == name => scores.cognitive_complexity += 1,
+                _ => {}
+            },
+            "stab_clause" if is_clause(child, content) && !is_catch_all(child, content) => {
+                scores.complexity += 1;
+            }
+            "else_block" if is_branch_else(child, content) => scores.cognitive_complexity += 1,
+            "anonymous_function" => child_nesting += 1,
+            "binary_operator" => {
+                if let Some(op) = operator(child).filter(|op| OPERATORS.contains(op)) {
+                    scores.complexity += 1;
+                    if child.parent().and_then(operator) != Some(op) {
+                        scores.cognitive_complexity += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+        scores.max_nesting = scores.max_nesting.max(child_nesting);
+        walk(child, head, child_nesting, name, content, scores);
+    }
+}
+
+/// The operator token of a `binary_operator`, e.g. `when` or `and`.
+fn operator(node: Node) -> Option<&'static str> {
+    node.child_by_field_name("operator").map(|op| op.kind())
+}
+
+/// Whether an `else` block belongs to an `if` or `unless` call.
+fn is_branch_else(block: Node, content: &str) -> bool {
+    block
+        .parent()
+        .and_then(|do_block| do_block.parent())
+        .and_then(|call| call_target(call, content))
+        .is_some_and(|t| BRANCHES.contains(&t))
+}
+
+/// Whether a `->` clause belongs to one of the [`CLAUSES`] calls, through
+/// its `do`, `else`, `rescue`, `catch` or `after` block.
+fn is_clause(clause: Node, content: &str) -> bool {
+    clause
+        .parent()
+        .and_then(|block| block.parent())
+        .is_some_and(|call| {
+            call.kind() == "call"
+                && call_target(call, content).is_some_and(|t| CLAUSES.contains(&t))
+        })
+}
+
+/// `_ ->` in a `case`, or `true ->` closing a `cond`.
+fn is_catch_all(clause: Node, content: &str) -> bool {
+    clause
+        .named_child(0)
+        .and_then(|pattern| pattern.utf8_text(content.as_bytes()).ok())
+        .is_some_and(|p| matches!(p.trim(), "_" | "true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_elixir_functions() {
+        let content = r#"
+defmodule Shop.Cart do
+  @moduledoc "A shopping cart."
+
+  @doc """
+  Adds an item.
+  """
+  @spec add(map, map) :: map
+  def add(cart, item) when is_map(item) do
+    if item.qty > 0 and item.price > 0 do
+      Map.update(cart, item.id, item, fn old -> old end)
+    else
+      cart
+    end
+  end
+
+  defp total(cart) do
+    case cart do
+      %{} -> 0
+      [_ | _] -> 1
+      _ -> 2
+    end
+  end
+
+  def empty, do: %{}
+end
+"#;
+        let extractor = ElixirExtractor;
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Shop.Cart",
+                "Shop.Cart.add",
+                "Shop.Cart.total",
+                "Shop.Cart.empty"
+            ]
+        );
+
+        let cart = &fns[0];
+        assert!(cart.is_class);
+        assert_eq!(cart.docstring.as_deref(), Some("A shopping cart."));
+
+        let add = &fns[1];
+        assert_eq!(add.parameters, vec!["cart", "item"]);
+        assert_eq!(add.docstring.as_deref(), Some("Adds an item."));
+        assert!(add.decorators.is_empty());
+        // if, and; the `fn` clause is not a branch
+        assert_eq!(add.complexity, 3);
+        // if, and, else
+        assert_eq!(add.cognitive_complexity, 3);
+        // The `fn` inside the `if`
+        assert_eq!(add.max_nesting, 2);
+
+        let total = &fns[2];
+        assert_eq!(total.decorators, vec!["defp"]);
+        // Two clauses besides `_`
+        assert_eq!(total.complexity, 3);
+        assert_eq!(total.cognitive_complexity, 1);
+        assert_eq!(total.max_nesting, 1);
+
+        assert!(fns[3].parameters.is_empty());
+    }
+}
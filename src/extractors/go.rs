@@ -1,28 +1,71 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/go.rs — Go function extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, parameter_list,
+};
 use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use tree_sitter::Node;
 
 pub struct GoExtractor;
 
+/// Go doc comments: the `//` block directly above, past any `//go:` directives.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["//"],
+    block: &[],
+    skip: &["//go:"],
+};
+
+/// Go decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "for_statement",
+        "expression_case",
+        "type_case",
+        "communication_case",
+    ],
+    arms: &[],
+    binary: &["binary_expression"],
+    operators: &["&&", "||"],
+    nested: &["function_declaration", "method_declaration"],
+    ifs: &["if_statement"],
+    elses: &[],
+    structures: &[
+        "for_statement",
+        "expression_switch_statement",
+        "type_switch_statement",
+        "select_statement",
+    ],
+    lambdas: &["func_literal"],
+    jumps: &[
+        ("break_statement", "label_name"),
+        ("continue_statement", "label_name"),
+        ("goto_statement", "label_name"),
+    ],
+    calls: &["call_expression"],
+};
+
 impl Extractor for GoExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&tree_sitter_go::LANGUAGE.into()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_go::LANGUAGE.into(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions);
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let package = root
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "package_clause")
+            .and_then(|n| n.named_child(0))
+            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+            .unwrap_or("");
+
+        traverse(root, content, &lines, &mut functions, package);
 
         functions.sort_by_key(|f| f.line_start);
         functions
@@ -34,22 +77,40 @@ fn traverse(
     content: &str,
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
+    package: &str,
 ) {
     let kind = node.kind();
 
     if kind == "function_declaration" {
         if let Some(info) = parse_function(node, content, lines, false) {
-            functions.push(info);
+            functions.push(in_scope(info, package, "."));
         }
     } else if kind == "method_declaration"
         && let Some(info) = parse_function(node, content, lines, true)
     {
-        functions.push(info);
+        let scope = match receiver_type(node, content) {
+            Some(receiver) if package.is_empty() => receiver,
+            Some(receiver) => format!("{}.{}", package, receiver),
+            None => package.to_string(),
+        };
+        functions.push(in_scope(info, &scope, "."));
     }
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        traverse(child, content, lines, functions);
+        traverse(child, content, lines, functions, package);
+    }
+}
+
+/// A method's receiver as Go writes it in qualified names: `(*T)` or `T`.
+fn receiver_type(method: Node, content: &str) -> Option<String> {
+    let receiver = method.child_by_field_name("receiver")?.named_child(0)?;
+    let ty = receiver.child_by_field_name("type")?;
+    let text = |n: Node| n.utf8_text(content.as_bytes()).ok().map(str::to_string);
+    if ty.kind() == "pointer_type" {
+        Some(format!("(*{})", text(ty.named_child(0)?)?))
+    } else {
+        text(ty)
     }
 }
 
@@ -60,19 +121,22 @@ fn parse_function(
     is_method: bool,
 ) -> Option<FunctionInfo> {
     let mut name = String::new();
-    let mut params_str = String::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
-        if kind == "identifier" || kind == "field_identifier" {
-            if name.is_empty() {
-                name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
-            }
-        } else if kind == "parameter_list" {
-            params_str = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+        if (kind == "identifier" || kind == "field_identifier") && name.is_empty() {
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         }
     }
+    // By field: the receiver and a multi-value result are parameter lists too
+    let parameters = node
+        .child_by_field_name("parameters")
+        .map(|list| parameter_list(list, content))
+        .unwrap_or_default();
 
     if name.is_empty() {
         return None;
@@ -81,31 +145,25 @@ fn parse_function(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
-
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async: false,
         is_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
@@ -118,6 +176,8 @@ mod tests {
         let content = "
 package main
 func Hello() {}
+// Get looks up a record.
+//go:noinline
 func (r *Repo) Get(id int) string {
     return \"\"
 }
@@ -126,14 +186,17 @@ func (r *Repo) Get(id int) string {
         let mut fns = extractor.extract(content);
         fns.sort_by(|a, b| a.name.cmp(&b.name));
         assert_eq!(fns.len(), 2);
-        
+
         let g = fns.iter().find(|f| f.name == "Get").unwrap();
         assert!(g.is_method);
-        // Usually `parameter_list` extracts the parameters text as `(id int)`.
-        // My simple split on `,` gets `id int`.
+        // The receiver and result lists are not parameters
         assert_eq!(g.parameters, vec!["id int"]);
-        
+        assert_eq!(g.qualified_name, "main.(*Repo).Get");
+        assert_eq!(g.parent.as_deref(), Some("main.(*Repo)"));
+        assert_eq!(g.docstring.as_deref(), Some("Get looks up a record."));
+
         let h = fns.iter().find(|f| f.name == "Hello").unwrap();
         assert!(!h.is_method);
+        assert_eq!(h.docstring, None);
     }
 }
@@ -0,0 +1,305 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/haskell.rs — Haskell binding/type extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead_of,
+    in_scope, max_nesting,
+};
+use crate::models::{FunctionInfo, Halstead};
+use std::collections::HashMap;
+use tree_sitter::Node;
+
+pub struct HaskellExtractor;
+
+/// Haddock comments (`-- |`, `{-| -}`) and the `--` lines continuing them.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["-- |", "--"],
+    block: &[("{-|", "-}")],
+    skip: &[],
+};
+
+/// Haskell decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &["conditional", "guards"],
+    arms: &["alternative"],
+    binary: &["infix"],
+    operators: &["&&", "||"],
+    nested: &[],
+    ifs: &["conditional"],
+    elses: &[],
+    structures: &["case", "multi_way_if", "lambda_case"],
+    lambdas: &["lambda"],
+    jumps: &[],
+    calls: &["apply"],
+};
+
+/// Type declarations, by the decorator they carry.
+const TYPES: &[(&str, &str)] = &[
+    ("data_type", "data"),
+    ("newtype", "newtype"),
+    ("class", "class"),
+];
+
+impl Extractor for HaskellExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_haskell::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let root = tree.root_node();
+        let text = |n: Node| n.utf8_text(content.as_bytes()).unwrap_or("").trim();
+
+        let mut cursor = root.walk();
+        let module = root
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "header")
+            .and_then(|h| h.child_by_field_name("module"))
+            .map_or("", text);
+        let mut cursor = root.walk();
+        let declarations = root
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "declarations")
+            .unwrap_or(root);
+
+        // The equations of a function are consecutive declarations sharing a
+        // name; its signature may come anywhere at the top level
+        let mut signatures = HashMap::new();
+        let mut bindings: Vec<(&str, Vec<Node>)> = Vec::new();
+        let mut functions = Vec::new();
+
+        let mut cursor = declarations.walk();
+        for decl in declarations.named_children(&mut cursor) {
+            let kind = decl.kind();
+            let Some(name) = decl.child_by_field_name("name").map(text) else {
+                continue;
+            };
+            match kind {
+                "signature" => {
+                    signatures.insert(name, decl);
+                }
+                "function" | "bind" => match bindings.last_mut() {
+                    Some((last, equations)) if *last == name => equations.push(decl),
+                    _ => bindings.push((name, vec![decl])),
+                },
+                _ => {
+                    if let Some(&(_, decorator)) = TYPES.iter().find(|(k, _)| *k == kind) {
+                        let info = parse_type(decl, name, decorator, &lines);
+                        functions.push(in_scope(info, module, "."));
+                    }
+                }
+            }
+        }
+
+        for (name, equations) in bindings {
+            let signature = signatures.get(name).copied();
+            let info = parse_binding(name, &equations, signature, content, &lines);
+            functions.push(in_scope(info, module, "."));
+        }
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+/// `s` split on `separator` outside of brackets.
+fn split_top_level<'a>(s: &'a str, separator: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ if depth == 0 && s[i..].starts_with(separator) && i >= start => {
+                parts.push(s[start..i].trim());
+                start = i + separator.len();
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+/// Argument types of a signature, `a -> [a] -> Bool` giving `a`, `[a]`,
+/// without its `forall` and class constraints.
+fn signature_parameters(signature: Node, content: &str) -> Vec<String> {
+    let Some(ty) = signature
+        .child_by_field_name("type")
+        .and_then(|t| t.utf8_text(content.as_bytes()).ok())
+    else {
+        return vec![];
+    };
+    let ty = ty.split_whitespace().collect::<Vec<_>>().join(" ");
+    let ty = split_top_level(&ty, "=>")
+        .pop()
+        .unwrap_or_default()
+        .to_string();
+    let ty = match ty.strip_prefix("forall ") {
+        Some(rest) => rest
+            .split_once('.')
+            .map_or(rest, |(_, t)| t)
+            .trim()
+            .to_string(),
+        None => ty,
+    };
+    let mut parts = split_top_level(&ty, "->");
+    parts.pop();
+    parts.into_iter().map(str::to_string).collect()
+}
+
+/// One function, merged from its equations. Every equation after the first
+/// is an alternative, and so adds to the complexity.
+fn parse_binding(
+    name: &str,
+    equations: &[Node],
+    signature: Option<Node>,
+    content: &str,
+    lines: &[&str],
+) -> FunctionInfo {
+    let first = equations[0];
+    let last = equations[equations.len() - 1];
+    let start_line = signature.unwrap_or(first).start_position().row + 1;
+    let end_line = last.end_position().row + 1;
+
+    let parameters = match signature {
+        Some(signature) => signature_parameters(signature, content),
+        None => first
+            .child_by_field_name("patterns")
+            .map(|patterns| {
+                let mut cursor = patterns.walk();
+                patterns
+                    .named_children(&mut cursor)
+                    .filter_map(|p| p.utf8_text(content.as_bytes()).ok())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    let complexity = equations
+        .iter()
+        .map(|eq| complexity(*eq, content, lines, &DECISIONS) - 1)
+        .sum::<u32>()
+        + equations.len() as u32;
+    let cognitive_complexity = equations
+        .iter()
+        .map(|eq| cognitive_complexity(*eq, name, content, &DECISIONS))
+        .sum();
+    let max_nesting = equations
+        .iter()
+        .map(|eq| max_nesting(*eq, &DECISIONS))
+        .max()
+        .unwrap_or(0);
+
+    FunctionInfo {
+        name: name.to_string(),
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: false,
+        is_method: false,
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators: vec![],
+        complexity,
+        cognitive_complexity,
+        halstead: halstead_of(equations, content),
+        max_nesting,
+    }
+}
+
+fn parse_type(node: Node, name: &str, decorator: &str, lines: &[&str]) -> FunctionInfo {
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    FunctionInfo {
+        name: name.to_string(),
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters: vec![],
+        is_async: false,
+        is_method: false,
+        is_class: true,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators: vec![decorator.to_string()],
+        complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_haskell_bindings() {
+        let content = "
+module Data.Shape (area) where
+
+-- | Area of a shape.
+area :: Shape -> Double
+area (Circle r) = pi * r * r
+area (Rect w h)
+  | w < 0 || h < 0 = 0
+  | otherwise = w * h
+
+data Shape = Circle Double | Rect Double Double
+
+member :: Eq a => a -> [a] -> Bool
+member x xs = any (== x) xs
+
+origin = (0, 0)
+";
+        let extractor = HaskellExtractor;
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Data.Shape.area",
+                "Data.Shape.Shape",
+                "Data.Shape.member",
+                "Data.Shape.origin"
+            ]
+        );
+
+        let area = &fns[0];
+        assert_eq!(area.line_start, 5);
+        assert_eq!(area.line_end, 9);
+        assert_eq!(area.parameters, vec!["Shape"]);
+        assert_eq!(area.docstring.as_deref(), Some("Area of a shape."));
+        // second equation, two guards, ||
+        assert_eq!(area.complexity, 5);
+
+        let shape = &fns[1];
+        assert!(shape.is_class);
+        assert_eq!(shape.decorators, vec!["data"]);
+
+        assert_eq!(fns[2].parameters, vec!["a", "[a]"]);
+        assert!(fns[3].parameters.is_empty());
+    }
+
+    #[test]
+    fn test_haskell_case_alternatives_and_guards() {
+        let content = "
+classify :: Int -> Bool -> String
+classify n flag = case n of
+  0 -> \"zero\"
+  m | m > 0 && flag -> \"positive\"
+    | otherwise -> \"negative\"
+  _ -> \"other\"
+";
+        let fns = HaskellExtractor.extract(content);
+        let classify = fns.iter().find(|f| f.name == "classify").unwrap();
+        // Two alternatives besides `_`, two guards, &&
+        assert_eq!(classify.complexity, 6);
+    }
+}
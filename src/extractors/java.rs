@@ -1,31 +1,79 @@
 // Author: kelexine (https://github.com/kelexine)
-// extractors/java.rs — Java/Kotlin/C# function extraction via Tree-sitter
+// extractors/java.rs — Java function extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
 
 pub struct JavaExtractor;
 
+/// Javadoc, with annotations in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &[],
+    block: &[("/**", "*/")],
+    skip: &["@"],
+};
+
+/// Java decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "for_statement",
+        "enhanced_for_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    arms: &["switch_label"],
+    binary: &["binary_expression"],
+    operators: &["&&", "||"],
+    nested: &[
+        "method_declaration",
+        "constructor_declaration",
+        "class_declaration",
+    ],
+    ifs: &["if_statement"],
+    elses: &[],
+    structures: &[
+        "switch_expression",
+        "for_statement",
+        "enhanced_for_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    lambdas: &["lambda_expression"],
+    jumps: &[
+        ("break_statement", "identifier"),
+        ("continue_statement", "identifier"),
+    ],
+    calls: &["method_invocation"],
+};
+
 impl Extractor for JavaExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        // Use Java grammar for now, although the module handles .kt and .cs via extensions.
-        // To be fully accurate for Kotlin/C#, they need their own grammars/extractors,
-        // but since this replaces the regex JavaExtractor, we'll map it to tree-sitter-java.
-        if parser.set_language(&tree_sitter_java::LANGUAGE.into()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_java::LANGUAGE.into(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions, false);
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let package = root
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "package_declaration")
+            .and_then(|n| n.named_child(0))
+            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+            .unwrap_or("");
+
+        traverse(root, content, &lines, &mut functions, false, package);
 
         functions.sort_by_key(|f| f.line_start);
         functions
@@ -38,24 +86,44 @@ fn traverse(
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
     in_class: bool,
+    scope: &str,
 ) {
     let kind = node.kind();
+    let is_type = matches!(
+        kind,
+        "class_declaration" | "record_declaration" | "interface_declaration" | "enum_declaration"
+    );
 
     if kind == "method_declaration" || kind == "constructor_declaration" {
         if let Some(info) = parse_method(node, content, lines, in_class) {
-            functions.push(info);
+            functions.push(in_scope(info, scope, "."));
         }
-    } else if (kind == "class_declaration" || kind == "record_declaration" || kind == "interface_declaration")
+    } else if is_type
+        && kind != "enum_declaration"
         && let Some(info) = parse_class(node, content, lines)
     {
-        functions.push(info);
+        functions.push(in_scope(info, scope, "."));
     }
 
     let is_class_body = kind == "class_body";
+    // Types scope their members, nested types included: `pkg.Outer.Inner.run`
+    let inner = is_type
+        .then(|| node.child_by_field_name("name"))
+        .flatten()
+        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+        .map(|name| nest_scope(scope, ".", name));
+    let scope = inner.as_deref().unwrap_or(scope);
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        traverse(child, content, lines, functions, in_class || is_class_body);
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            in_class || is_class_body,
+            scope,
+        );
     }
 }
 
@@ -66,15 +134,18 @@ fn parse_method(
     is_method: bool,
 ) -> Option<FunctionInfo> {
     let mut name = String::new();
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if kind == "identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "formal_parameters" {
-            params_str = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            parameters = parameter_list(child, content);
         }
     }
 
@@ -85,41 +156,38 @@ fn parse_method(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
-
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async: false,
         is_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
-fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInfo> {
+fn parse_class(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
     let mut name = String::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         if child.kind() == "identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
             break;
         }
     }
@@ -133,15 +201,20 @@ fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInf
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters: vec![],
         is_async: false,
         is_method: false,
         is_class: true,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
     })
 }
 
@@ -152,26 +225,37 @@ mod tests {
     #[test]
     fn test_extract_java_functions() {
         let content = "
+package app.cli;
+
 public class Main {
     public static void main(String[] args) {}
+    /**
+     * Adds two numbers.
+     */
+    @Deprecated
     private int calc(int a, int b) { return a + b; }
 }
 ";
         let extractor = JavaExtractor;
         let mut fns = extractor.extract(content);
         fns.sort_by(|a, b| a.name.cmp(&b.name));
-        
+
         assert_eq!(fns.len(), 3);
-        
+
         let c = fns.iter().find(|f| f.name == "Main").unwrap();
         assert!(c.is_class);
-        
+        assert_eq!(c.qualified_name, "app.cli.Main");
+
         let m = fns.iter().find(|f| f.name == "main").unwrap();
         assert!(m.is_method);
+        assert_eq!(m.qualified_name, "app.cli.Main.main");
+        assert_eq!(m.parent.as_deref(), Some("app.cli.Main"));
         assert_eq!(m.parameters, vec!["String[] args"]);
-        
+
         let calc = fns.iter().find(|f| f.name == "calc").unwrap();
         assert!(calc.is_method);
         assert_eq!(calc.parameters, vec!["int a", "int b"]);
+        assert_eq!(calc.docstring.as_deref(), Some("Adds two numbers."));
+        assert_eq!(m.docstring, None);
     }
 }
@@ -1,14 +1,62 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/javascript.rs — JavaScript/TypeScript function/class extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Language, Node, Parser};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::{Language, Node};
 
 pub struct JavascriptExtractor {
     language: Language,
 }
 
+/// JSDoc/TSDoc, with decorators in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &[],
+    block: &[("/**", "*/")],
+    skip: &["@"],
+};
+
+/// JavaScript/TypeScript decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "for_statement",
+        "for_in_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    arms: &["switch_case"],
+    binary: &["binary_expression"],
+    operators: &["&&", "||", "??"],
+    nested: &[
+        "function_declaration",
+        "method_definition",
+        "class_declaration",
+    ],
+    ifs: &["if_statement"],
+    elses: &["else_clause"],
+    structures: &[
+        "switch_statement",
+        "for_statement",
+        "for_in_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    lambdas: &["arrow_function", "function_expression"],
+    jumps: &[
+        ("break_statement", "statement_identifier"),
+        ("continue_statement", "statement_identifier"),
+    ],
+    calls: &["call_expression"],
+};
+
 impl JavascriptExtractor {
     pub fn new(language: Language) -> Self {
         Self { language }
@@ -17,20 +65,14 @@ impl JavascriptExtractor {
 
 impl Extractor for JavascriptExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&self.language).is_err() {
+        let Some(tree) = super::parse(&self.language, content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions, false);
+        traverse(tree.root_node(), content, &lines, &mut functions, false, "");
 
         functions.retain(|f| f.name != "?");
         functions.sort_by_key(|f| f.line_start);
@@ -44,16 +86,22 @@ fn traverse(
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
     _in_class: bool,
+    scope: &str,
 ) {
     let kind = node.kind();
 
-    if kind == "function_declaration" || kind == "generator_function_declaration" || kind == "method_definition" || kind == "arrow_function" || kind == "function" {
+    if kind == "function_declaration"
+        || kind == "generator_function_declaration"
+        || kind == "method_definition"
+        || kind == "arrow_function"
+        || kind == "function"
+    {
         if let Some(info) = parse_function(node, content, lines, kind == "method_definition") {
-            functions.push(info);
+            functions.push(in_scope(info, scope, "."));
         }
     } else if kind == "class_declaration" || kind == "class" {
         if let Some(info) = parse_class(node, content, lines) {
-            functions.push(info);
+            functions.push(in_scope(info, scope, "."));
         }
     } else if kind == "lexical_declaration" || kind == "variable_declaration" {
         // Find arrow functions or anonymous functions assigned to variables
@@ -62,20 +110,39 @@ fn traverse(
             if child.kind() == "variable_declarator"
                 && let Some(info) = parse_variable_declarator(child, content, lines)
             {
-                functions.push(info);
+                functions.push(in_scope(info, scope, "."));
             }
         }
     }
 
     let is_class_body = kind == "class_body";
+    // Classes and named functions scope what is defined inside them
+    let inner = match kind {
+        "class_declaration"
+        | "class"
+        | "function_declaration"
+        | "generator_function_declaration"
+        | "method_definition" => node.child_by_field_name("name"),
+        _ => None,
+    }
+    .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+    .map(|name| nest_scope(scope, ".", name));
+    let scope = inner.as_deref().unwrap_or(scope);
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        // Arrow functions and nested functions can be inside others, 
+        // Arrow functions and nested functions can be inside others,
         // we keep traversing unless we already processed an arrow function node itself above.
-        // Actually, parse_function doesn't traverse into the body to find nested functions, 
+        // Actually, parse_function doesn't traverse into the body to find nested functions,
         // so we SHOULD traverse into the body of functions to find nested ones!
-        traverse(child, content, lines, functions, _in_class || is_class_body);
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            _in_class || is_class_body,
+            scope,
+        );
     }
 }
 
@@ -87,19 +154,22 @@ fn parse_function(
 ) -> Option<FunctionInfo> {
     let mut name = String::new();
     let mut is_async = false;
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if (kind == "identifier" || kind == "property_identifier") && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "formal_parameters" {
-            params_str = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            parameters = parameter_list(child, content);
         }
     }
 
-    // For arrow functions inside variable declarators, the name might not be here. 
+    // For arrow functions inside variable declarators, the name might not be here.
     // It's handled by `parse_variable_declarator`.
     // But if we encounter an arrow_function here, we can give it a default name "?" if missing.
 
@@ -117,39 +187,29 @@ fn parse_function(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
-
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async,
         is_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
-fn parse_variable_declarator(
-    node: Node,
-    content: &str,
-    lines: &[&str],
-) -> Option<FunctionInfo> {
+fn parse_variable_declarator(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
     let mut name = String::new();
     let mut func_node = None;
 
@@ -157,7 +217,10 @@ fn parse_variable_declarator(
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if kind == "identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "arrow_function" || kind == "function" {
             func_node = Some(child);
         }
@@ -168,6 +231,7 @@ fn parse_variable_declarator(
     {
         let mut info = parse_function(fnode, content, lines, false)?;
         // Override the name since parse_function wouldn't find it inside the arrow_function
+        info.cognitive_complexity = cognitive_complexity(fnode, &name, content, &DECISIONS);
         info.name = name;
         // Async check for arrow function
         let text = fnode.utf8_text(content.as_bytes()).unwrap_or("");
@@ -179,14 +243,17 @@ fn parse_variable_declarator(
     None
 }
 
-fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInfo> {
+fn parse_class(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
     let mut name = String::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if (kind == "identifier" || kind == "type_identifier") && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         }
     }
 
@@ -199,15 +266,20 @@ fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInf
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters: vec![],
         is_async: false,
         is_method: false,
         is_class: true,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
     })
 }
 
@@ -221,6 +293,7 @@ mod tests {
 export async function fetchData(url) {}
 const process = (data) => {}
 class Calculator {
+    /** Sum of `a` and `b`. */
     add(a, b) {}
 }
 ";
@@ -228,18 +301,22 @@ class Calculator {
         let mut fns = extractor.extract(content);
         // Sort by name for deterministic test order
         fns.sort_by(|a, b| a.name.cmp(&b.name));
-        
+
         assert_eq!(fns.len(), 4);
-        
+
         let c = fns.iter().find(|f| f.name == "Calculator").unwrap();
         assert!(c.is_class);
-        
+
         let a = fns.iter().find(|f| f.name == "add").unwrap();
         assert!(a.is_method);
-        
+        assert_eq!(a.qualified_name, "Calculator.add");
+        assert_eq!(a.docstring.as_deref(), Some("Sum of `a` and `b`."));
+
         let fd = fns.iter().find(|f| f.name == "fetchData").unwrap();
         assert!(fd.is_async);
-        
+        assert_eq!(fd.qualified_name, "fetchData");
+        assert_eq!(fd.parent, None);
+
         let p = fns.iter().find(|f| f.name == "process").unwrap();
         assert!(!p.is_async);
     }
@@ -0,0 +1,400 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/kotlin.rs — Kotlin function/class extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
+
+pub struct KotlinExtractor;
+
+/// KDoc, with annotations in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &[],
+    block: &[("/**", "*/")],
+    skip: &["@"],
+};
+
+/// Kotlin decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_expression",
+        "for_statement",
+        "while_statement",
+        "do_while_statement",
+        "catch_block",
+    ],
+    arms: &["when_entry"],
+    binary: &["binary_expression"],
+    operators: &["&&", "||"],
+    nested: &[
+        "function_declaration",
+        "class_declaration",
+        "object_declaration",
+    ],
+    ifs: &["if_expression"],
+    elses: &[],
+    structures: &[
+        "when_expression",
+        "for_statement",
+        "while_statement",
+        "do_while_statement",
+        "catch_block",
+    ],
+    lambdas: &["lambda_literal", "anonymous_function"],
+    jumps: &[],
+    calls: &["call_expression"],
+};
+
+impl Extractor for KotlinExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_kotlin_ng::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = Vec::new();
+
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let package = root
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "package_header")
+            .and_then(|n| n.named_child(0))
+            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+            .unwrap_or("");
+
+        traverse(root, content, &lines, &mut functions, false, package);
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+fn traverse(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    functions: &mut Vec<FunctionInfo>,
+    in_class: bool,
+    scope: &str,
+) {
+    let kind = node.kind();
+    let is_type = matches!(
+        kind,
+        "class_declaration" | "object_declaration" | "companion_object"
+    );
+    let mut inner = None;
+
+    if kind == "function_declaration" {
+        if let Some(mut info) = parse_function(node, content, lines, in_class) {
+            // An extension function keeps its receiver in the name, `String.shout`,
+            // so the receiver is not mistaken for a type of the enclosing package
+            if let Some(receiver) = receiver_type(node, content) {
+                info.name = format!("{}.{}", receiver, info.name);
+            }
+            inner = Some(nest_scope(scope, ".", &info.name));
+            functions.push(in_scope(info, scope, "."));
+        }
+    } else if kind == "property_declaration" {
+        if let Some(info) = parse_property(node, content, lines, in_class) {
+            functions.push(in_scope(info, scope, "."));
+        }
+    } else if is_type && let Some(info) = parse_class(node, content, lines) {
+        inner = Some(nest_scope(scope, ".", &info.name));
+        functions.push(in_scope(info, scope, "."));
+    }
+
+    let is_class_body = kind == "class_body" || kind == "enum_class_body";
+    let scope = inner.as_deref().unwrap_or(scope);
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            in_class || is_class_body,
+            scope,
+        );
+    }
+}
+
+/// The declared name: the `name` field, or the first identifier child.
+fn declared_name(node: Node, content: &str) -> Option<String> {
+    let name = node.child_by_field_name("name").or_else(|| {
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor).find(|c| {
+            matches!(
+                c.kind(),
+                "simple_identifier" | "identifier" | "type_identifier"
+            )
+        })
+    })?;
+    let text = name.utf8_text(content.as_bytes()).ok()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// The `String` of `fun String.shout()`, if the function has a receiver.
+fn receiver_type<'a>(node: Node, content: &'a str) -> Option<&'a str> {
+    let mut cursor = node.walk();
+    let receiver = node
+        .named_children(&mut cursor)
+        .take_while(|c| !matches!(c.kind(), "simple_identifier" | "identifier"))
+        .find(|c| matches!(c.kind(), "receiver_type" | "user_type" | "nullable_type"))?;
+    let text = receiver.utf8_text(content.as_bytes()).ok()?;
+    Some(text.trim().trim_end_matches('.'))
+}
+
+/// Modifier keywords and annotations of a declaration, e.g. `data`, `@JvmStatic`.
+fn modifiers(node: Node, content: &str) -> (Vec<String>, Vec<String>) {
+    let mut keywords = Vec::new();
+    let mut annotations = Vec::new();
+    let mut cursor = node.walk();
+    let Some(list) = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "modifiers")
+    else {
+        return (keywords, annotations);
+    };
+    let mut cursor = list.walk();
+    for modifier in list.named_children(&mut cursor) {
+        let text = modifier
+            .utf8_text(content.as_bytes())
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        if modifier.kind() == "annotation" {
+            annotations.push(text);
+        } else {
+            keywords.push(text);
+        }
+    }
+    (keywords, annotations)
+}
+
+/// One entry per declared parameter, without the `= default` expressions
+/// that the grammar puts alongside them.
+fn parameters(node: Node, content: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    let Some(list) = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "function_value_parameters")
+    else {
+        return vec![];
+    };
+    let mut cursor = list.walk();
+    list.named_children(&mut cursor)
+        .filter(|p| matches!(p.kind(), "parameter" | "function_value_parameter"))
+        .map(|p| {
+            let raw = p.utf8_text(content.as_bytes()).unwrap_or("");
+            raw.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .collect()
+}
+
+fn parse_function(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    is_method: bool,
+) -> Option<FunctionInfo> {
+    let name = declared_name(node, content)?;
+    let (keywords, decorators) = modifiers(node, content);
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters: parameters(node, content),
+        is_async: keywords.iter().any(|k| k == "suspend"),
+        is_method,
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators,
+        complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
+    })
+}
+
+/// A property with a custom getter, reported as a function named after the
+/// property. Plain properties and bodiless `get` accessors are skipped.
+fn parse_property(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    is_method: bool,
+) -> Option<FunctionInfo> {
+    let mut cursor = node.walk();
+    let getter = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "getter" && c.named_child_count() > 0)?;
+    let mut cursor = node.walk();
+    let name = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "variable_declaration")
+        .and_then(|v| declared_name(v, content))?;
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let complexity = complexity(getter, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(getter, &name, content, &DECISIONS);
+    let (_, mut decorators) = modifiers(node, content);
+    decorators.push("get".into());
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters: vec![],
+        is_async: false,
+        is_method,
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators,
+        complexity,
+        cognitive_complexity,
+        halstead: halstead(getter, content),
+        max_nesting: max_nesting(getter, &DECISIONS),
+    })
+}
+
+/// Classes, interfaces and objects. `data`, `sealed`, `enum` and the like
+/// are recorded as decorators, as are `object` and `companion`.
+fn parse_class(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
+    let (keywords, annotations) = modifiers(node, content);
+    let unnamed = if node.kind() == "companion_object" {
+        "Companion"
+    } else {
+        "?"
+    };
+    let name = declared_name(node, content).unwrap_or_else(|| unnamed.into());
+
+    const KINDS: &[&str] = &["data", "sealed", "enum", "abstract", "inner", "value"];
+    let mut decorators: Vec<String> = keywords
+        .into_iter()
+        .filter(|k| KINDS.contains(&k.as_str()))
+        .collect();
+    match node.kind() {
+        "object_declaration" => decorators.push("object".into()),
+        "companion_object" => decorators.push("companion".into()),
+        _ => {}
+    }
+    decorators.extend(annotations);
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters: vec![],
+        is_async: false,
+        is_method: false,
+        is_class: true,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators,
+        complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_kotlin_functions() {
+        let content = "
+package com.example
+
+/** A point in space. */
+data class Point(val x: Int, val y: Int) {
+    val norm: Int
+        get() {
+            return if (x > y) x else y
+        }
+
+    suspend fun move(dx: Int, dy: Int = 0): Point = Point(x + dx, y + dy)
+}
+
+object Registry {
+    fun register(name: String) {}
+}
+
+fun String.shout(): String = uppercase()
+";
+        let extractor = KotlinExtractor;
+        let fns = extractor.extract(content);
+
+        let point = fns.iter().find(|f| f.name == "Point").unwrap();
+        assert!(point.is_class);
+        assert!(point.decorators.contains(&"data".to_string()));
+        assert_eq!(point.qualified_name, "com.example.Point");
+        assert_eq!(point.docstring.as_deref(), Some("A point in space."));
+
+        let norm = fns.iter().find(|f| f.name == "norm").unwrap();
+        assert!(norm.is_method);
+        assert!(norm.decorators.contains(&"get".to_string()));
+        assert_eq!(norm.complexity, 2);
+
+        let mv = fns.iter().find(|f| f.name == "move").unwrap();
+        assert!(mv.is_async);
+        assert!(mv.is_method);
+        assert_eq!(mv.parameters, vec!["dx: Int", "dy: Int"]);
+        assert_eq!(mv.qualified_name, "com.example.Point.move");
+
+        let registry = fns.iter().find(|f| f.name == "Registry").unwrap();
+        assert!(registry.decorators.contains(&"object".to_string()));
+        let register = fns.iter().find(|f| f.name == "register").unwrap();
+        assert_eq!(register.qualified_name, "com.example.Registry.register");
+
+        let shout = fns.iter().find(|f| f.name == "String.shout").unwrap();
+        assert!(!shout.is_method);
+        assert_eq!(shout.parent.as_deref(), Some("com.example"));
+        assert_eq!(shout.qualified_name, "com.example.String.shout");
+    }
+
+    #[test]
+    fn test_kotlin_decision_nodes() {
+        let content = "
+fun classify(x: Int, y: Int): String {
+    if (x > 0 && y > 0 || x == y) {
+        return \"both\"
+    }
+    return when (x) {
+        1 -> \"one\"
+        2, 3 -> \"few\"
+        else -> \"many\"
+    }
+}
+";
+        let fns = KotlinExtractor.extract(content);
+        let classify = fns.iter().find(|f| f.name == "classify").unwrap();
+        // if, &&, || and two non-default when entries
+        assert_eq!(classify.complexity, 6);
+        // if, one mixed run of &&/||, when
+        assert_eq!(classify.cognitive_complexity, 4);
+    }
+}
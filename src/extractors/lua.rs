@@ -0,0 +1,188 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/lua.rs — Lua function extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, parameter_list,
+};
+use crate::models::FunctionInfo;
+use tree_sitter::Node;
+
+pub struct LuaExtractor;
+
+/// LDoc comments, which start with `---`.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["---"],
+    block: &[("--[[", "]]")],
+    skip: &[],
+};
+
+/// Lua decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "elseif_statement",
+        "while_statement",
+        "repeat_statement",
+        "for_statement",
+    ],
+    arms: &[],
+    binary: &["binary_expression"],
+    operators: &["and", "or"],
+    nested: &["function_declaration"],
+    ifs: &["if_statement"],
+    elses: &["elseif_statement", "else_statement"],
+    structures: &["while_statement", "repeat_statement", "for_statement"],
+    lambdas: &["function_definition"],
+    jumps: &[("goto_statement", "identifier")],
+    calls: &["function_call"],
+};
+
+impl Extractor for LuaExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_lua::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = Vec::new();
+
+        traverse(tree.root_node(), content, &lines, &mut functions);
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+/// Lua functions are scoped by the table they are stored in (`M.util.f`,
+/// `Account:deposit`), not by where they are declared.
+fn traverse(node: Node, content: &str, lines: &[&str], functions: &mut Vec<FunctionInfo>) {
+    if node.kind() == "function_declaration" {
+        functions.extend(parse_function(node, content, lines));
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        traverse(child, content, lines, functions);
+    }
+}
+
+fn parse_function(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
+    let full_name = node
+        .child_by_field_name("name")?
+        .utf8_text(content.as_bytes())
+        .ok()?;
+    // `Account:deposit` takes an implicit `self`, `M.util.f` does not
+    let (scope, separator, name) = match full_name.rsplit_once(':') {
+        Some((table, method)) => (table, ":", method),
+        None => match full_name.rsplit_once('.') {
+            Some((table, field)) => (table, ".", field),
+            None => ("", ".", full_name),
+        },
+    };
+    let name = name.to_string();
+    let parameters = node
+        .child_by_field_name("parameters")
+        .map(|list| parameter_list(list, content))
+        .unwrap_or_default();
+
+    let mut cursor = node.walk();
+    let is_local = node.children(&mut cursor).any(|c| c.kind() == "local");
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
+
+    let info = FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: false,
+        is_method: separator == ":",
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators: is_local.then(|| "local".to_string()).into_iter().collect(),
+        complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
+    };
+    Some(in_scope(info, scope, separator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_lua_functions() {
+        let content = "
+local M = {}
+
+--- Clamps a value.
+local function clamp(x, lo, hi)
+    if x < lo then
+        return lo
+    elseif x > hi or x ~= x then
+        return hi
+    end
+    return x
+end
+
+function M.util.run(...)
+    for i = 1, 3 do print(i) end
+end
+
+function Account:deposit(v)
+    self.balance = self.balance + v
+end
+
+return M
+";
+        let extractor = LuaExtractor;
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(names, vec!["clamp", "M.util.run", "Account:deposit"]);
+
+        let clamp = &fns[0];
+        assert_eq!(clamp.parameters, vec!["x", "lo", "hi"]);
+        assert_eq!(clamp.decorators, vec!["local"]);
+        assert_eq!(clamp.docstring.as_deref(), Some("Clamps a value."));
+        // if, elseif, or
+        assert_eq!(clamp.complexity, 4);
+
+        let run = &fns[1];
+        assert_eq!(run.parent.as_deref(), Some("M.util"));
+        assert!(!run.is_method);
+        assert_eq!(run.complexity, 2);
+
+        let deposit = &fns[2];
+        assert!(deposit.is_method);
+        assert_eq!(deposit.parameters, vec!["v"]);
+        // `+` is not a decision
+        assert_eq!(deposit.complexity, 1);
+    }
+
+    #[test]
+    fn test_lua_boolean_operators() {
+        let content = "
+local function ready(a, b, c)
+    while a and b or c do
+        a = step(a)
+    end
+    return a and b and c
+end
+";
+        let fns = LuaExtractor.extract(content);
+        let ready = fns.iter().find(|f| f.name == "ready").unwrap();
+        // while, and four logical operators
+        assert_eq!(ready.complexity, 6);
+        // while, the and/or run in its condition (2), the chain of ands (1)
+        assert_eq!(ready.cognitive_complexity, 4);
+    }
+}
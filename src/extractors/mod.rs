@@ -1,25 +1,58 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/mod.rs — Trait definition and shared utilities for extractors
 
+pub mod c;
 pub mod cpp;
+pub mod csharp;
+pub mod dart;
+pub mod elixir;
 pub mod go;
+pub mod haskell;
 pub mod java;
 pub mod javascript;
+pub mod kotlin;
+pub mod lua;
 pub mod nim;
 pub mod php;
 pub mod python;
 pub mod ruby;
 pub mod rust;
+pub mod scala;
+pub mod shell;
+pub mod sql;
 pub mod swift;
 pub mod tree_sitter;
+pub mod zig;
 
-use crate::models::FunctionInfo;
+use crate::models::{FunctionInfo, Halstead};
+use ::tree_sitter::{Language, Node, Parser, Tree};
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 pub trait Extractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo>;
 }
 
+thread_local! {
+    /// One parser per language and thread, reused across files.
+    static PARSERS: RefCell<HashMap<Language, Parser>> = RefCell::new(HashMap::new());
+}
+
+/// Parse `content` with this thread's cached parser for `language`.
+/// Returns `None` if the grammar cannot be loaded or parsing fails.
+pub fn parse(language: &Language, content: &str) -> Option<Tree> {
+    PARSERS.with_borrow_mut(|parsers| {
+        if !parsers.contains_key(language) {
+            let mut parser = Parser::new();
+            parser.set_language(language).ok()?;
+            parsers.insert(language.clone(), parser);
+        }
+        parsers.get_mut(language)?.parse(content, None)
+    })
+}
+
 pub fn get_extractor(path: &Path) -> Option<Box<dyn Extractor>> {
     let ext = path
         .extension()
@@ -30,28 +63,527 @@ pub fn get_extractor(path: &Path) -> Option<Box<dyn Extractor>> {
     match ext.as_str() {
         ".rs" => Some(Box::new(rust::RustExtractor)),
         ".py" | ".pyw" | ".pyi" => Some(Box::new(python::PythonExtractor)),
-        ".js" | ".mjs" | ".cjs" | ".jsx" => {
-            Some(Box::new(javascript::JavascriptExtractor::new(tree_sitter_javascript::LANGUAGE.into())))
-        }
-        ".ts" | ".mts" => {
-            Some(Box::new(javascript::JavascriptExtractor::new(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())))
-        }
-        ".tsx" => {
-            Some(Box::new(javascript::JavascriptExtractor::new(tree_sitter_typescript::LANGUAGE_TSX.into())))
-        }
+        ".js" | ".mjs" | ".cjs" | ".jsx" => Some(Box::new(javascript::JavascriptExtractor::new(
+            tree_sitter_javascript::LANGUAGE.into(),
+        ))),
+        ".ts" | ".mts" => Some(Box::new(javascript::JavascriptExtractor::new(
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        ))),
+        ".tsx" => Some(Box::new(javascript::JavascriptExtractor::new(
+            tree_sitter_typescript::LANGUAGE_TSX.into(),
+        ))),
         ".go" => Some(Box::new(go::GoExtractor)),
-        ".c" | ".h" | ".cpp" | ".cc" | ".cxx" | ".hpp" | ".hxx" => {
+        ".c" => Some(Box::new(c::CExtractor)),
+        // C++ is the safer guess for a header without its project; scans
+        // decide with `HeaderLanguage` instead
+        ".h" | ".cpp" | ".cc" | ".cxx" | ".hpp" | ".hxx" | ".h++" => {
             Some(Box::new(cpp::CppExtractor))
         }
-        ".java" | ".kt" | ".kts" | ".cs" | ".scala" => Some(Box::new(java::JavaExtractor)),
+        ".java" => Some(Box::new(java::JavaExtractor)),
+        ".kt" | ".kts" => Some(Box::new(kotlin::KotlinExtractor)),
+        ".cs" => Some(Box::new(csharp::CSharpExtractor)),
+        ".scala" | ".sc" => Some(Box::new(scala::ScalaExtractor)),
         ".php" | ".php3" | ".php4" | ".php5" | ".phtml" => Some(Box::new(php::PhpExtractor)),
         ".swift" => Some(Box::new(swift::SwiftExtractor)),
+        ".dart" => Some(Box::new(dart::DartExtractor)),
         ".rb" | ".rake" | ".gemspec" => Some(Box::new(ruby::RubyExtractor)),
         ".nim" | ".nims" => Some(Box::new(nim::NimExtractor)),
+        ".sh" | ".bash" | ".zsh" => Some(Box::new(shell::ShellExtractor)),
+        ".lua" => Some(Box::new(lua::LuaExtractor)),
+        ".ex" | ".exs" => Some(Box::new(elixir::ElixirExtractor)),
+        ".hs" => Some(Box::new(haskell::HaskellExtractor)),
+        ".zig" => Some(Box::new(zig::ZigExtractor)),
+        ".sql" => Some(Box::new(sql::SqlExtractor)),
         _ => None,
     }
 }
 
+/// Which grammar parses `.h` headers, which C and C++ share.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderLanguage {
+    /// Decide from the project's sources, or per header from its content.
+    #[default]
+    Auto,
+    C,
+    #[serde(alias = "c++")]
+    Cpp,
+}
+
+/// Extensions of C++ sources, whose presence makes a project's headers C++.
+const CPP_SOURCES: &[&str] = &["cpp", "cc", "cxx", "c++", "hpp", "hxx", "hh", "h++"];
+
+impl HeaderLanguage {
+    /// Settle `Auto` from the files of the project: headers are C when it has
+    /// C sources but no C++ ones, and C++ the other way round. With both or
+    /// neither it stays `Auto`, and each header is judged by its content.
+    pub fn detect<'a, I>(self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Path>,
+    {
+        if self != Self::Auto {
+            return self;
+        }
+        let (mut has_c, mut has_cpp) = (false, false);
+        for path in paths {
+            match path.extension().and_then(|e| e.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("c") => has_c = true,
+                Some(ext) if CPP_SOURCES.iter().any(|c| ext.eq_ignore_ascii_case(c)) => {
+                    has_cpp = true
+                }
+                _ => {}
+            }
+            if has_c && has_cpp {
+                break;
+            }
+        }
+        match (has_c, has_cpp) {
+            (true, false) => Self::C,
+            (false, true) => Self::Cpp,
+            _ => Self::Auto,
+        }
+    }
+
+    /// The extractor for a header with this `content`.
+    pub fn extractor(self, content: &str) -> Box<dyn Extractor> {
+        let is_cpp = match self {
+            Self::C => false,
+            Self::Cpp => true,
+            Self::Auto => looks_like_cpp(content),
+        };
+        if is_cpp {
+            Box::new(cpp::CppExtractor)
+        } else {
+            Box::new(c::CExtractor)
+        }
+    }
+}
+
+/// Whether a header uses C++-only syntax: classes, namespaces, templates,
+/// access specifiers or `std::`. `extern "C"` guards are common in C headers
+/// and do not count.
+fn looks_like_cpp(content: &str) -> bool {
+    const STARTS: &[&str] = &[
+        "class ",
+        "namespace ",
+        "template<",
+        "template <",
+        "public:",
+        "private:",
+        "protected:",
+        "using ",
+    ];
+    content
+        .lines()
+        .map(str::trim_start)
+        .any(|line| STARTS.iter().any(|s| line.starts_with(s)) || line.contains("std::"))
+}
+
+/// Comment syntax that documents the declaration directly below it.
+pub struct DocStyle {
+    /// Line-comment markers, e.g. `///`; consecutive lines form one comment.
+    pub line: &'static [&'static str],
+    /// Block-comment delimiters, e.g. `("/**", "*/")`.
+    pub block: &'static [(&'static str, &'static str)],
+    /// Prefixes of lines (attributes, annotations) that may sit between the
+    /// comment and the declaration.
+    pub skip: &'static [&'static str],
+}
+
+/// The doc comment directly above the declaration starting at `line_start`
+/// (1-based), with comment markers stripped. `None` if there is none.
+pub fn doc_comment(lines: &[&str], line_start: usize, style: &DocStyle) -> Option<String> {
+    let above = |end: usize| {
+        end.checked_sub(1)
+            .and_then(|i| lines.get(i))
+            .map(|l| l.trim())
+    };
+
+    // `lines[..end]` is everything above the declaration and its attributes
+    let mut end = line_start.saturating_sub(1).min(lines.len());
+    while let Some(line) = above(end)
+        && style.skip.iter().any(|s| line.starts_with(s))
+    {
+        end -= 1;
+    }
+    let last = above(end)?;
+
+    let text = if let Some(&(open, close)) = style.block.iter().find(|(_, c)| last.ends_with(c)) {
+        block_doc(&lines[..end], open, close)?
+    } else {
+        let mut text = Vec::new();
+        while let Some(line) = above(end)
+            && let Some(marker) = style.line.iter().find(|m| line.starts_with(*m))
+        {
+            text.push(line[marker.len()..].trim());
+            end -= 1;
+        }
+        text.reverse();
+        text.join("\n")
+    };
+
+    let doc = text.trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// The body of the `open ... close` comment ending on the last of `lines`.
+/// An ordinary block comment (`/*` without the doc marker) documents nothing.
+fn block_doc(lines: &[&str], open: &str, close: &str) -> Option<String> {
+    let plain = &open[..open.len() - 1];
+    let mut start = lines.len() - 1;
+    while !lines[start].trim().starts_with(open) {
+        if lines[start].contains(plain) || start == 0 {
+            return None;
+        }
+        start -= 1;
+    }
+    let body = lines[start..].join("\n");
+    let body = body.trim().strip_prefix(open)?.strip_suffix(close)?;
+    Some(
+        body.lines()
+            .map(|l| l.trim().trim_start_matches('*').trim())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// Grammar node kinds that drive the complexity metrics of one language.
+pub struct DecisionNodes {
+    /// Branches, loops, exception handlers and ternaries: one each.
+    pub branches: &'static [&'static str],
+    /// Switch cases and match arms: one each, except the default arm
+    /// (`default`, Kotlin's `else`, or a `_` or shell `*` wildcard pattern).
+    pub arms: &'static [&'static str],
+    /// Logical binary expressions: counted when their operator is in
+    /// `operators`, or always for grammars without an operator token.
+    pub binary: &'static [&'static str],
+    pub operators: &'static [&'static str],
+    /// Nested declarations that are reported, and scored, on their own.
+    pub nested: &'static [&'static str],
+    /// Cognitive complexity: `if` constructs.
+    pub ifs: &'static [&'static str],
+    /// Cognitive complexity: `else`, `elif` and `else if` clauses.
+    pub elses: &'static [&'static str],
+    /// Cognitive complexity: other structures that nest (switch/match,
+    /// loops, catch, ternaries).
+    pub structures: &'static [&'static str],
+    /// Cognitive complexity: closures and lambdas, which only deepen nesting.
+    pub lambdas: &'static [&'static str],
+    /// Cognitive complexity: jumps (`goto`, labelled `break`) as
+    /// `(kind, label kind)`, counted when they carry a label.
+    pub jumps: &'static [(&'static str, &'static str)],
+    /// Cognitive complexity: call expressions, checked for recursion.
+    pub calls: &'static [&'static str],
+}
+
+/// Cyclomatic complexity of the function rooted at `node`: one plus a point
+/// per decision node. Falls back to [`estimate_complexity`] over the
+/// function's lines when the parse tree has errors in it.
+pub fn complexity(node: Node, content: &str, lines: &[&str], table: &DecisionNodes) -> u32 {
+    if node.has_error() {
+        let start = node.start_position().row;
+        let end = (node.end_position().row + 1).min(lines.len());
+        return estimate_complexity(&lines[start.min(end)..end]);
+    }
+
+    let mut cc = 1;
+    let mut cursor = node.walk();
+    let mut stack: Vec<Node> = node.children(&mut cursor).collect();
+    while let Some(n) = stack.pop() {
+        let kind = n.kind();
+        if table.nested.contains(&kind) {
+            continue;
+        }
+        // Keywords are anonymous nodes that may share a kind with their
+        // construct (Ruby's `if` token inside an `if` node)
+        let decision = if !n.is_named() {
+            false
+        } else if table.arms.contains(&kind) {
+            !is_default_arm(n, content)
+        } else if table.binary.contains(&kind) {
+            logical_operator(n, content, table).is_some()
+        } else {
+            table.branches.contains(&kind)
+        };
+        if decision {
+            cc += 1;
+        }
+        stack.extend(n.children(&mut cursor));
+    }
+    cc
+}
+
+fn is_default_arm(arm: Node, content: &str) -> bool {
+    let text = |n: Node| {
+        n.utf8_text(content.as_bytes())
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    };
+    let arm_text = text(arm);
+    arm_text.starts_with("default")
+        || arm_text.starts_with("else")
+        || arm
+            .named_child(0)
+            .is_some_and(|p| matches!(text(p).as_str(), "_" | "*"))
+}
+
+/// Cognitive complexity (SonarSource) of the function `name` rooted at
+/// `node`: one per break in linear flow (`if`, `else`, loops, `switch`,
+/// `catch`, labelled jumps, recursion, each run of mixed `&&`/`||`), plus the
+/// current nesting depth for structures that nest.
+pub fn cognitive_complexity(node: Node, name: &str, content: &str, table: &DecisionNodes) -> u32 {
+    let mut score = 0;
+    cognitive_walk(node, 0, name, content, table, &mut score);
+    score
+}
+
+fn cognitive_walk(
+    node: Node,
+    nesting: u32,
+    name: &str,
+    content: &str,
+    table: &DecisionNodes,
+    score: &mut u32,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let kind = child.kind();
+        if table.nested.contains(&kind) {
+            continue;
+        }
+
+        let is_if = table.ifs.contains(&kind);
+        // Grammars without an else node (Go, Java) hang the else block off `alternative`
+        if is_if
+            && let Some(alt) = child.child_by_field_name("alternative")
+            && !table.ifs.contains(&alt.kind())
+            && !table.elses.contains(&alt.kind())
+        {
+            *score += 1;
+        }
+
+        let child_nesting = if is_if && is_else_if(child, table) {
+            // Scored like `else`: the chain stays at the nesting of its first `if`
+            *score += 1;
+            nesting
+        } else if is_if || table.structures.contains(&kind) {
+            *score += 1 + nesting;
+            nesting + 1
+        } else if table.elses.contains(&kind) {
+            // Only the `else` of an `if` counts, not that of a loop or `try`
+            let parent = node.kind();
+            if (table.ifs.contains(&parent) || table.elses.contains(&parent))
+                && !wraps_if(child, table)
+            {
+                *score += 1;
+            }
+            nesting
+        } else if table.lambdas.contains(&kind) {
+            nesting + 1
+        } else {
+            if table.binary.contains(&kind)
+                && let Some(op) = logical_operator(child, content, table)
+                && child
+                    .parent()
+                    .and_then(|p| logical_operator(p, content, table))
+                    != Some(op)
+            {
+                *score += 1;
+            }
+            if table
+                .jumps
+                .iter()
+                .any(|&(jump, label)| jump == kind && has_named_child(child, label))
+                || (table.calls.contains(&kind) && callee_name(child, content) == Some(name))
+            {
+                *score += 1;
+            }
+            nesting
+        };
+
+        cognitive_walk(child, child_nesting, name, content, table, score);
+    }
+}
+
+/// Deepest nesting of control structures and closures inside `node`: 0 for
+/// straight-line code, 1 for a single `if` or loop. `else if` chains stay at
+/// the depth of their first `if`.
+pub fn max_nesting(node: Node, table: &DecisionNodes) -> u32 {
+    let mut deepest = 0;
+    let mut stack = vec![(node, 0)];
+    while let Some((n, depth)) = stack.pop() {
+        let mut cursor = n.walk();
+        for child in n.named_children(&mut cursor) {
+            let kind = child.kind();
+            if table.nested.contains(&kind) {
+                continue;
+            }
+            let opens = (table.ifs.contains(&kind) && !is_else_if(child, table))
+                || table.structures.contains(&kind)
+                || table.lambdas.contains(&kind);
+            let depth = depth + opens as u32;
+            deepest = deepest.max(depth);
+            stack.push((child, depth));
+        }
+    }
+    deepest
+}
+
+/// Whether `node` (an `if`) continues an `else if` chain.
+fn is_else_if(node: Node, table: &DecisionNodes) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    (table.elses.contains(&parent.kind()) && parent.named_child(0) == Some(node))
+        || node
+            .prev_named_sibling()
+            .is_some_and(|s| table.elses.contains(&s.kind()))
+        || (table.ifs.contains(&parent.kind())
+            && parent.child_by_field_name("alternative") == Some(node))
+}
+
+/// Whether an else node is just the `else` of an `else if`.
+fn wraps_if(node: Node, table: &DecisionNodes) -> bool {
+    node.named_child(0)
+        .or_else(|| node.next_named_sibling())
+        .is_some_and(|n| table.ifs.contains(&n.kind()))
+}
+
+/// `(kind, operator)` of a logical binary expression, so runs of the same
+/// operator can be told apart from a switch between `&&` and `||`. Named
+/// operator nodes (Scala's `operator_identifier`) are compared by their text;
+/// without an `operator` field (Lua, Swift) the first anonymous token is used.
+fn logical_operator<'a>(
+    node: Node,
+    content: &'a str,
+    table: &DecisionNodes,
+) -> Option<(&'static str, &'a str)> {
+    if !table.binary.contains(&node.kind()) {
+        return None;
+    }
+    let op = match node.child_by_field_name("operator") {
+        Some(op) if op.is_named() => op.utf8_text(content.as_bytes()).unwrap_or(""),
+        Some(op) => op.kind(),
+        None => {
+            let mut cursor = node.walk();
+            let token = node.children(&mut cursor).find(|c| !c.is_named());
+            token.map_or("", |t| t.kind())
+        }
+    };
+    (op.is_empty() || table.operators.contains(&op)).then_some((node.kind(), op))
+}
+
+fn has_named_child(node: Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).any(|c| c.kind() == kind)
+}
+
+/// The called function's bare name: `f` for `f(x)`, `self.f(x)` or `Self::f(x)`.
+fn callee_name<'a>(call: Node, content: &'a str) -> Option<&'a str> {
+    let callee = ["function", "method", "name"]
+        .iter()
+        .find_map(|field| call.child_by_field_name(field))
+        .or_else(|| call.named_child(0))?;
+    let text = callee.utf8_text(content.as_bytes()).ok()?;
+    text.rsplit(['.', ':', '>']).next().map(str::trim)
+}
+
+/// Record the enclosing scope (`""` at top level) on `info`: it becomes the
+/// `parent`, and is joined to the name with `separator` for `qualified_name`.
+pub fn in_scope(mut info: FunctionInfo, scope: &str, separator: &str) -> FunctionInfo {
+    if scope.is_empty() {
+        info.parent = None;
+        info.qualified_name = info.name.clone();
+    } else {
+        info.parent = Some(scope.to_string());
+        info.qualified_name = format!("{}{}{}", scope, separator, info.name);
+    }
+    info
+}
+
+/// `scope` extended by `name`, e.g. `a::b` + `c` → `a::b::c`.
+pub fn nest_scope(scope: &str, separator: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}{}{}", scope, separator, name)
+    }
+}
+
+/// Parameters of a parameter-list node, one per named child, so commas inside
+/// generics, default values or patterns don't split a parameter. A Go
+/// declaration sharing one type (`a, b int`) yields one entry per name.
+pub fn parameter_list(list: Node, content: &str) -> Vec<String> {
+    let text = |n: Node| {
+        let raw = n.utf8_text(content.as_bytes()).unwrap_or("");
+        raw.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+
+    let mut parameters = Vec::new();
+    let mut cursor = list.walk();
+    for param in list.named_children(&mut cursor) {
+        let kind = param.kind();
+        if param.is_extra()
+            || kind.contains("comment")
+            || kind.contains("attribute")
+            || kind.ends_with("_separator")
+        {
+            continue;
+        }
+        let mut names_cursor = param.walk();
+        let names: Vec<_> = param
+            .children_by_field_name("name", &mut names_cursor)
+            .collect();
+        match param.child_by_field_name("type") {
+            Some(ty) if names.len() > 1 => {
+                parameters.extend(names.iter().map(|n| format!("{} {}", text(*n), text(ty))))
+            }
+            _ => parameters.push(text(param)),
+        }
+    }
+    parameters
+}
+
+/// Halstead counts over the leaf tokens of `node`. Anonymous tokens are
+/// operators; named leaves and whole string literals are operands. Comments
+/// are skipped and closing brackets are left to their opening half.
+pub fn halstead(node: Node, content: &str) -> Halstead {
+    halstead_of(&[node], content)
+}
+
+/// [`halstead`] over several nodes counted as one unit, such as the
+/// equations of a Haskell function.
+pub fn halstead_of(nodes: &[Node], content: &str) -> Halstead {
+    let mut operators = HashSet::new();
+    let mut operands = HashSet::new();
+    let mut counts = Halstead::default();
+    let mut stack = nodes.to_vec();
+    while let Some(n) = stack.pop() {
+        let kind = n.kind();
+        if n.is_extra() || n.is_missing() || kind.contains("comment") {
+            continue;
+        }
+        if n.is_named() && (n.child_count() == 0 || kind.contains("string")) {
+            operands.insert(n.utf8_text(content.as_bytes()).unwrap_or(kind));
+            counts.operands += 1;
+        } else if n.child_count() == 0 {
+            if !matches!(kind, ")" | "]" | "}") {
+                operators.insert(kind);
+                counts.operators += 1;
+            }
+        } else {
+            let mut cursor = n.walk();
+            stack.extend(n.children(&mut cursor));
+        }
+    }
+    counts.distinct_operators = operators.len() as u32;
+    counts.distinct_operands = operands.len() as u32;
+    counts
+}
+
+/// Keyword-counting estimate over raw source lines, for grammars without a
+/// decision table or trees that failed to parse.
 pub fn estimate_complexity(block: &[&str]) -> u32 {
     const KEYWORDS: &[&str] = &[
         "if ", "else if", "elif ", " while ", " for ", " match ", "case ", " catch ", " except ",
@@ -83,4 +615,206 @@ mod tests {
         // 1 (base) + 1 (if) + 1 (for) + 1 (if) + 1 (&&) + 2 (else if & if ) = 7
         assert_eq!(estimate_complexity(&block), 7);
     }
+
+    #[test]
+    fn test_ast_complexity_ignores_comments_and_strings() {
+        let src = r#"fn f(x: Option<i32>) -> Result<i32, E> {
+    // if this fails, else if that fails
+    let s = "else if && ||";
+    let v = x.ok_or(E)?;
+    if v > 0 && v < 10 {
+        return Ok(1);
+    }
+    match v {
+        1 => Ok(1),
+        2 | 3 => Ok(2),
+        _ => Ok(0),
+    }
+}"#;
+        let fns = rust::RustExtractor.extract(src);
+        // 1 + if + && + two non-wildcard arms
+        assert_eq!(fns[0].complexity, 5);
+
+        let py = "def g(xs):\n    for x in xs:\n        if x and not y or z:\n            pass\n    return [x for x in xs if x] # if\n";
+        let fns = python::PythonExtractor.extract(py);
+        // 1 + for + if + and + or + comprehension for + comprehension if
+        assert_eq!(fns[0].complexity, 7);
+    }
+
+    #[test]
+    fn test_cognitive_complexity_weighs_nesting() {
+        let src = r#"fn sum_primes(max: u32) -> u32 {
+    let mut total = 0;
+    'outer: for i in 1..max {
+        for j in 2..i {
+            if i % j == 0 {
+                continue 'outer;
+            }
+        }
+        total += i;
+    }
+    total
+}
+
+fn words(n: u32) -> &'static str {
+    match n {
+        1 => "one",
+        2 => "a couple",
+        _ => "lots",
+    }
+}
+
+fn chain(a: bool, b: bool, c: bool) -> u32 {
+    if a && b || c { 1 } else if a { 2 } else { 3 }
+}
+
+fn fact(n: u64) -> u64 {
+    if n < 2 { 1 } else { n * fact(n - 1) }
+}"#;
+        let fns = rust::RustExtractor.extract(src);
+        let cognitive: Vec<u32> = fns.iter().map(|f| f.cognitive_complexity).collect();
+        // for + nested for (+2) + doubly nested if (+3) + labelled continue
+        assert_eq!(cognitive[0], 7);
+        // A whole match is one increment, unlike its cyclomatic 3
+        assert_eq!((fns[1].complexity, cognitive[1]), (3, 1));
+        // if + `&&`/`||` switch (2) + else if + else
+        assert_eq!(cognitive[2], 5);
+        // if + else + recursion
+        assert_eq!(cognitive[3], 3);
+
+        let py = "def f(xs):\n    for x in xs:\n        if x > 0 and x < 10:\n            pass\n        elif x == 0:\n            pass\n        else:\n            try:\n                g(x)\n            except ValueError:\n                return f(x - 1)\n";
+        let fns = python::PythonExtractor.extract(py);
+        // for + if (+2) + and + elif + else + except (+3) + recursion
+        assert_eq!(fns[0].cognitive_complexity, 10);
+    }
+
+    #[test]
+    fn test_halstead_counts_leaf_tokens() {
+        let src = "fn add(a: i32, b: i32) -> i32 {\n    // a + b\n    a + b\n}\n\nfn greet() -> &'static str { \"hi, there\" }";
+        let fns = rust::RustExtractor.extract(src);
+        let h = fns[0].halstead;
+        // fn ( : , -> { + (closing brackets pair with their opener); the comment is ignored
+        assert_eq!((h.distinct_operators, h.operators), (7, 8));
+        // add a b i32
+        assert_eq!((h.distinct_operands, h.operands), (4, 8));
+        assert!((h.volume() - 16.0 * 11f64.log2()).abs() < 1e-9);
+        // The one-liner with fewer tokens is the more maintainable
+        assert!(fns[1].maintainability_index() > fns[0].maintainability_index());
+
+        // greet static str "hi, there": the string literal is a single operand
+        assert_eq!(fns[1].halstead.distinct_operands, 4);
+    }
+
+    #[test]
+    fn test_parameters_and_nesting_from_tree() {
+        let src = r#"fn merge(a: HashMap<String, Vec<u8>>, b: &[(u8, u8)]) -> usize {
+    for x in b {
+        if x.0 > 0 {
+            let f = |y: u8| if y > 1 { 1 } else { 0 };
+        } else if x.1 > 0 {
+        }
+    }
+    0
+}"#;
+        let fns = rust::RustExtractor.extract(src);
+        assert_eq!(
+            fns[0].parameters,
+            vec!["a: HashMap<String, Vec<u8>>", "b: &[(u8, u8)]"]
+        );
+        // for > if > closure > if
+        assert_eq!(fns[0].max_nesting, 4);
+
+        let py = "def f(a, b=(1, 2), *, c: dict[str, int] = {}, **kw):\n    return a\n";
+        let fns = python::PythonExtractor.extract(py);
+        assert_eq!(
+            fns[0].parameters,
+            vec!["a", "b=(1, 2)", "c: dict[str, int] = {}", "**kw"]
+        );
+        assert_eq!(fns[0].max_nesting, 0);
+
+        let go = "package main\n\nfunc g(a, b int, m map[string]int) {}\n";
+        let fns = go::GoExtractor.extract(go);
+        assert_eq!(
+            fns[0].parameters,
+            vec!["a int", "b int", "m map[string]int"]
+        );
+    }
+
+    #[test]
+    fn test_qualified_names_follow_enclosing_scopes() {
+        let src = "mod scan {\n    impl<'a> Walker<'a> {\n        fn new() {}\n    }\n    fn new() {}\n}\nfn main() {}\n";
+        let fns = rust::RustExtractor.extract(src);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(names, vec!["scan::Walker::new", "scan::new", "main"]);
+        assert_eq!(fns[0].parent.as_deref(), Some("scan::Walker"));
+        assert_eq!(fns[2].parent, None);
+
+        let py = "class Outer:\n    class Inner:\n        def run(self):\n            def step():\n                pass\n";
+        let fns = python::PythonExtractor.extract(py);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Outer",
+                "Outer.Inner",
+                "Outer.Inner.run",
+                "Outer.Inner.run.step"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_doc_comment_above_declaration() {
+        let src = "/// Adds two numbers.\n/// Saturates.\n#[inline]\nfn add() {}\n\n/* plain */\nfn b() {}\n/**\n * Javadoc.\n */\n@Override\nvoid c() {}";
+        let lines: Vec<&str> = src.lines().collect();
+        const RUST_DOCS: DocStyle = DocStyle {
+            line: &["///"],
+            block: &[("/**", "*/")],
+            skip: &["#["],
+        };
+        const JAVADOC: DocStyle = DocStyle {
+            line: &[],
+            block: &[("/**", "*/")],
+            skip: &["@"],
+        };
+        assert_eq!(
+            doc_comment(&lines, 4, &RUST_DOCS).as_deref(),
+            Some("Adds two numbers.\nSaturates.")
+        );
+        assert_eq!(doc_comment(&lines, 7, &RUST_DOCS), None);
+        assert_eq!(
+            doc_comment(&lines, 12, &JAVADOC).as_deref(),
+            Some("Javadoc.")
+        );
+        assert_eq!(doc_comment(&lines, 1, &RUST_DOCS), None);
+    }
+
+    #[test]
+    fn test_parser_reused_per_language() {
+        let rust: Language = tree_sitter_rust::LANGUAGE.into();
+        let python: Language = tree_sitter_python::LANGUAGE.into();
+        assert!(parse(&rust, "fn a() {}").is_some());
+        assert!(parse(&rust, "fn b() {}").is_some());
+        assert!(parse(&python, "def c(): pass").is_some());
+        PARSERS.with_borrow(|parsers| assert_eq!(parsers.len(), 2));
+    }
+
+    #[test]
+    fn test_header_language_from_project_and_content() {
+        let c_project = [Path::new("src/list.c"), Path::new("src/list.h")];
+        let cpp_project = [Path::new("src/list.cpp"), Path::new("src/list.h")];
+        let mixed = [Path::new("a.c"), Path::new("b.CC")];
+        assert_eq!(HeaderLanguage::Auto.detect(c_project), HeaderLanguage::C);
+        assert_eq!(
+            HeaderLanguage::Auto.detect(cpp_project),
+            HeaderLanguage::Cpp
+        );
+        assert_eq!(HeaderLanguage::Auto.detect(mixed), HeaderLanguage::Auto);
+        // An explicit choice wins over the project
+        assert_eq!(HeaderLanguage::C.detect(cpp_project), HeaderLanguage::C);
+
+        let c_header = "#ifdef __cplusplus\nextern \"C\" {\n#endif\nint add(int a, int b);\n";
+        assert!(!looks_like_cpp(c_header));
+        assert!(looks_like_cpp("namespace geo {\nclass Box;\n}\n"));
+    }
 }
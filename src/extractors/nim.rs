@@ -1,28 +1,48 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/nim.rs — Nim function/class extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use super::{
+    DecisionNodes, Extractor, cognitive_complexity, complexity, halstead, in_scope, max_nesting,
+    nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
 
 pub struct NimExtractor;
 
+/// Nim decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &["if", "when", "elif_branch", "while", "for", "except_branch"],
+    arms: &["of_branch"],
+    binary: &["infix_expression"],
+    operators: &["and", "or"],
+    nested: &[
+        "proc_declaration",
+        "func_declaration",
+        "method_declaration",
+        "iterator_declaration",
+        "converter_declaration",
+        "macro_declaration",
+        "template_declaration",
+    ],
+    ifs: &["if", "when"],
+    elses: &["elif_branch", "else_branch"],
+    structures: &["case", "while", "for", "except_branch"],
+    lambdas: &["proc_expression", "func_expression"],
+    jumps: &[("break_statement", "identifier")],
+    calls: &["call"],
+};
+
 impl Extractor for NimExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&tree_sitter_nim::language()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_nim::language(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions);
+        traverse(tree.root_node(), content, &lines, &mut functions, "");
 
         functions.sort_by_key(|f| f.line_start);
         functions
@@ -34,12 +54,22 @@ fn traverse(
     content: &str,
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
+    scope: &str,
 ) {
     let kind = node.kind();
+    // Nim methods live outside their types, so only routines nest scopes
+    let mut inner = None;
 
-    if kind == "proc_declaration" || kind == "func_declaration" || kind == "method_declaration" || kind == "iterator_declaration" || kind == "macro_declaration" || kind == "template_declaration" {
+    if kind == "proc_declaration"
+        || kind == "func_declaration"
+        || kind == "method_declaration"
+        || kind == "iterator_declaration"
+        || kind == "macro_declaration"
+        || kind == "template_declaration"
+    {
         if let Some(info) = parse_function(node, content, lines, kind == "method_declaration") {
-            functions.push(info);
+            inner = Some(nest_scope(scope, ".", &info.name));
+            functions.push(in_scope(info, scope, "."));
         }
     } else if kind == "type_declaration" {
         // Find object or ref object
@@ -49,8 +79,13 @@ fn traverse(
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             if child.kind() == "type_symbol_declaration" && name.is_empty() {
-                name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
-            } else if child.kind() == "object_declaration" || child.kind() == "ref_object_declaration" {
+                name = child
+                    .utf8_text(content.as_bytes())
+                    .unwrap_or("")
+                    .to_string();
+            } else if child.kind() == "object_declaration"
+                || child.kind() == "ref_object_declaration"
+            {
                 is_class = true;
             }
         }
@@ -58,9 +93,11 @@ fn traverse(
         if is_class && !name.is_empty() {
             let start_line = node.start_position().row + 1;
             let end_line = node.end_position().row + 1;
-            
-            functions.push(FunctionInfo {
+
+            let info = FunctionInfo {
                 name,
+                parent: None,
+                qualified_name: String::new(),
                 line_start: start_line,
                 line_end: end_line,
                 parameters: vec![],
@@ -70,13 +107,18 @@ fn traverse(
                 docstring: None,
                 decorators: vec![],
                 complexity: 1,
-            });
+                cognitive_complexity: 0,
+                halstead: Halstead::default(),
+                max_nesting: 0,
+            };
+            functions.push(in_scope(info, scope, "."));
         }
     }
 
+    let scope = inner.as_deref().unwrap_or(scope);
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        traverse(child, content, lines, functions);
+        traverse(child, content, lines, functions, scope);
     }
 }
 
@@ -87,25 +129,31 @@ fn parse_function(
     is_method: bool,
 ) -> Option<FunctionInfo> {
     let mut name = String::new();
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
     let mut is_exported = false;
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if kind == "identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "exported_symbol" && name.is_empty() {
             is_exported = true;
             let mut inner_cursor = child.walk();
             for inner_child in child.children(&mut inner_cursor) {
                 if inner_child.kind() == "identifier" {
-                    name = inner_child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+                    name = inner_child
+                        .utf8_text(content.as_bytes())
+                        .unwrap_or("")
+                        .to_string();
                     break;
                 }
             }
         } else if kind == "parameter_declaration_list" {
-            params_str = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            parameters = parameter_list(child, content);
         }
     }
 
@@ -116,19 +164,8 @@ fn parse_function(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
-
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(';') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     let decorators = if is_exported {
         vec!["public(*)".into()]
@@ -138,18 +175,38 @@ fn parse_function(
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async: false,
         is_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, end_line),
         decorators,
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
+/// Nim documents a routine with `##` lines at the top of its body, right
+/// after the signature (which ends with `=` and may span several lines).
+fn doc_comment(lines: &[&str], start_line: usize, end_line: usize) -> Option<String> {
+    let body = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
+    let signature_end = body.iter().position(|l| l.trim_end().ends_with('='))?;
+    let doc: Vec<&str> = body[signature_end + 1..]
+        .iter()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with("##") && !l.starts_with("##["))
+        .map(|l| l[2..].trim())
+        .collect();
+    let doc = doc.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +218,7 @@ proc hello*(name: string) =
   echo \"Hello \", name
 
 func add(a, b: int): int =
+  proc twice(x: int): int = x * 2
   a + b
 
 method draw(s: Shape) =
@@ -169,16 +227,52 @@ method draw(s: Shape) =
         let extractor = NimExtractor;
         let mut fns = extractor.extract(content);
         fns.sort_by(|a, b| a.name.cmp(&b.name));
-        
-        assert_eq!(fns.len(), 3);
-        
+
+        assert_eq!(fns.len(), 4);
+
         let a = fns.iter().find(|f| f.name == "add").unwrap();
         assert!(!a.is_method);
-        
+        assert_eq!(a.qualified_name, "add");
+
+        let t = fns.iter().find(|f| f.name == "twice").unwrap();
+        assert_eq!(t.qualified_name, "add.twice");
+
         let d = fns.iter().find(|f| f.name == "draw").unwrap();
         assert!(d.is_method);
-        
+
         let h = fns.iter().find(|f| f.name == "hello").unwrap();
         assert!(h.decorators.contains(&"public(*)".to_string()));
     }
+
+    #[test]
+    fn test_nim_decision_nodes() {
+        let content = "
+proc classify(x: int, strict: bool): string =
+  if x > 0 and strict:
+    result = \"pos\"
+  elif x < 0 or not strict:
+    result = \"neg\"
+  else:
+    result = \"zero\"
+  case x
+  of 1: discard
+  of 2, 3: discard
+  else: discard
+  while x > 10:
+    if strict:
+      break
+  try:
+    discard
+  except ValueError:
+    discard
+";
+        let fns = NimExtractor.extract(content);
+        let classify = fns.iter().find(|f| f.name == "classify").unwrap();
+        // if, elif, and, or, two `of` branches, while, the inner if, except
+        assert_eq!(classify.complexity, 10);
+        // if, and, elif, or, else, case, while, the if nested in it (2), except
+        assert_eq!(classify.cognitive_complexity, 10);
+        // The if inside the while
+        assert_eq!(classify.max_nesting, 2);
+    }
 }
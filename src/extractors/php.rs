@@ -1,28 +1,84 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/php.rs — PHP function/class extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
 
 pub struct PhpExtractor;
 
+/// PHPDoc, with `#[...]` attributes in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &[],
+    block: &[("/**", "*/")],
+    skip: &["#["],
+};
+
+/// PHP decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "else_if_clause",
+        "for_statement",
+        "foreach_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "conditional_expression",
+        "match_conditional_expression",
+    ],
+    arms: &["case_statement"],
+    binary: &["binary_expression"],
+    operators: &["&&", "||", "and", "or", "??"],
+    nested: &[
+        "function_definition",
+        "method_declaration",
+        "class_declaration",
+    ],
+    ifs: &["if_statement"],
+    elses: &["else_if_clause", "else_clause"],
+    structures: &[
+        "switch_statement",
+        "match_expression",
+        "for_statement",
+        "foreach_statement",
+        "while_statement",
+        "do_statement",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    lambdas: &["anonymous_function", "arrow_function"],
+    jumps: &[("goto_statement", "name")],
+    calls: &[
+        "function_call_expression",
+        "member_call_expression",
+        "scoped_call_expression",
+    ],
+};
+
 impl Extractor for PhpExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&tree_sitter_php::LANGUAGE_PHP.into()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_php::LANGUAGE_PHP.into(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions, false);
+        // `namespace App\Models;` applies to the rest of the file
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let namespace = root
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "namespace_definition" && n.child_by_field_name("body").is_none())
+            .and_then(|n| n.child_by_field_name("name"))
+            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+            .unwrap_or("");
+
+        traverse(root, content, &lines, &mut functions, false, namespace);
 
         functions.sort_by_key(|f| f.line_start);
         functions
@@ -35,24 +91,48 @@ fn traverse(
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
     _in_class: bool,
+    scope: &str,
 ) {
     let kind = node.kind();
+    let is_type = kind == "class_declaration"
+        || kind == "interface_declaration"
+        || kind == "trait_declaration";
 
+    // Methods hang off their class with `::`, everything else off its namespace with `\`
     if kind == "function_definition" || kind == "method_declaration" {
         if let Some(info) = parse_function(node, content, lines, kind == "method_declaration") {
-            functions.push(info);
+            let separator = if kind == "method_declaration" {
+                "::"
+            } else {
+                "\\"
+            };
+            functions.push(in_scope(info, scope, separator));
         }
-    } else if (kind == "class_declaration" || kind == "interface_declaration" || kind == "trait_declaration")
-        && let Some(info) = parse_class(node, content, lines)
-    {
-        functions.push(info);
+    } else if is_type && let Some(info) = parse_class(node, content, lines) {
+        functions.push(in_scope(info, scope, "\\"));
     }
 
     let is_class_body = kind == "declaration_list";
+    // `namespace X { ... }` blocks and types scope their contents
+    let scoped =
+        is_type || (kind == "namespace_definition" && node.child_by_field_name("body").is_some());
+    let inner = scoped
+        .then(|| node.child_by_field_name("name"))
+        .flatten()
+        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+        .map(|name| nest_scope(scope, "\\", name));
+    let scope = inner.as_deref().unwrap_or(scope);
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        traverse(child, content, lines, functions, _in_class || is_class_body);
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            _in_class || is_class_body,
+            scope,
+        );
     }
 }
 
@@ -63,28 +143,37 @@ fn parse_function(
     is_method: bool,
 ) -> Option<FunctionInfo> {
     let mut name = String::new();
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if kind == "name" {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "formal_parameters" {
-            params_str = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            parameters = parameter_list(child, content);
         }
     }
 
     if name.is_empty() {
         // Fallback for getting name in PHP tree-sitter
         if let Some(name_node) = node.child_by_field_name("name") {
-            name = name_node.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = name_node
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else {
             // Some nodes might have identifier children directly
             let mut c2 = node.walk();
             for child in node.children(&mut c2) {
                 if child.kind() == "name" || child.kind() == "identifier" {
-                    name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+                    name = child
+                        .utf8_text(content.as_bytes())
+                        .unwrap_or("")
+                        .to_string();
                     break;
                 }
             }
@@ -98,44 +187,44 @@ fn parse_function(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
-
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async: false,
         is_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
-fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInfo> {
+fn parse_class(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
     let mut name = String::new();
 
     if let Some(name_node) = node.child_by_field_name("name") {
-        name = name_node.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+        name = name_node
+            .utf8_text(content.as_bytes())
+            .unwrap_or("")
+            .to_string();
     } else {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             if child.kind() == "name" && name.is_empty() {
-                name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+                name = child
+                    .utf8_text(content.as_bytes())
+                    .unwrap_or("")
+                    .to_string();
                 break;
             }
         }
@@ -150,15 +239,20 @@ fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInf
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters: vec![],
         is_async: false,
         is_method: false,
         is_class: true,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
     })
 }
 
@@ -170,7 +264,12 @@ mod tests {
     fn test_extract_php() {
         let content = "
 <?php
+namespace App\\Models;
+
 class User {
+    /**
+     * @param int $id
+     */
     public function getName($id) {
         return 'kelexine';
     }
@@ -180,10 +279,16 @@ function helper() {}
         let extractor = PhpExtractor;
         let mut fns = extractor.extract(content);
         fns.sort_by(|a, b| a.name.cmp(&b.name));
-        
+
         assert_eq!(fns.len(), 3);
         assert!(fns.iter().any(|f| f.name == "User" && f.is_class));
         assert!(fns.iter().any(|f| f.name == "getName" && f.is_method));
+        let get_name = fns.iter().find(|f| f.name == "getName").unwrap();
+        assert_eq!(get_name.docstring.as_deref(), Some("@param int $id"));
+        assert_eq!(get_name.qualified_name, "App\\Models\\User::getName");
+        assert_eq!(get_name.parent.as_deref(), Some("App\\Models\\User"));
         assert!(fns.iter().any(|f| f.name == "helper" && !f.is_method));
+        let helper = fns.iter().find(|f| f.name == "helper").unwrap();
+        assert_eq!(helper.qualified_name, "App\\Models\\helper");
     }
-}
\ No newline at end of file
+}
@@ -1,28 +1,63 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/python.rs — Python function/class extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use super::{
+    DecisionNodes, Extractor, cognitive_complexity, complexity, halstead, in_scope, max_nesting,
+    nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
 
 pub struct PythonExtractor;
 
+/// Python decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "elif_clause",
+        "for_statement",
+        "while_statement",
+        "except_clause",
+        "conditional_expression",
+        "for_in_clause",
+        "if_clause",
+    ],
+    arms: &["case_clause"],
+    binary: &["boolean_operator"],
+    operators: &["and", "or"],
+    nested: &["function_definition", "class_definition"],
+    ifs: &["if_statement"],
+    elses: &["elif_clause", "else_clause"],
+    structures: &[
+        "for_statement",
+        "while_statement",
+        "except_clause",
+        "conditional_expression",
+        "match_statement",
+    ],
+    lambdas: &["lambda"],
+    jumps: &[],
+    calls: &["call"],
+};
+
 impl Extractor for PythonExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&tree_sitter_python::LANGUAGE.into()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_python::LANGUAGE.into(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions, false, Vec::new());
+        traverse(
+            tree.root_node(),
+            content,
+            &lines,
+            &mut functions,
+            false,
+            Vec::new(),
+            "",
+        );
 
         functions.sort_by_key(|f| f.line_start);
         functions
@@ -36,9 +71,10 @@ fn traverse(
     functions: &mut Vec<FunctionInfo>,
     in_class: bool,
     mut pending_decorators: Vec<String>,
+    scope: &str,
 ) {
     let kind = node.kind();
-    
+
     if kind == "decorator" {
         // Just extract the decorator text, e.g., "@staticmethod"
         let dec_text = node.utf8_text(content.as_bytes()).unwrap_or("");
@@ -48,26 +84,53 @@ fn traverse(
         // Collect decorators and pass them to children
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            traverse(child, content, lines, functions, in_class, pending_decorators.clone());
+            traverse(
+                child,
+                content,
+                lines,
+                functions,
+                in_class,
+                pending_decorators.clone(),
+                scope,
+            );
         }
         return;
     }
 
     if kind == "function_definition" {
-        functions.push(parse_function(node, content, lines, in_class, pending_decorators.clone()));
+        let info = parse_function(node, content, lines, in_class, pending_decorators.clone());
+        functions.push(in_scope(info, scope, "."));
         pending_decorators.clear();
     } else if kind == "class_definition" {
-        functions.push(parse_class(node, content, lines, pending_decorators.clone()));
+        let info = parse_class(node, content, lines, pending_decorators.clone());
+        functions.push(in_scope(info, scope, "."));
         pending_decorators.clear();
     }
 
     let is_class_body = kind == "class_definition";
-    
+    // Classes and functions scope what is defined inside them, like `__qualname__`
+    let inner = if kind == "class_definition" || kind == "function_definition" {
+        node.child_by_field_name("name")
+            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+            .map(|name| nest_scope(scope, ".", name))
+    } else {
+        None
+    };
+    let scope = inner.as_deref().unwrap_or(scope);
+
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         // Only pass decorators down if we're in a decorated_definition (handled above)
         // If we hit a normal statement, clear pending decorators (though they shouldn't leak)
-        traverse(child, content, lines, functions, in_class || is_class_body, Vec::new());
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            in_class || is_class_body,
+            Vec::new(),
+            scope,
+        );
     }
 }
 
@@ -80,32 +143,23 @@ fn parse_function(
 ) -> FunctionInfo {
     let mut name = String::new();
     let mut is_async = false;
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
     let mut docstring = None;
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if kind == "identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "async" {
             is_async = true;
         } else if kind == "parameters" {
-            params_str = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            parameters = parameter_list(child, content);
         } else if kind == "block" {
-            // Find docstring
-            if child.child_count() > 0 {
-                let first_stmt = child.child(0).unwrap();
-                if first_stmt.kind() == "expression_statement" {
-                    if first_stmt.child_count() > 0 {
-                        let expr = first_stmt.child(0).unwrap();
-                        if expr.kind() == "string" {
-                            let doc = expr.utf8_text(content.as_bytes()).unwrap_or("");
-                            docstring = Some(clean_docstring(doc));
-                        }
-                    }
-                }
-            }
+            docstring = block_docstring(child, content);
         }
     }
 
@@ -116,28 +170,20 @@ fn parse_function(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
-
-    // Parse parameters
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            // simple split might fail on default args with commas (like tuples), 
-            // but for simple cases it's identical to the regex logic.
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     // Heuristic: If first param is self or cls, it's definitely a method
-    let actual_is_method = is_method || parameters.first().map(|p| p.starts_with("self") || p.starts_with("cls")).unwrap_or(false);
+    let actual_is_method = is_method
+        || parameters
+            .first()
+            .map(|p| p.starts_with("self") || p.starts_with("cls"))
+            .unwrap_or(false);
 
     FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
@@ -147,6 +193,9 @@ fn parse_function(
         docstring,
         decorators,
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     }
 }
 
@@ -157,15 +206,21 @@ fn parse_class(
     decorators: Vec<String>,
 ) -> FunctionInfo {
     let mut name = String::new();
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
+    let mut docstring = None;
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if kind == "identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "argument_list" {
-            params_str = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            parameters = parameter_list(child, content);
+        } else if kind == "block" {
+            docstring = block_docstring(child, content);
         }
     }
 
@@ -176,38 +231,48 @@ fn parse_class(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
-
     FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters, // Used for bases in class
         is_async: false,
         is_method: false,
         is_class: true,
-        docstring: None,
+        docstring,
         decorators,
         complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    }
+}
+
+/// The string literal opening a function or class body, if any.
+fn block_docstring(block: Node, content: &str) -> Option<String> {
+    let first_stmt = block.child(0)?;
+    if first_stmt.kind() != "expression_statement" {
+        return None;
+    }
+    let expr = first_stmt.child(0)?;
+    if expr.kind() != "string" {
+        return None;
     }
+    let doc = expr.utf8_text(content.as_bytes()).unwrap_or("");
+    Some(clean_docstring(doc))
 }
 
 fn clean_docstring(doc: &str) -> String {
     let s = doc.trim();
-    if (s.starts_with("\"\"\"") && s.ends_with("\"\"\"") && s.len() >= 6) ||
-       (s.starts_with("'''") && s.ends_with("'''") && s.len() >= 6) {
+    if (s.starts_with("\"\"\"") && s.ends_with("\"\"\"") && s.len() >= 6)
+        || (s.starts_with("'''") && s.ends_with("'''") && s.len() >= 6)
+    {
         s[3..s.len() - 3].trim().to_string()
-    } else if (s.starts_with('"') && s.ends_with('"') && s.len() >= 2) ||
-              (s.starts_with('\'') && s.ends_with('\'') && s.len() >= 2) {
+    } else if (s.starts_with('"') && s.ends_with('"') && s.len() >= 2)
+        || (s.starts_with('\'') && s.ends_with('\'') && s.len() >= 2)
+    {
         s[1..s.len() - 1].trim().to_string()
     } else {
         s.to_string()
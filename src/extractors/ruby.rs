@@ -1,28 +1,72 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/ruby.rs — Ruby function/class extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
 
 pub struct RubyExtractor;
 
+/// The `#` block directly above a method or class.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["#"],
+    block: &[],
+    skip: &[],
+};
+
+/// Ruby decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if",
+        "elsif",
+        "unless",
+        "while",
+        "until",
+        "for",
+        "rescue",
+        "conditional",
+        "if_modifier",
+        "unless_modifier",
+        "while_modifier",
+        "until_modifier",
+        "rescue_modifier",
+    ],
+    arms: &["when"],
+    binary: &["binary"],
+    operators: &["&&", "||", "and", "or"],
+    nested: &["method", "singleton_method", "class", "module"],
+    ifs: &["if", "unless", "if_modifier", "unless_modifier"],
+    elses: &["elsif", "else"],
+    structures: &[
+        "case",
+        "case_match",
+        "while",
+        "until",
+        "for",
+        "rescue",
+        "conditional",
+        "while_modifier",
+        "until_modifier",
+        "rescue_modifier",
+    ],
+    lambdas: &["block", "do_block", "lambda"],
+    jumps: &[],
+    calls: &["call"],
+};
+
 impl Extractor for RubyExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&tree_sitter_ruby::LANGUAGE.into()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_ruby::LANGUAGE.into(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions, false);
+        traverse(tree.root_node(), content, &lines, &mut functions, false, "");
 
         functions.retain(|f| f.name != "?");
         functions.sort_by_key(|f| f.line_start);
@@ -36,24 +80,42 @@ fn traverse(
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
     in_class: bool,
+    scope: &str,
 ) {
     let kind = node.kind();
 
+    // Ruby's own notation: `Mod::Klass#instance_method`, `Mod.singleton_method`
     if kind == "method" || kind == "singleton_method" {
-        if let Some(info) = parse_method(node, content, lines, in_class || kind == "singleton_method") {
-            functions.push(info);
+        if let Some(info) =
+            parse_method(node, content, lines, in_class || kind == "singleton_method")
+        {
+            let separator = if kind == "method" { "#" } else { "." };
+            functions.push(in_scope(info, scope, separator));
         }
     } else if (kind == "class" || kind == "module")
         && let Some(info) = parse_class(node, content, lines)
     {
-        functions.push(info);
+        functions.push(in_scope(info, scope, "::"));
     }
 
     let is_class_body = kind == "class" || kind == "module";
+    let inner = is_class_body
+        .then(|| node.child_by_field_name("name"))
+        .flatten()
+        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+        .map(|name| nest_scope(scope, "::", name));
+    let scope = inner.as_deref().unwrap_or(scope);
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        traverse(child, content, lines, functions, in_class || is_class_body);
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            in_class || is_class_body,
+            scope,
+        );
     }
 }
 
@@ -64,14 +126,17 @@ fn parse_method(
     is_method: bool,
 ) -> Option<FunctionInfo> {
     let mut name = String::new();
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
 
     if let Some(name_node) = node.child_by_field_name("name") {
-        name = name_node.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+        name = name_node
+            .utf8_text(content.as_bytes())
+            .unwrap_or("")
+            .to_string();
     }
-    
+
     if let Some(params_node) = node.child_by_field_name("parameters") {
-        params_str = params_node.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+        parameters = parameter_list(params_node, content);
     }
 
     if name.is_empty() || name == "?" || name == "?obj" {
@@ -81,39 +146,36 @@ fn parse_method(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
-
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async: false,
         is_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
-fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInfo> {
+fn parse_class(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
     let mut name = String::new();
 
     if let Some(name_node) = node.child_by_field_name("name") {
-        name = name_node.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+        name = name_node
+            .utf8_text(content.as_bytes())
+            .unwrap_or("")
+            .to_string();
     }
 
     if name.is_empty() {
@@ -125,15 +187,20 @@ fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInf
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters: vec![],
         is_async: false,
         is_method: false,
         is_class: true,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
     })
 }
 
@@ -150,7 +217,10 @@ module Utils
   end
 end
 
+# A registered user.
 class User
+  # Creates a user.
+  # +name+ is required.
   def initialize(name)
     @name = name
   end
@@ -163,20 +233,29 @@ end
         let extractor = RubyExtractor;
         let mut fns = extractor.extract(content);
         fns.sort_by(|a, b| a.name.cmp(&b.name));
-        
+
         assert_eq!(fns.len(), 5);
-        
+
         let u = fns.iter().find(|f| f.name == "Utils").unwrap();
         assert!(u.is_class);
-        
+
         let log = fns.iter().find(|f| f.name == "log").unwrap();
         assert!(log.is_method);
         assert_eq!(log.parameters, vec!["msg"]);
-        
+        assert_eq!(log.qualified_name, "Utils.log");
+
         let user = fns.iter().find(|f| f.name == "User").unwrap();
         assert!(user.is_class);
-        
+
         let init = fns.iter().find(|f| f.name == "initialize").unwrap();
         assert!(init.is_method);
+        assert_eq!(init.qualified_name, "User#initialize");
+        assert_eq!(init.parent.as_deref(), Some("User"));
+        assert_eq!(
+            init.docstring.as_deref(),
+            Some("Creates a user.\n+name+ is required.")
+        );
+        assert_eq!(user.docstring.as_deref(), Some("A registered user."));
+        assert_eq!(log.docstring, None);
     }
 }
@@ -1,28 +1,55 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/rust.rs — Rust function/struct extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
 
 pub struct RustExtractor;
 
+/// Rust doc comments, with `#[...]` attributes in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["///"],
+    block: &[("/**", "*/")],
+    skip: &["#["],
+};
+
+/// Rust decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &["if_expression", "while_expression", "for_expression"],
+    arms: &["match_arm"],
+    binary: &["binary_expression"],
+    operators: &["&&", "||"],
+    nested: &["function_item"],
+    ifs: &["if_expression"],
+    elses: &["else_clause"],
+    structures: &[
+        "match_expression",
+        "for_expression",
+        "while_expression",
+        "loop_expression",
+    ],
+    lambdas: &["closure_expression"],
+    jumps: &[
+        ("break_expression", "label"),
+        ("continue_expression", "label"),
+    ],
+    calls: &["call_expression"],
+};
+
 impl Extractor for RustExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&tree_sitter_rust::LANGUAGE.into()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_rust::LANGUAGE.into(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions, false);
+        traverse(tree.root_node(), content, &lines, &mut functions, false, "");
 
         functions.sort_by_key(|f| f.line_start);
         functions
@@ -35,44 +62,64 @@ fn traverse(
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
     in_impl: bool,
+    scope: &str,
 ) {
     let kind = node.kind();
     let is_impl = kind == "impl_item";
 
     if kind == "function_item" {
         if let Some(info) = parse_function(node, content, lines, in_impl) {
-            functions.push(info);
-        }
-    } else if kind == "struct_item" {
-        if let Some(info) = parse_struct(node, content) {
-            functions.push(info);
+            functions.push(in_scope(info, scope, "::"));
         }
+    } else if kind == "struct_item"
+        && let Some(info) = parse_struct(node, content, lines)
+    {
+        functions.push(in_scope(info, scope, "::"));
     }
 
+    // Modules, traits, impl blocks (by their type, without generics) and
+    // functions scope whatever is declared inside them
+    let inner = match kind {
+        "impl_item" => node.child_by_field_name("type"),
+        "mod_item" | "trait_item" | "function_item" => node.child_by_field_name("name"),
+        _ => None,
+    }
+    .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+    .map(|name| nest_scope(scope, "::", name.split('<').next().unwrap_or(name)));
+    let scope = inner.as_deref().unwrap_or(scope);
+
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        traverse(child, content, lines, functions, in_impl || is_impl);
+        traverse(child, content, lines, functions, in_impl || is_impl, scope);
     }
 }
 
-fn parse_function(node: Node, content: &str, lines: &[&str], is_method: bool) -> Option<FunctionInfo> {
+fn parse_function(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    is_method: bool,
+) -> Option<FunctionInfo> {
     let mut name = String::new();
     let mut is_async = false;
     let mut is_pub = false;
     let mut has_test_attr = false;
-    let mut params_str = String::new();
+    let mut parameters = Vec::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if kind == "identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "async" {
             is_async = true;
         } else if kind == "visibility_modifier" {
             is_pub = true;
         } else if kind == "parameters" {
-            params_str = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            parameters = parameter_list(child, content);
         } else if kind == "attribute_item" {
             let attr_text = child.utf8_text(content.as_bytes()).unwrap_or("");
             if attr_text.contains("test") {
@@ -92,42 +139,39 @@ fn parse_function(node: Node, content: &str, lines: &[&str], is_method: bool) ->
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     // Extract parameters
-    let mut parameters = Vec::new();
-    let trimmed_params = params_str.trim_start_matches('(').trim_end_matches(')');
-    if !trimmed_params.is_empty() {
-        for p in trimmed_params.split(',') {
-            let p_trim = p.trim();
-            if !p_trim.is_empty() {
-                parameters.push(p_trim.to_string());
-            }
-        }
-    }
-
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async,
         is_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: if is_pub { vec!["pub".into()] } else { vec![] },
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
-fn parse_struct(node: Node, content: &str) -> Option<FunctionInfo> {
+fn parse_struct(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
     let mut name = String::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         if child.kind() == "type_identifier" {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
             break;
         }
     }
@@ -141,14 +185,19 @@ fn parse_struct(node: Node, content: &str) -> Option<FunctionInfo> {
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters: vec![],
         is_async: false,
         is_method: false,
         is_class: true,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
     })
 }
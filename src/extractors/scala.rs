@@ -0,0 +1,318 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/scala.rs — Scala function/class extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
+
+pub struct ScalaExtractor;
+
+/// Scaladoc, with annotations in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &[],
+    block: &[("/**", "*/")],
+    skip: &["@"],
+};
+
+/// Scala decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_expression",
+        "while_expression",
+        "do_while_expression",
+        "for_expression",
+        "catch_clause",
+    ],
+    arms: &["case_clause"],
+    binary: &["infix_expression"],
+    operators: &["&&", "||"],
+    nested: &[
+        "function_definition",
+        "class_definition",
+        "object_definition",
+        "trait_definition",
+    ],
+    ifs: &["if_expression"],
+    elses: &[],
+    structures: &[
+        "match_expression",
+        "while_expression",
+        "do_while_expression",
+        "for_expression",
+        "catch_clause",
+    ],
+    lambdas: &["lambda_expression"],
+    jumps: &[],
+    calls: &["call_expression"],
+};
+
+/// Type definitions, and the decorator recorded for each besides `class`.
+const TYPES: &[(&str, Option<&str>)] = &[
+    ("class_definition", None),
+    ("object_definition", Some("object")),
+    ("trait_definition", Some("trait")),
+    ("enum_definition", Some("enum")),
+];
+
+impl Extractor for ScalaExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_scala::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = Vec::new();
+
+        // `package a` / `package b` clauses without a body chain up for the rest of the file
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let package: Vec<&str> = root
+            .named_children(&mut cursor)
+            .filter(|n| n.kind() == "package_clause" && n.child_by_field_name("body").is_none())
+            .filter_map(|n| n.child_by_field_name("name"))
+            .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+            .collect();
+
+        traverse(
+            root,
+            content,
+            &lines,
+            &mut functions,
+            false,
+            &package.join("."),
+        );
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+fn traverse(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    functions: &mut Vec<FunctionInfo>,
+    in_class: bool,
+    scope: &str,
+) {
+    let kind = node.kind();
+    let type_kind = TYPES.iter().find(|(k, _)| *k == kind);
+
+    if kind == "function_definition" || kind == "function_declaration" {
+        if let Some(info) = parse_function(node, content, lines, in_class) {
+            functions.push(in_scope(info, scope, "."));
+        }
+    } else if let Some(&(_, decorator)) = type_kind
+        && let Some(info) = parse_type(node, content, lines, decorator)
+    {
+        functions.push(in_scope(info, scope, "."));
+    }
+
+    // Packages with a body, types and defs scope whatever is defined inside them
+    let scoped = type_kind.is_some()
+        || kind == "function_definition"
+        || (kind == "package_clause" && node.child_by_field_name("body").is_some());
+    let inner = scoped
+        .then(|| node.child_by_field_name("name"))
+        .flatten()
+        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+        .map(|name| nest_scope(scope, ".", name));
+    let scope = inner.as_deref().unwrap_or(scope);
+
+    // Only the members of a type are methods, not defs nested in a def
+    let in_class = match kind {
+        "function_definition" => false,
+        _ => in_class || type_kind.is_some(),
+    };
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        traverse(child, content, lines, functions, in_class, scope);
+    }
+}
+
+/// Annotations on a definition, e.g. `@tailrec`.
+fn annotations(node: Node, content: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|c| c.kind() == "annotation")
+        .map(|c| {
+            c.utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
+
+fn parse_function(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    is_method: bool,
+) -> Option<FunctionInfo> {
+    let name = node
+        .child_by_field_name("name")?
+        .utf8_text(content.as_bytes())
+        .ok()?
+        .to_string();
+    // Every parameter clause of a curried `def f(a: Int)(b: Int)`
+    let mut cursor = node.walk();
+    let parameters = node
+        .named_children(&mut cursor)
+        .filter(|c| c.kind() == "parameters")
+        .flat_map(|clause| parameter_list(clause, content))
+        .collect();
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: false,
+        is_method,
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators: annotations(node, content),
+        complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
+    })
+}
+
+/// Classes, case classes, objects, traits and enums. A class keeps its
+/// constructor parameters as its parameters.
+fn parse_type(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    decorator: Option<&str>,
+) -> Option<FunctionInfo> {
+    let name = node
+        .child_by_field_name("name")
+        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+        .unwrap_or("?")
+        .to_string();
+
+    let mut cursor = node.walk();
+    let is_case = node.children(&mut cursor).any(|c| c.kind() == "case");
+    let mut cursor = node.walk();
+    let parameters = node
+        .named_children(&mut cursor)
+        .filter(|c| c.kind() == "class_parameters")
+        .flat_map(|clause| parameter_list(clause, content))
+        .collect();
+
+    let mut decorators: Vec<String> = is_case.then(|| "case".to_string()).into_iter().collect();
+    decorators.extend(decorator.map(str::to_string));
+    decorators.extend(annotations(node, content));
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: false,
+        is_method: false,
+        is_class: true,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators,
+        complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_scala_functions() {
+        let content = "
+package shop.orders
+
+case class Line(sku: String, qty: Int)
+
+trait Pricing {
+  def price(line: Line): BigDecimal
+}
+
+object Checkout extends Pricing {
+  /** Totals the lines. */
+  def total(lines: Seq[Line])(implicit tax: Double): BigDecimal = {
+    if (lines.isEmpty || tax < 0) 0 else lines.map(price).sum
+  }
+
+  def price(line: Line): BigDecimal = line.qty match {
+    case 0 => 0
+    case n => n * 2
+    case _ => 1
+  }
+}
+";
+        let extractor = ScalaExtractor;
+        let fns = extractor.extract(content);
+
+        let line = fns.iter().find(|f| f.name == "Line").unwrap();
+        assert!(line.is_class);
+        assert!(line.decorators.contains(&"case".to_string()));
+        assert_eq!(line.parameters, vec!["sku: String", "qty: Int"]);
+        assert_eq!(line.qualified_name, "shop.orders.Line");
+
+        let pricing = fns.iter().find(|f| f.name == "Pricing").unwrap();
+        assert!(pricing.decorators.contains(&"trait".to_string()));
+
+        let total = fns.iter().find(|f| f.name == "total").unwrap();
+        assert!(total.is_method);
+        assert_eq!(total.parameters, vec!["lines: Seq[Line]", "tax: Double"]);
+        assert_eq!(total.docstring.as_deref(), Some("Totals the lines."));
+        assert_eq!(total.qualified_name, "shop.orders.Checkout.total");
+        // if, ||
+        assert_eq!(total.complexity, 3);
+
+        let price = fns
+            .iter()
+            .find(|f| f.name == "price" && f.parent.as_deref() == Some("shop.orders.Checkout"))
+            .unwrap();
+        // Two cases besides the wildcard
+        assert_eq!(price.complexity, 3);
+    }
+
+    #[test]
+    fn test_scala_guards_and_boolean_operators() {
+        let content = "
+object Grades {
+  def grade(score: Int, bonus: Boolean): String = score match {
+    case s if s >= 90 && !bonus => \"A\"
+    case s if s >= 80 || bonus => \"B\"
+    case _ => \"C\"
+  }
+}
+";
+        let fns = ScalaExtractor.extract(content);
+        let grade = fns.iter().find(|f| f.name == "grade").unwrap();
+        // Two guarded cases, && and ||
+        assert_eq!(grade.complexity, 5);
+        // match, plus one per boolean operator in the guards
+        assert_eq!(grade.cognitive_complexity, 3);
+    }
+}
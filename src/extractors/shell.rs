@@ -0,0 +1,147 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/shell.rs — Shell function extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting,
+};
+use crate::models::FunctionInfo;
+use tree_sitter::Node;
+
+pub struct ShellExtractor;
+
+/// `#` comment lines directly above a function.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["#"],
+    block: &[],
+    skip: &[],
+};
+
+/// Shell decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "elif_clause",
+        "while_statement",
+        "for_statement",
+        "c_style_for_statement",
+    ],
+    arms: &["case_item"],
+    // `a && b` command lists, and `[[ a && b ]]` tests
+    binary: &["list", "binary_expression"],
+    operators: &["&&", "||", "-a", "-o"],
+    nested: &["function_definition"],
+    ifs: &["if_statement"],
+    elses: &["elif_clause", "else_clause"],
+    structures: &[
+        "case_statement",
+        "while_statement",
+        "for_statement",
+        "c_style_for_statement",
+    ],
+    lambdas: &[],
+    jumps: &[],
+    calls: &["command"],
+};
+
+impl Extractor for ShellExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_bash::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = Vec::new();
+
+        traverse(tree.root_node(), content, &lines, &mut functions);
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+/// Shell functions are global once defined, so nested ones are not scoped.
+fn traverse(node: Node, content: &str, lines: &[&str], functions: &mut Vec<FunctionInfo>) {
+    if node.kind() == "function_definition"
+        && let Some(info) = parse_function(node, content, lines)
+    {
+        functions.push(in_scope(info, "", "."));
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        traverse(child, content, lines, functions);
+    }
+}
+
+fn parse_function(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
+    let name = node
+        .child_by_field_name("name")?
+        .utf8_text(content.as_bytes())
+        .ok()?
+        .to_string();
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        // Shell functions read `$1`, `$@`, ... instead of declaring parameters
+        parameters: vec![],
+        is_async: false,
+        is_method: false,
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators: vec![],
+        complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_shell_functions() {
+        let content = r#"#!/bin/sh
+
+# Prints usage.
+usage() {
+    echo "usage: deploy <env>"
+}
+
+function deploy {
+    if [ -z "$1" ] || [ "$1" = "-h" ]; then
+        usage
+    elif [ "$1" = prod ]; then
+        echo prod
+    fi
+    case "$2" in
+        start) echo start ;;
+        stop) echo stop ;;
+        *) echo other ;;
+    esac
+}
+"#;
+        let extractor = ShellExtractor;
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["usage", "deploy"]);
+
+        assert_eq!(fns[0].docstring.as_deref(), Some("Prints usage."));
+        assert_eq!(fns[0].complexity, 1);
+
+        // if, ||, elif, two cases besides `*`
+        assert_eq!(fns[1].complexity, 6);
+    }
+}
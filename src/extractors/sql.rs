@@ -0,0 +1,339 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/sql.rs — SQL routine/view extraction from CREATE statements
+
+use super::{DocStyle, Extractor, doc_comment, in_scope};
+use crate::models::{FunctionInfo, Halstead};
+
+pub struct SqlExtractor;
+
+/// `--` comment lines directly above a statement.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["--"],
+    block: &[("/**", "*/")],
+    skip: &[],
+};
+
+/// Objects reported by `CREATE <kind>`, and the decorator each carries.
+const KINDS: &[(&str, &str)] = &[
+    ("FUNCTION", "function"),
+    ("PROCEDURE", "procedure"),
+    ("PROC", "procedure"),
+    ("TRIGGER", "trigger"),
+    ("VIEW", "view"),
+];
+
+/// Words that may sit between `CREATE` and the object kind.
+const QUALIFIERS: &[&str] = &[
+    "OR",
+    "REPLACE",
+    "ALTER",
+    "TEMP",
+    "TEMPORARY",
+    "MATERIALIZED",
+    "RECURSIVE",
+    "SECURE",
+    "CONSTRAINT",
+];
+
+/// Procedural keywords that branch, one point each.
+const DECISIONS: &[&str] = &["IF", "ELSIF", "ELSEIF", "WHILE", "FOR", "WHEN"];
+
+impl Extractor for SqlExtractor {
+    /// There is no grammar for the many SQL dialects, so statements are found
+    /// by their words: every `CREATE FUNCTION`, `PROCEDURE`, `TRIGGER` or
+    /// `VIEW`, ending at the `;` that closes its body, a `GO` batch separator
+    /// or the next `CREATE`.
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let lines: Vec<&str> = content.lines().collect();
+        let words = words(content);
+
+        let mut functions = Vec::new();
+        for (i, w) in words.iter().enumerate() {
+            if !w.text.eq_ignore_ascii_case("CREATE") {
+                continue;
+            }
+            let mut rest = words[i + 1..]
+                .iter()
+                .skip_while(|w| QUALIFIERS.iter().any(|q| w.text.eq_ignore_ascii_case(q)))
+                .skip_while(|w| w.text.to_ascii_uppercase().starts_with("DEFINER"));
+            let Some(&(_, decorator)) = rest.next().and_then(|kind| {
+                KINDS
+                    .iter()
+                    .find(|(k, _)| kind.text.eq_ignore_ascii_case(k))
+            }) else {
+                continue;
+            };
+            let mut rest = rest.skip_while(|w| {
+                ["IF", "NOT", "EXISTS"]
+                    .iter()
+                    .any(|k| w.text.eq_ignore_ascii_case(k))
+            });
+            let Some(name) = rest.next() else {
+                continue;
+            };
+            functions.push(parse_statement(
+                content,
+                &lines,
+                &words[i..],
+                name,
+                decorator,
+            ));
+        }
+        functions
+    }
+}
+
+/// A word of SQL outside comments and string literals, or one of `;(),`.
+struct Word<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+/// The words of `content`, skipping `--` and `/* */` comments and quoted
+/// strings. Dollar-quoted bodies (`$$ ... $$`) are kept: they hold the
+/// routine's code.
+fn words(content: &str) -> Vec<Word<'_>> {
+    let bytes = content.as_bytes();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &content[i..];
+        if rest.starts_with("--") {
+            i += rest.find('\n').unwrap_or(rest.len());
+        } else if rest.starts_with("/*") {
+            i += rest.find("*/").map_or(rest.len(), |end| end + 2);
+        } else if bytes[i] == b'\'' {
+            i += 1 + rest[1..].find('\'').map_or(rest.len() - 1, |end| end + 1);
+        } else if matches!(bytes[i], b';' | b'(' | b')' | b',') {
+            words.push(Word {
+                text: &rest[..1],
+                offset: i,
+            });
+            i += 1;
+        } else if bytes[i].is_ascii_whitespace() {
+            i += 1;
+        } else {
+            let len = rest
+                .find(|c: char| {
+                    c.is_ascii_whitespace() || matches!(c, ';' | '(' | ')' | ',' | '\'')
+                })
+                .unwrap_or(rest.len());
+            words.push(Word {
+                text: &rest[..len],
+                offset: i,
+            });
+            i += len;
+        }
+    }
+    words
+}
+
+/// Whether a word opens or closes a dollar-quoted body: `$$` or `$tag$`.
+fn is_dollar_quote(word: &str) -> bool {
+    word.len() >= 2
+        && word.starts_with('$')
+        && word.ends_with('$')
+        && word[1..word.len() - 1]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// 1-based line of a byte offset.
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+/// The statement starting at `words[0]` (its `CREATE`), named by `name`.
+fn parse_statement(
+    content: &str,
+    lines: &[&str],
+    words: &[Word],
+    name: &Word,
+    decorator: &str,
+) -> FunctionInfo {
+    // `BEGIN ... END` (and `CASE ... END`) blocks hold `;` that do not end the
+    // statement; `$$` bodies hold them too
+    let mut depth = 0i32;
+    let mut dollar = false;
+    let mut end = words.len() - 1;
+    for (i, w) in words.iter().enumerate().skip(1) {
+        if is_dollar_quote(w.text) {
+            dollar = !dollar;
+            continue;
+        }
+        if dollar {
+            continue;
+        }
+        let after_end = words[i - 1].text.eq_ignore_ascii_case("END");
+        match w.text.to_ascii_uppercase().as_str() {
+            "BEGIN" => depth += 1,
+            "CASE" if !after_end => depth += 1,
+            "END" => depth -= 1,
+            // `END IF`, `END LOOP`: that `END` closed a block that never opened one
+            "IF" | "LOOP" | "WHILE" | "REPEAT" | "FOR" if after_end => depth += 1,
+            ";" if depth <= 0 => {
+                end = i;
+                break;
+            }
+            "GO" | "CREATE" => {
+                end = i - 1;
+                break;
+            }
+            _ => {}
+        }
+    }
+    let body = &words[..=end];
+
+    // `schema.name`, with any identifier quoting removed
+    let unquote = |s: &str| {
+        s.trim_matches(|c| matches!(c, '"' | '`' | '[' | ']'))
+            .to_string()
+    };
+    let (scope, name_text) = match name.text.rsplit_once('.') {
+        Some((schema, object)) => (unquote(schema), unquote(object)),
+        None => (String::new(), unquote(name.text)),
+    };
+
+    // The parenthesized list right after the name of a routine
+    let mut parameters = Vec::new();
+    let name_index = body
+        .iter()
+        .position(|w| w.offset == name.offset)
+        .unwrap_or(0);
+    if decorator != "view"
+        && decorator != "trigger"
+        && body.get(name_index + 1).is_some_and(|w| w.text == "(")
+    {
+        let mut depth = 0;
+        let mut start = None;
+        for w in &body[name_index + 1..] {
+            match w.text {
+                "(" => {
+                    depth += 1;
+                    if depth == 1 {
+                        start = Some(w.offset + 1);
+                    }
+                }
+                "," | ")" if depth == 1 => {
+                    if let Some(from) = start {
+                        let param = content[from..w.offset]
+                            .split_whitespace()
+                            .collect::<Vec<_>>();
+                        if !param.is_empty() {
+                            parameters.push(param.join(" "));
+                        }
+                    }
+                    start = Some(w.offset + 1);
+                    if w.text == ")" {
+                        break;
+                    }
+                }
+                ")" => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    let complexity = match decorator {
+        "view" => 1,
+        _ => 1 + decisions(body),
+    };
+
+    let line_start = line_of(content, words[0].offset);
+    let last = &body[body.len() - 1];
+    let line_end = line_of(content, last.offset + last.text.len().saturating_sub(1));
+
+    let info = FunctionInfo {
+        name: name_text,
+        parent: None,
+        qualified_name: String::new(),
+        line_start,
+        line_end,
+        parameters,
+        is_async: false,
+        is_method: false,
+        is_class: false,
+        docstring: doc_comment(lines, line_start, &DOCS),
+        decorators: vec![decorator.to_string()],
+        complexity,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    };
+    in_scope(info, &scope, ".")
+}
+
+/// Branching keywords in a routine body, leaving out the `IF` of `END IF`
+/// and `IF [NOT] EXISTS`, and the `FOR` of `FOR EACH ROW` and `FOR UPDATE`.
+fn decisions(body: &[Word]) -> u32 {
+    let upper: Vec<String> = body.iter().map(|w| w.text.to_ascii_uppercase()).collect();
+    let mut count = 0;
+    for (i, word) in upper.iter().enumerate() {
+        if !DECISIONS.contains(&word.as_str()) {
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|p| upper[p].as_str());
+        let next = upper.get(i + 1).map(String::as_str);
+        let skip = prev == Some("END")
+            || (word == "IF" && matches!(next, Some("EXISTS" | "NOT")))
+            || (word == "FOR" && matches!(next, Some("EACH" | "UPDATE")));
+        if !skip {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_sql_routines() {
+        let content = "
+-- Orders per customer.
+CREATE OR REPLACE VIEW sales.order_counts AS
+SELECT customer_id, COUNT(*) FROM orders GROUP BY customer_id;
+
+CREATE FUNCTION sales.discount(total NUMERIC, tier TEXT DEFAULT 'std')
+RETURNS NUMERIC AS $$
+BEGIN
+    IF total > 100 AND tier = 'gold' THEN
+        RETURN total * 0.9;
+    ELSIF total > 50 THEN
+        RETURN total * 0.95;
+    END IF;
+    RETURN total;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_orders AFTER INSERT ON orders
+FOR EACH ROW EXECUTE FUNCTION audit();
+";
+        let extractor = SqlExtractor;
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["sales.order_counts", "sales.discount", "audit_orders"]
+        );
+
+        let view = &fns[0];
+        assert_eq!(view.decorators, vec!["view"]);
+        assert_eq!(view.docstring.as_deref(), Some("Orders per customer."));
+        assert_eq!((view.line_start, view.line_end), (3, 4));
+
+        let discount = &fns[1];
+        assert_eq!(
+            discount.parameters,
+            vec!["total NUMERIC", "tier TEXT DEFAULT 'std'"]
+        );
+        assert_eq!((discount.line_start, discount.line_end), (6, 16));
+        // IF, ELSIF
+        assert_eq!(discount.complexity, 3);
+
+        let trigger = &fns[2];
+        assert_eq!(trigger.decorators, vec!["trigger"]);
+        assert_eq!(trigger.complexity, 1);
+    }
+}
@@ -1,28 +1,63 @@
 // Author: kelexine (https://github.com/kelexine)
 // extractors/swift.rs — Swift function/class extraction via Tree-sitter
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Node, Parser};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
 
 pub struct SwiftExtractor;
 
+/// Swift `///` and `/** */` docs, with `@attributes` in between.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["///"],
+    block: &[("/**", "*/")],
+    skip: &["@"],
+};
+
+/// Swift decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "guard_statement",
+        "for_statement",
+        "while_statement",
+        "repeat_while_statement",
+        "catch_block",
+        "ternary_expression",
+    ],
+    arms: &["switch_entry"],
+    binary: &["conjunction_expression", "disjunction_expression"],
+    operators: &["&&", "||"],
+    nested: &["function_declaration", "class_declaration"],
+    ifs: &["if_statement"],
+    elses: &["else"],
+    structures: &[
+        "guard_statement",
+        "switch_statement",
+        "for_statement",
+        "while_statement",
+        "repeat_while_statement",
+        "catch_block",
+        "ternary_expression",
+    ],
+    lambdas: &["lambda_literal"],
+    jumps: &[],
+    calls: &["call_expression"],
+};
+
 impl Extractor for SwiftExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&tree_sitter_swift::LANGUAGE.into()).is_err() {
+        let Some(tree) = super::parse(&tree_sitter_swift::LANGUAGE.into(), content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let lines: Vec<&str> = content.lines().collect();
         let mut functions = Vec::new();
 
-        traverse(tree.root_node(), content, &lines, &mut functions, false);
+        traverse(tree.root_node(), content, &lines, &mut functions, false, "");
 
         functions.sort_by_key(|f| f.line_start);
         functions
@@ -35,24 +70,46 @@ fn traverse(
     lines: &[&str],
     functions: &mut Vec<FunctionInfo>,
     in_class: bool,
+    scope: &str,
 ) {
     let kind = node.kind();
+    let is_type = kind == "class_declaration"
+        || kind == "struct_declaration"
+        || kind == "enum_declaration"
+        || kind == "protocol_declaration"
+        || kind == "extension_declaration";
 
     if kind == "function_declaration" || kind == "init_declaration" {
         if let Some(info) = parse_function(node, content, lines, in_class) {
-            functions.push(info);
+            functions.push(in_scope(info, scope, "."));
         }
-    } else if (kind == "class_declaration" || kind == "struct_declaration" || kind == "enum_declaration" || kind == "protocol_declaration" || kind == "extension_declaration")
-        && let Some(info) = parse_class(node, content, lines)
-    {
-        functions.push(info);
+    } else if is_type && let Some(info) = parse_class(node, content, lines) {
+        functions.push(in_scope(info, scope, "."));
     }
 
-    let is_class_body = kind == "class_body" || kind == "struct_body" || kind == "enum_body" || kind == "protocol_body" || kind == "extension_body";
+    let is_class_body = kind == "class_body"
+        || kind == "struct_body"
+        || kind == "enum_body"
+        || kind == "protocol_body"
+        || kind == "extension_body";
+    // Types scope their members; an extension scopes them by the type it extends
+    let inner = is_type
+        .then(|| node.child_by_field_name("name"))
+        .flatten()
+        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+        .map(|name| nest_scope(scope, ".", name));
+    let scope = inner.as_deref().unwrap_or(scope);
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        traverse(child, content, lines, functions, in_class || is_class_body);
+        traverse(
+            child,
+            content,
+            lines,
+            functions,
+            in_class || is_class_body,
+            scope,
+        );
     }
 }
 
@@ -71,12 +128,24 @@ fn parse_function(
     for child in node.children(&mut cursor) {
         let kind = child.kind();
         if kind == "simple_identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
         } else if kind == "parameter" {
-            parameters.push(child.utf8_text(content.as_bytes()).unwrap_or("").to_string());
+            parameters.push(
+                child
+                    .utf8_text(content.as_bytes())
+                    .unwrap_or("")
+                    .to_string(),
+            );
         } else if kind == "modifiers" {
             let mod_text = child.utf8_text(content.as_bytes()).unwrap_or("");
-            if mod_text.contains("mutating") || mod_text.contains("override") || mod_text.contains("static") || mod_text.contains("class") {
+            if mod_text.contains("mutating")
+                || mod_text.contains("override")
+                || mod_text.contains("static")
+                || mod_text.contains("class")
+            {
                 is_explicit_method = true;
             }
         } else if kind == "async" {
@@ -95,30 +164,38 @@ fn parse_function(
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    let block = &lines[start_line.saturating_sub(1)..end_line.min(lines.len())];
-    let complexity = estimate_complexity(block);
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters,
         is_async,
         is_method: is_explicit_method,
         is_class: false,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
     })
 }
 
-fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInfo> {
+fn parse_class(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
     let mut name = String::new();
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         if child.kind() == "type_identifier" && name.is_empty() {
-            name = child.utf8_text(content.as_bytes()).unwrap_or("").to_string();
+            name = child
+                .utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .to_string();
             break;
         }
     }
@@ -132,15 +209,20 @@ fn parse_class(node: Node, content: &str, _lines: &[&str]) -> Option<FunctionInf
 
     Some(FunctionInfo {
         name,
+        parent: None,
+        qualified_name: String::new(),
         line_start: start_line,
         line_end: end_line,
         parameters: vec![],
         is_async: false,
         is_method: false,
         is_class: true,
-        docstring: None,
+        docstring: doc_comment(lines, start_line, &DOCS),
         decorators: vec![],
         complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
     })
 }
 
@@ -152,6 +234,8 @@ mod tests {
     fn test_extract_swift_functions() {
         let content = "
 import Foundation
+/// Says hello.
+@discardableResult
 func hello() {}
 public func fetchData() async -> Data? { return nil }
 class Service {
@@ -165,22 +249,27 @@ struct Point {
         let extractor = SwiftExtractor;
         let mut fns = extractor.extract(content);
         fns.sort_by(|a, b| a.name.cmp(&b.name));
-        
+
         assert_eq!(fns.len(), 6);
-        
+
         let c = fns.iter().find(|f| f.name == "Service").unwrap();
         assert!(c.is_class);
-        
+
         let p = fns.iter().find(|f| f.name == "Point").unwrap();
         assert!(p.is_class);
-        
+
         let h = fns.iter().find(|f| f.name == "hello").unwrap();
         assert!(!h.is_method);
-        
+        assert_eq!(h.docstring.as_deref(), Some("Says hello."));
+
         let m = fns.iter().find(|f| f.name == "moveBy").unwrap();
         assert!(m.is_method);
         assert_eq!(m.parameters, vec!["x deltaX: Double", "y deltaY: Double"]);
-        
+        assert_eq!(m.qualified_name, "Point.moveBy");
+
+        let s = fns.iter().find(|f| f.name == "start").unwrap();
+        assert_eq!(s.parent.as_deref(), Some("Service"));
+
         let f = fns.iter().find(|f| f.name == "fetchData").unwrap();
         assert!(f.is_async);
     }
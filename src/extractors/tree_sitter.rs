@@ -1,114 +1,426 @@
 // Author: kelexine (https://github.com/kelexine)
-// extractors/tree_sitter.rs — Generic tree-sitter extraction logic
+// extractors/tree_sitter.rs — Generic query-driven tree-sitter extraction
 
-use super::{Extractor, estimate_complexity};
-use crate::models::FunctionInfo;
-use tree_sitter::{Language, Parser, Query, QueryCursor, StreamingIterator};
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment,
+    estimate_complexity, halstead, in_scope, max_nesting, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use anyhow::{Context, Result};
 use std::collections::HashMap;
+use tree_sitter::{Language, Node, Query, QueryCursor, StreamingIterator};
 
-/// A generic extractor that uses tree-sitter queries to identify functions and classes.
-// TODO: Flesh out and complete implementation
-#[allow(dead_code)]
+/// Grammars compiled into loc-rs that a query can target.
+pub const GRAMMARS: &[&str] = &[
+    "bash",
+    "c",
+    "cpp",
+    "csharp",
+    "dart",
+    "elixir",
+    "go",
+    "haskell",
+    "java",
+    "javascript",
+    "kotlin",
+    "lua",
+    "nim",
+    "php",
+    "python",
+    "ruby",
+    "rust",
+    "scala",
+    "swift",
+    "tsx",
+    "typescript",
+    "zig",
+];
+
+/// An extractor driven by a tree-sitter query instead of hand-written traversal.
+///
+/// Every match of a `@function`, `@method` or `@class` capture is reported,
+/// named by the `@name` capture of the same pattern. Optional captures fill
+/// in the rest:
+///
+/// - `@parameters`: a parameter-list node, split into one entry per parameter
+/// - `@parameter`: a single parameter
+/// - `@async`: marks the function `async`
+/// - `@doc`: comment nodes documenting it, markers stripped
+/// - `@decorator`: an attribute, annotation or decorator
+///
+/// Functions inside a `@class` match are methods, scoped by its name.
 pub struct TreeSitterExtractor {
     language: Language,
     query: Query,
+    source: String,
+    decisions: Option<&'static DecisionNodes>,
+    docs: Option<&'static DocStyle>,
 }
 
-#[allow(dead_code)]
 impl TreeSitterExtractor {
     /// Creates a new TreeSitterExtractor for a given language and query string.
-    /// The query string should define captures like @function, @name, @class, @method.
+    /// Without a decision table, complexity falls back to [`estimate_complexity`].
     pub fn new(language: Language, query_source: &str) -> Result<Self, tree_sitter::QueryError> {
         let query = Query::new(&language, query_source)?;
-        Ok(Self { language, query })
+        Ok(Self {
+            language,
+            query,
+            source: query_source.to_string(),
+            decisions: None,
+            docs: None,
+        })
+    }
+
+    /// An extractor for one of the [`GRAMMARS`], reusing the complexity
+    /// tables and doc-comment style of that language's built-in extractor.
+    pub fn for_grammar(grammar: &str, query_source: &str) -> Result<Self> {
+        let (language, decisions, docs) = builtin_grammar(grammar).with_context(|| {
+            format!(
+                "Unknown grammar '{}' (expected one of: {})",
+                grammar,
+                GRAMMARS.join(", ")
+            )
+        })?;
+        let extractor = Self::new(language, query_source)
+            .with_context(|| format!("Invalid query for grammar '{}'", grammar))?;
+        Ok(Self {
+            decisions,
+            docs,
+            ..extractor
+        })
+    }
+
+    /// The query this extractor was built from.
+    pub fn query_source(&self) -> &str {
+        &self.source
+    }
+
+    /// Metrics of one captured definition, named `name`.
+    fn info(
+        &self,
+        node: Node,
+        name: String,
+        is_class: bool,
+        content: &str,
+        lines: &[&str],
+    ) -> FunctionInfo {
+        let line_start = node.start_position().row + 1;
+        let line_end = node.end_position().row + 1;
+
+        let (complexity, cognitive_complexity, max_nesting, counts) = if is_class {
+            (1, 0, 0, Halstead::default())
+        } else if let Some(table) = self.decisions {
+            (
+                complexity(node, content, lines, table),
+                cognitive_complexity(node, &name, content, table),
+                max_nesting(node, table),
+                halstead(node, content),
+            )
+        } else {
+            let block = &lines[line_start.saturating_sub(1)..line_end.min(lines.len())];
+            (estimate_complexity(block), 0, 0, halstead(node, content))
+        };
+
+        FunctionInfo {
+            name,
+            parent: None,
+            qualified_name: String::new(),
+            line_start,
+            line_end,
+            parameters: vec![],
+            is_async: false,
+            is_method: false,
+            is_class,
+            docstring: self
+                .docs
+                .and_then(|style| doc_comment(lines, line_start, style)),
+            decorators: vec![],
+            complexity,
+            cognitive_complexity,
+            halstead: counts,
+            max_nesting,
+        }
     }
 }
 
+/// Language and metric tables of a compiled-in grammar.
+fn builtin_grammar(
+    name: &str,
+) -> Option<(
+    Language,
+    Option<&'static DecisionNodes>,
+    Option<&'static DocStyle>,
+)> {
+    use super::{
+        c, cpp, csharp, dart, go, haskell, java, javascript, kotlin, lua, php, python, ruby, rust,
+        scala, shell, swift, zig,
+    };
+
+    Some(match name.to_lowercase().as_str() {
+        "bash" | "sh" | "shell" => (
+            tree_sitter_bash::LANGUAGE.into(),
+            Some(&shell::DECISIONS),
+            Some(&shell::DOCS),
+        ),
+        "c" => (
+            tree_sitter_c::LANGUAGE.into(),
+            Some(&c::DECISIONS),
+            Some(&c::DOCS),
+        ),
+        "cpp" | "c++" => (
+            tree_sitter_cpp::LANGUAGE.into(),
+            Some(&cpp::DECISIONS),
+            Some(&cpp::DOCS),
+        ),
+        "csharp" | "c#" | "cs" => (
+            tree_sitter_c_sharp::LANGUAGE.into(),
+            Some(&csharp::DECISIONS),
+            Some(&csharp::DOCS),
+        ),
+        "dart" => (
+            tree_sitter_dart::LANGUAGE.into(),
+            Some(&dart::DECISIONS),
+            Some(&dart::DOCS),
+        ),
+        "elixir" | "ex" => (tree_sitter_elixir::LANGUAGE.into(), None, None),
+        "go" => (
+            tree_sitter_go::LANGUAGE.into(),
+            Some(&go::DECISIONS),
+            Some(&go::DOCS),
+        ),
+        "haskell" | "hs" => (
+            tree_sitter_haskell::LANGUAGE.into(),
+            Some(&haskell::DECISIONS),
+            Some(&haskell::DOCS),
+        ),
+        "java" => (
+            tree_sitter_java::LANGUAGE.into(),
+            Some(&java::DECISIONS),
+            Some(&java::DOCS),
+        ),
+        "javascript" | "js" => (
+            tree_sitter_javascript::LANGUAGE.into(),
+            Some(&javascript::DECISIONS),
+            Some(&javascript::DOCS),
+        ),
+        "typescript" | "ts" => (
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Some(&javascript::DECISIONS),
+            Some(&javascript::DOCS),
+        ),
+        "tsx" => (
+            tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Some(&javascript::DECISIONS),
+            Some(&javascript::DOCS),
+        ),
+        "kotlin" | "kt" => (
+            tree_sitter_kotlin_ng::LANGUAGE.into(),
+            Some(&kotlin::DECISIONS),
+            Some(&kotlin::DOCS),
+        ),
+        "lua" => (
+            tree_sitter_lua::LANGUAGE.into(),
+            Some(&lua::DECISIONS),
+            Some(&lua::DOCS),
+        ),
+        "nim" => (tree_sitter_nim::language(), None, None),
+        "php" => (
+            tree_sitter_php::LANGUAGE_PHP.into(),
+            Some(&php::DECISIONS),
+            Some(&php::DOCS),
+        ),
+        "python" | "py" => (
+            tree_sitter_python::LANGUAGE.into(),
+            Some(&python::DECISIONS),
+            None,
+        ),
+        "ruby" | "rb" => (
+            tree_sitter_ruby::LANGUAGE.into(),
+            Some(&ruby::DECISIONS),
+            Some(&ruby::DOCS),
+        ),
+        "rust" | "rs" => (
+            tree_sitter_rust::LANGUAGE.into(),
+            Some(&rust::DECISIONS),
+            Some(&rust::DOCS),
+        ),
+        "scala" => (
+            tree_sitter_scala::LANGUAGE.into(),
+            Some(&scala::DECISIONS),
+            Some(&scala::DOCS),
+        ),
+        "swift" => (
+            tree_sitter_swift::LANGUAGE.into(),
+            Some(&swift::DECISIONS),
+            Some(&swift::DOCS),
+        ),
+        "zig" => (
+            tree_sitter_zig::LANGUAGE.into(),
+            Some(&zig::DECISIONS),
+            Some(&zig::DOCS),
+        ),
+        _ => return None,
+    })
+}
+
 impl Extractor for TreeSitterExtractor {
     fn extract(&self, content: &str) -> Vec<FunctionInfo> {
-        let mut parser = Parser::new();
-        if parser.set_language(&self.language).is_err() {
+        let Some(tree) = super::parse(&self.language, content) else {
             return vec![];
-        }
-
-        let tree = match parser.parse(content, None) {
-            Some(tree) => tree,
-            None => return vec![],
         };
 
         let mut cursor = QueryCursor::new();
         let mut matches = cursor.matches(&self.query, tree.root_node(), content.as_bytes());
 
         let lines: Vec<&str> = content.lines().collect();
-        let mut functions = HashMap::new();
-
         let capture_names = self.query.capture_names();
-        let mut capture_map = HashMap::new();
-        for (i, name) in capture_names.iter().enumerate() {
-            capture_map.insert(*name, i as u32);
-        }
+        let text = |n: Node| {
+            n.utf8_text(content.as_bytes())
+                .unwrap_or("")
+                .trim()
+                .to_string()
+        };
+
+        // Keyed by node id, so a definition matched by several patterns is reported once
+        let mut found = HashMap::new();
 
         while let Some(m) = matches.next() {
-            let mut root_node = None;
+            let mut root = None;
             let mut name = String::new();
-            let mut is_class = false;
-            let mut is_method = false;
+            let mut parameters = Vec::new();
+            let mut is_async = false;
+            let mut docs = Vec::new();
+            let mut decorators = Vec::new();
 
             for cap in m.captures {
-                let capture_name = capture_names[cap.index as usize];
-                let text = cap.node.utf8_text(content.as_bytes()).unwrap_or("").to_string();
-
-                match capture_name {
-                    "function" | "class" | "method" => {
-                        root_node = Some(cap.node);
-                        if capture_name == "class" {
-                            is_class = true;
-                        }
-                        if capture_name == "method" {
-                            is_method = true;
-                        }
-                    }
-                    "name" => {
-                        name = text;
-                    }
+                match capture_names[cap.index as usize] {
+                    kind @ ("function" | "method" | "class") => root = Some((cap.node, kind)),
+                    "name" => name = text(cap.node),
+                    "parameters" => parameters.extend(parameter_list(cap.node, content)),
+                    "parameter" => parameters.push(text(cap.node)),
+                    "async" => is_async = true,
+                    "doc" => docs.push(strip_doc_markers(&text(cap.node))),
+                    "decorator" => decorators.push(text(cap.node)),
                     _ => {}
                 }
             }
 
-            if let Some(node) = root_node
-                && !name.is_empty()
-            {
-                let start_point = node.start_position();
-                let end_point = node.end_position();
-                
-                let line_start = start_point.row + 1;
-                let line_end = end_point.row + 1;
-                
-                // Fallback to simple complexity estimation for now
-                // TODO: Flesh out and complete implementation
-                let block = &lines[line_start.saturating_sub(1)..line_end.min(lines.len())];
-                let complexity = if is_class { 1 } else { estimate_complexity(block) };
-
-                let info = FunctionInfo {
-                    name: name.clone(),
-                    line_start,
-                    line_end,
-                    parameters: vec![], // TODO: Expand this logic as needed for params
-                    is_async: false,    // TODO: Expand this logic for async
-                    is_method,
-                    is_class,
-                    docstring: None,
-                    decorators: vec![],
-                    complexity,
-                };
-
-                // Use node id to avoid duplicates if multiple queries match the same node
-                functions.insert(node.id(), info);
+            let Some((node, kind)) = root else {
+                continue;
+            };
+            if name.is_empty() || found.contains_key(&node.id()) {
+                continue;
             }
+
+            let mut info = self.info(node, name, kind == "class", content, &lines);
+            info.parameters = parameters;
+            info.is_async = is_async;
+            info.is_method = kind == "method";
+            info.decorators = decorators;
+            if !docs.is_empty() {
+                let doc = docs.join("\n").trim().to_string();
+                info.docstring = (!doc.is_empty()).then_some(doc);
+            }
+            found.insert(node.id(), (node.byte_range(), info));
         }
 
-        let mut result: Vec<_> = functions.into_values().collect();
+        // Scope each match by the @class matches enclosing it, outermost first
+        let mut classes: Vec<_> = found
+            .values()
+            .filter(|(_, f)| f.is_class)
+            .map(|(range, f)| (range.clone(), f.name.clone()))
+            .collect();
+        classes.sort_by_key(|(range, _)| range.start);
+
+        let mut result: Vec<_> = found
+            .into_values()
+            .map(|(range, mut info)| {
+                let scope: Vec<&str> = classes
+                    .iter()
+                    .filter(|(outer, _)| {
+                        outer.start <= range.start && range.end <= outer.end && *outer != range
+                    })
+                    .map(|(_, name)| name.as_str())
+                    .collect();
+                info.is_method |= !info.is_class && !scope.is_empty();
+                in_scope(info, &scope.join("."), ".")
+            })
+            .collect();
         result.sort_by_key(|f| f.line_start);
         result
     }
 }
+
+/// Text of a `@doc` capture without comment markers (`///`, `#`, `--`,
+/// `/** */`, leading `*`) or docstring quotes.
+fn strip_doc_markers(text: &str) -> String {
+    let text = ["/**", "/*!", "/*", "\"\"\"", "'''"]
+        .iter()
+        .find_map(|open| text.strip_prefix(open))
+        .unwrap_or(text);
+    let text = ["*/", "\"\"\"", "'''"]
+        .iter()
+        .find_map(|close| text.strip_suffix(close))
+        .unwrap_or(text);
+    text.lines()
+        .map(|line| {
+            let line = line.trim();
+            ["///", "//!", "//", "##", "#", "--", "*"]
+                .iter()
+                .find_map(|marker| line.strip_prefix(marker))
+                .unwrap_or(line)
+                .trim()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_extractor_counts_traits_and_enums() {
+        let query = r#"
+            (function_item name: (identifier) @name parameters: (parameters) @parameters) @function
+            (trait_item name: (type_identifier) @name) @class
+            (enum_item name: (type_identifier) @name) @class
+            (function_signature_item name: (identifier) @name parameters: (parameters) @parameters) @method
+        "#;
+        let extractor = TreeSitterExtractor::for_grammar("rust", query).unwrap();
+        let content = "
+trait Shape {
+    fn area(&self) -> f64;
+}
+enum Kind { A, B }
+/// Picks one.
+fn pick(a: u8, b: u8) -> u8 {
+    if a > b && a > 0 { a } else { b }
+}
+";
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(names, vec!["Shape", "Shape.area", "Kind", "pick"]);
+
+        let area = &fns[1];
+        assert!(area.is_method);
+        assert_eq!(area.parameters, vec!["&self"]);
+
+        let pick = &fns[3];
+        assert_eq!(pick.parameters, vec!["a: u8", "b: u8"]);
+        // No @doc capture: the Rust doc-comment style applies
+        assert_eq!(pick.docstring.as_deref(), Some("Picks one."));
+        // if, && from the Rust decision table
+        assert_eq!(pick.complexity, 3);
+        assert_eq!(pick.max_nesting, 1);
+    }
+
+    #[test]
+    fn test_for_grammar_rejects_unknown_grammar_and_bad_query() {
+        assert!(TreeSitterExtractor::for_grammar("cobol", "").is_err());
+        assert!(TreeSitterExtractor::for_grammar("rust", "(no_such_node) @function").is_err());
+        assert_eq!(strip_doc_markers("/**\n * Adds.\n */"), "Adds.");
+    }
+}
@@ -0,0 +1,282 @@
+// Author: kelexine (https://github.com/kelexine)
+// extractors/zig.rs — Zig function/container extraction via Tree-sitter
+
+use super::{
+    DecisionNodes, DocStyle, Extractor, cognitive_complexity, complexity, doc_comment, halstead,
+    in_scope, max_nesting, nest_scope, parameter_list,
+};
+use crate::models::{FunctionInfo, Halstead};
+use tree_sitter::Node;
+
+pub struct ZigExtractor;
+
+/// `///` doc comments.
+pub(crate) const DOCS: DocStyle = DocStyle {
+    line: &["///"],
+    block: &[],
+    skip: &[],
+};
+
+/// Zig decision points for cyclomatic complexity.
+pub(crate) const DECISIONS: DecisionNodes = DecisionNodes {
+    branches: &[
+        "if_statement",
+        "if_expression",
+        "while_statement",
+        "while_expression",
+        "for_statement",
+        "for_expression",
+    ],
+    arms: &["switch_case"],
+    binary: &["binary_expression"],
+    operators: &["and", "or"],
+    nested: &[
+        "function_declaration",
+        "struct_declaration",
+        "enum_declaration",
+        "union_declaration",
+    ],
+    ifs: &["if_statement", "if_expression"],
+    elses: &["else_clause"],
+    structures: &[
+        "switch_expression",
+        "while_statement",
+        "while_expression",
+        "for_statement",
+        "for_expression",
+    ],
+    lambdas: &[],
+    jumps: &[],
+    calls: &["call_expression"],
+};
+
+/// Container expressions a `const` can name, by the decorator they carry.
+const CONTAINERS: &[(&str, &str)] = &[
+    ("struct_declaration", "struct"),
+    ("enum_declaration", "enum"),
+    ("union_declaration", "union"),
+    ("opaque_declaration", "opaque"),
+];
+
+/// Function modifiers recorded as decorators.
+const MODIFIERS: &[&str] = &["pub", "export", "extern", "inline", "noinline"];
+
+impl Extractor for ZigExtractor {
+    fn extract(&self, content: &str) -> Vec<FunctionInfo> {
+        let Some(tree) = super::parse(&tree_sitter_zig::LANGUAGE.into(), content) else {
+            return vec![];
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = Vec::new();
+
+        traverse(tree.root_node(), content, &lines, &mut functions, false, "");
+
+        functions.sort_by_key(|f| f.line_start);
+        functions
+    }
+}
+
+fn traverse(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    functions: &mut Vec<FunctionInfo>,
+    in_container: bool,
+    scope: &str,
+) {
+    let kind = node.kind();
+    let mut inner = None;
+
+    if kind == "function_declaration" {
+        if let Some(info) = parse_function(node, content, lines, in_container) {
+            functions.push(in_scope(info, scope, "."));
+        }
+    } else if kind == "variable_declaration"
+        && let Some(info) = parse_container(node, content, lines)
+    {
+        // `const Point = struct { ... }` names the container
+        inner = Some(nest_scope(scope, ".", &info.name));
+        functions.push(in_scope(info, scope, "."));
+    }
+
+    let is_container = CONTAINERS.iter().any(|(k, _)| *k == kind);
+    let in_container = match kind {
+        "function_declaration" => false,
+        _ => in_container || is_container,
+    };
+    let scope = inner.as_deref().unwrap_or(scope);
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        traverse(child, content, lines, functions, in_container, scope);
+    }
+}
+
+/// The declared identifier: the `name` field, or the first identifier.
+fn declared_name<'a>(node: Node, content: &'a str) -> Option<&'a str> {
+    let name = node.child_by_field_name("name").or_else(|| {
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .find(|c| c.kind() == "identifier")
+    })?;
+    name.utf8_text(content.as_bytes()).ok()
+}
+
+fn parse_function(
+    node: Node,
+    content: &str,
+    lines: &[&str],
+    is_method: bool,
+) -> Option<FunctionInfo> {
+    let name = declared_name(node, content)?.to_string();
+
+    let mut cursor = node.walk();
+    let parameters = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "parameters")
+        .map(|list| parameter_list(list, content))
+        .unwrap_or_default();
+    let mut cursor = node.walk();
+    let decorators = node
+        .children(&mut cursor)
+        .filter(|c| MODIFIERS.contains(&c.kind()))
+        .map(|c| c.kind().to_string())
+        .collect();
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    let complexity = complexity(node, content, lines, &DECISIONS);
+    let cognitive_complexity = cognitive_complexity(node, &name, content, &DECISIONS);
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters,
+        is_async: false,
+        is_method,
+        is_class: false,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators,
+        complexity,
+        cognitive_complexity,
+        halstead: halstead(node, content),
+        max_nesting: max_nesting(node, &DECISIONS),
+    })
+}
+
+/// A `const` bound to a struct, enum, union or opaque type; any other
+/// variable yields `None`.
+fn parse_container(node: Node, content: &str, lines: &[&str]) -> Option<FunctionInfo> {
+    let mut cursor = node.walk();
+    let decorator = node
+        .named_children(&mut cursor)
+        .find_map(|c| CONTAINERS.iter().find(|(k, _)| *k == c.kind()))
+        .map(|&(_, decorator)| decorator)?;
+    let name = declared_name(node, content)?.to_string();
+
+    let start_line = node.start_position().row + 1;
+    let end_line = node.end_position().row + 1;
+
+    Some(FunctionInfo {
+        name,
+        parent: None,
+        qualified_name: String::new(),
+        line_start: start_line,
+        line_end: end_line,
+        parameters: vec![],
+        is_async: false,
+        is_method: false,
+        is_class: true,
+        docstring: doc_comment(lines, start_line, &DOCS),
+        decorators: vec![decorator.to_string()],
+        complexity: 1,
+        cognitive_complexity: 0,
+        halstead: Halstead::default(),
+        max_nesting: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_zig_functions() {
+        let content = "
+const std = @import(\"std\");
+
+/// A 2D point.
+pub const Point = struct {
+    x: i32,
+    y: i32,
+
+    /// Manhattan length.
+    pub fn len(self: Point) i32 {
+        if (self.x < 0 or self.y < 0) return 0;
+        return self.x + self.y;
+    }
+};
+
+fn classify(n: u8) u8 {
+    return switch (n) {
+        0 => 0,
+        1, 2 => 1,
+        else => 2,
+    };
+}
+";
+        let extractor = ZigExtractor;
+        let fns = extractor.extract(content);
+        let names: Vec<_> = fns.iter().map(|f| f.qualified_name.as_str()).collect();
+        assert_eq!(names, vec!["Point", "Point.len", "classify"]);
+
+        let point = &fns[0];
+        assert!(point.is_class);
+        assert_eq!(point.decorators, vec!["struct"]);
+        assert_eq!(point.docstring.as_deref(), Some("A 2D point."));
+
+        let len = &fns[1];
+        assert!(len.is_method);
+        assert_eq!(len.decorators, vec!["pub"]);
+        assert_eq!(len.parameters, vec!["self: Point"]);
+        assert_eq!(len.docstring.as_deref(), Some("Manhattan length."));
+        // if, or
+        assert_eq!(len.complexity, 3);
+
+        let classify = &fns[2];
+        assert!(!classify.is_method);
+        // Two cases besides `else`
+        assert_eq!(classify.complexity, 3);
+    }
+
+    #[test]
+    fn test_zig_loops_switch_and_boolean_operators() {
+        let content = "
+fn scan(items: []const u8, strict: bool) u32 {
+    var count: u32 = 0;
+    for (items) |c| {
+        switch (c) {
+            'a'...'z' => {
+                count += 1;
+            },
+            '0', '1' => {},
+            else => {},
+        }
+    }
+    while (count > 10 and !strict or count > 100) {
+        count -= 1;
+    }
+    return count;
+}
+";
+        let fns = ZigExtractor.extract(content);
+        let scan = fns.iter().find(|f| f.name == "scan").unwrap();
+        // for, two cases besides `else`, while, and, or
+        assert_eq!(scan.complexity, 7);
+    }
+}
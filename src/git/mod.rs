@@ -0,0 +1,246 @@
+// Author: kelexine (https://github.com/kelexine)
+// git/mod.rs — Reading trees, blobs and commit logs through the git CLI
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, TimeZone, Utc};
+
+/// A blob listed by `git ls-tree`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntry {
+    /// Path relative to the directory `ls-tree` ran in.
+    pub path: PathBuf,
+    /// Blob object id.
+    pub oid: String,
+    pub size: u64,
+}
+
+/// A commit with its committer timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    pub oid: String,
+    pub date: DateTime<Utc>,
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let out = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| "Failed to run git (is it installed?)")?;
+    if !out.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(out.stdout)
+}
+
+/// Resolve any revision (commit, tag, branch, `HEAD~3`...) to a full commit id.
+pub fn resolve_rev(dir: &Path, rev: &str) -> Result<String> {
+    let spec = format!("{}^{{commit}}", rev);
+    let out = git(dir, &["rev-parse", "--verify", "--quiet", &spec])
+        .with_context(|| format!("Unknown revision '{}'", rev))?;
+    Ok(String::from_utf8_lossy(&out).trim().to_string())
+}
+
+/// Blobs in `rev` under `dir`, recursively, with paths relative to `dir`.
+/// Submodules and symlinks are skipped.
+pub fn ls_tree(dir: &Path, rev: &str) -> Result<Vec<TreeEntry>> {
+    let out = git(dir, &["ls-tree", "-r", "-z", "-l", rev])?;
+    let stdout = String::from_utf8_lossy(&out);
+
+    let mut entries = Vec::new();
+    // Each record: "<mode> <type> <oid> <size>\t<path>\0"
+    for record in stdout.split('\0').filter(|s| !s.is_empty()) {
+        let Some((meta, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let mut fields = meta.split_whitespace();
+        let (Some(mode), Some(kind), Some(oid), Some(size)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if kind != "blob" || mode == "120000" {
+            continue;
+        }
+        entries.push(TreeEntry {
+            path: PathBuf::from(path),
+            oid: oid.to_string(),
+            size: size.parse().unwrap_or(0),
+        });
+    }
+    Ok(entries)
+}
+
+/// First-parent history of `rev`, oldest first.
+pub fn first_parent_log(dir: &Path, rev: &str) -> Result<Vec<Commit>> {
+    let out = git(
+        dir,
+        &["log", "--first-parent", "--reverse", "--format=%H %ct", rev],
+    )?;
+    let stdout = String::from_utf8_lossy(&out);
+
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let (oid, ts) = line.trim().split_once(' ')?;
+            let date = Utc.timestamp_opt(ts.parse().ok()?, 0).single()?;
+            Some(Commit {
+                oid: oid.to_string(),
+                date,
+            })
+        })
+        .collect())
+}
+
+/// Streams blob contents out of one long-running `git cat-file --batch`.
+pub struct BlobReader {
+    child: Child,
+    stdin: BufWriter<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl BlobReader {
+    pub fn new(dir: &Path) -> Result<Self> {
+        let mut child = Command::new("git")
+            .args(["cat-file", "--batch"])
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| "Failed to start git cat-file")?;
+        let stdin = BufWriter::new(child.stdin.take().expect("piped stdin"));
+        let stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+        Ok(Self {
+            child,
+            stdin,
+            stdout,
+        })
+    }
+
+    /// Raw bytes of the blob `oid`.
+    pub fn read(&mut self, oid: &str) -> Result<Vec<u8>> {
+        writeln!(self.stdin, "{}", oid)?;
+        self.stdin.flush()?;
+
+        // Header: "<oid> <type> <size>\n", or "<oid> missing\n"
+        let mut header = String::new();
+        self.stdout.read_line(&mut header)?;
+        let mut fields = header.split_whitespace();
+        let size = match (fields.next(), fields.next(), fields.next()) {
+            (Some(_), Some("blob"), Some(size)) => size.parse::<usize>()?,
+            _ => bail!("Object {} is not a readable blob", oid),
+        };
+
+        // Content followed by a single newline
+        let mut buf = vec![0u8; size + 1];
+        self.stdout.read_exact(&mut buf)?;
+        buf.truncate(size);
+        Ok(buf)
+    }
+}
+
+impl Drop for BlobReader {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// How often a file changed, from `git log --numstat`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FileChurn {
+    /// Commits touching the file.
+    pub commits: usize,
+    pub added: usize,
+    pub deleted: usize,
+}
+
+impl FileChurn {
+    /// Lines added plus lines deleted.
+    pub fn lines(&self) -> usize {
+        self.added + self.deleted
+    }
+}
+
+/// Per-file churn over the history of `rev`, for files under `dir` (paths
+/// relative to `dir`). Renames are not followed; binary changes count as
+/// commits without lines.
+pub fn file_churn(dir: &Path, rev: &str) -> Result<HashMap<PathBuf, FileChurn>> {
+    let out = git(
+        dir,
+        &[
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "--numstat",
+            "--no-renames",
+            "--relative",
+            "--format=commit %H",
+            rev,
+            "--",
+        ],
+    )?;
+    let stdout = String::from_utf8_lossy(&out);
+
+    let mut map: HashMap<PathBuf, FileChurn> = HashMap::new();
+    for line in stdout.lines() {
+        if line.is_empty() || line.starts_with("commit ") {
+            continue;
+        }
+        // "<added>\t<deleted>\t<path>", with "-" for binary files
+        let mut fields = line.splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let entry = map.entry(PathBuf::from(path)).or_default();
+        entry.commits += 1;
+        entry.added += added.parse().unwrap_or(0);
+        entry.deleted += deleted.parse().unwrap_or(0);
+    }
+    Ok(map)
+}
+
+/// One line of `git blame` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    pub author: String,
+    pub content: String,
+}
+
+/// Blame `path` (relative to `dir`) at `rev`, or the working tree copy when
+/// `rev` is `None`. Uncommitted lines are attributed to "Not Committed Yet".
+pub fn blame(dir: &Path, rev: Option<&str>, path: &Path) -> Result<Vec<BlameLine>> {
+    let path = path.to_string_lossy();
+    let mut args = vec!["blame", "--line-porcelain"];
+    args.extend(rev);
+    args.extend(["--", path.as_ref()]);
+    let out = git(dir, &args)?;
+    let stdout = String::from_utf8_lossy(&out);
+
+    // Every line gets a full header block ending in "\t<content>"
+    let mut lines = Vec::new();
+    let mut author = String::new();
+    for line in stdout.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            lines.push(BlameLine {
+                author: std::mem::take(&mut author),
+                content: content.to_string(),
+            });
+        } else if let Some(name) = line.strip_prefix("author ") {
+            author = name.to_string();
+        }
+    }
+    Ok(lines)
+}
@@ -0,0 +1,231 @@
+// Author: kelexine (https://github.com/kelexine)
+// history/mod.rs — LOC over time, computed from git objects without checkouts
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, Duration, Utc};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::compare::Metrics;
+use crate::counter::{self, ScanConfig};
+use crate::git::{self, BlobReader, Commit};
+use crate::models::FileInfo;
+
+/// Which commits `--history` samples along the first-parent chain of `rev`.
+#[derive(Debug, Clone)]
+pub struct HistoryOptions {
+    /// Revision whose history is walked (default `HEAD`).
+    pub rev: String,
+    /// Keep every Nth commit (1 = all of them).
+    pub every: usize,
+    /// Keep at most one commit per interval; takes precedence over `every`.
+    pub interval: Option<Duration>,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            rev: "HEAD".to_string(),
+            every: 1,
+            interval: None,
+        }
+    }
+}
+
+/// Totals of one sampled revision.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryPoint {
+    pub commit: String,
+    pub date: DateTime<Utc>,
+    pub total: Metrics,
+    /// Keyed like `Breakdown` (extension, or file name when extension-less).
+    pub breakdown: BTreeMap<String, Metrics>,
+}
+
+/// Time series produced by [`run_history`], oldest point first.
+#[derive(Debug, Clone, Serialize)]
+pub struct History {
+    pub root: PathBuf,
+    pub rev: String,
+    pub points: Vec<HistoryPoint>,
+}
+
+/// Parse an interval such as `30d`, `2w`, `6m` (30 days) or `1y` (365 days).
+/// A bare number is read as days.
+pub fn parse_interval(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let (num, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => s.split_at(i),
+        None => (s, "d"),
+    };
+    let n: i64 = match num.parse() {
+        Ok(n) if n > 0 => n,
+        _ => bail!("Invalid interval '{}': expected e.g. 7d, 2w, 3m or 1y", s),
+    };
+    let days = match unit {
+        "d" => n,
+        "w" => n * 7,
+        "m" => n * 30,
+        "y" => n * 365,
+        _ => bail!("Invalid interval unit in '{}': use d, w, m or y", s),
+    };
+    Ok(Duration::days(days))
+}
+
+/// Pick the commits to measure from an oldest-first log. The newest commit is
+/// always kept so the series ends at `rev`.
+pub fn sample(commits: &[Commit], options: &HistoryOptions) -> Vec<Commit> {
+    let mut picked: Vec<Commit> = Vec::new();
+
+    if let Some(interval) = options.interval {
+        for c in commits {
+            if picked
+                .last()
+                .is_none_or(|last| c.date - last.date >= interval)
+            {
+                picked.push(c.clone());
+            }
+        }
+    } else {
+        let every = options.every.max(1);
+        picked.extend(commits.iter().step_by(every).cloned());
+    }
+
+    if let Some(last) = commits.last()
+        && picked.last() != Some(last)
+    {
+        picked.push(last.clone());
+    }
+    picked
+}
+
+/// Measure every sampled revision of `config.target_dir`.
+///
+/// Blobs are read through `git cat-file --batch` and counted once per object
+/// id, so unchanged files cost nothing on later revisions. The language
+/// filter and function extraction settings of `config` apply.
+pub fn run_history(config: &ScanConfig, options: &HistoryOptions) -> Result<History> {
+    if !config.is_git_repo {
+        bail!(
+            "--history needs a git repository: {}",
+            config.target_dir.display()
+        );
+    }
+    let root = &config.target_dir;
+    let commits = sample(&git::first_parent_log(root, &options.rev)?, options);
+
+    let mut reader = BlobReader::new(root)?;
+    // Blob id -> per-file metrics (None for binary blobs)
+    let mut cache: HashMap<String, Option<Metrics>> = HashMap::new();
+    let mut points = Vec::with_capacity(commits.len());
+
+    for commit in commits {
+        let entries: Vec<_> = git::ls_tree(root, &commit.oid)?
+            .into_iter()
+            .filter(|e| config.allows_extension(&e.path))
+            .collect();
+
+        let mut fresh = Vec::new();
+        let mut seen = HashSet::new();
+        for e in &entries {
+            if !cache.contains_key(&e.oid) && seen.insert(e.oid.as_str()) {
+                fresh.push((e.oid.clone(), e.path.clone(), reader.read(&e.oid)?));
+            }
+        }
+
+        let measure = |(oid, path, bytes): &(String, PathBuf, Vec<u8>)| {
+            let fi = counter::process_blob(path, bytes, config, None).filter(|fi| !fi.is_binary);
+            (oid.clone(), fi.as_ref().map(Metrics::of_file))
+        };
+        let measured: Vec<_> = if config.parallel {
+            fresh.par_iter().map(measure).collect()
+        } else {
+            fresh.iter().map(measure).collect()
+        };
+        cache.extend(measured);
+
+        let mut total = Metrics::default();
+        let mut breakdown: BTreeMap<String, Metrics> = BTreeMap::new();
+        for e in &entries {
+            if let Some(Some(m)) = cache.get(&e.oid) {
+                total.add(m);
+                breakdown.entry(breakdown_key(&e.path)).or_default().add(m);
+            }
+        }
+
+        points.push(HistoryPoint {
+            commit: commit.oid,
+            date: commit.date,
+            total,
+            breakdown,
+        });
+    }
+
+    Ok(History {
+        root: root.clone(),
+        rev: options.rev.clone(),
+        points,
+    })
+}
+
+fn breakdown_key(path: &Path) -> String {
+    FileInfo::new(path.to_path_buf(), 0, 0, 0, 0, false, None).breakdown_key()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn commits(days: &[i64]) -> Vec<Commit> {
+        days.iter()
+            .enumerate()
+            .map(|(i, d)| Commit {
+                oid: format!("c{}", i),
+                date: Utc.timestamp_opt(d * 86_400, 0).unwrap(),
+            })
+            .collect()
+    }
+
+    fn oids(picked: &[Commit]) -> Vec<&str> {
+        picked.iter().map(|c| c.oid.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sample_every_nth_keeps_newest() {
+        let log = commits(&[0, 1, 2, 3, 4, 5, 6]);
+        let options = HistoryOptions {
+            every: 3,
+            ..Default::default()
+        };
+        assert_eq!(oids(&sample(&log, &options)), ["c0", "c3", "c6"]);
+
+        let options = HistoryOptions {
+            every: 4,
+            ..Default::default()
+        };
+        assert_eq!(oids(&sample(&log, &options)), ["c0", "c4", "c6"]);
+    }
+
+    #[test]
+    fn test_sample_by_interval() {
+        let log = commits(&[0, 2, 6, 8, 15, 16]);
+        let options = HistoryOptions {
+            interval: Some(parse_interval("1w").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(oids(&sample(&log, &options)), ["c0", "c3", "c4", "c5"]);
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("10").unwrap(), Duration::days(10));
+        assert_eq!(parse_interval("2w").unwrap(), Duration::days(14));
+        assert_eq!(parse_interval("1y").unwrap(), Duration::days(365));
+        assert!(parse_interval("0d").is_err());
+        assert!(parse_interval("3h").is_err());
+    }
+}
@@ -0,0 +1,155 @@
+// Author: kelexine (https://github.com/kelexine)
+// hotspots/mod.rs — Churn × complexity ranking of files worth refactoring first
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+
+use crate::counter::ScanConfig;
+use crate::git::{self, FileChurn};
+use crate::models::ScanResult;
+
+/// A file ranked by how often it changes and how complex it is.
+#[derive(Debug, Clone, Serialize)]
+pub struct Hotspot {
+    pub path: PathBuf,
+    /// Commits touching the file.
+    pub commits: usize,
+    /// Lines added plus deleted over all those commits.
+    pub churn: usize,
+    pub code: usize,
+    pub max_complexity: u32,
+    pub total_complexity: u32,
+    /// Combined score in `0.0..=1.0`; see [`rank`].
+    pub score: f64,
+}
+
+/// Churn of every scanned file over the history of the scanned revision.
+pub fn collect_churn(config: &ScanConfig) -> Result<HashMap<PathBuf, FileChurn>> {
+    if !config.is_git_repo {
+        bail!(
+            "--hotspots needs a git repository: {}",
+            config.target_dir.display()
+        );
+    }
+    let rev = config.rev.as_deref().unwrap_or("HEAD");
+    let churn = git::file_churn(&config.target_dir, rev)?;
+    Ok(churn
+        .into_iter()
+        .map(|(rel, c)| (config.target_dir.join(rel), c))
+        .collect())
+}
+
+/// Rank text files by `change × complexity` and keep the top `n`.
+///
+/// Both factors are normalized against the largest value in the scan, so the
+/// score is relative to this codebase:
+/// - change = mean of commits and churn
+/// - complexity = mean of total cyclomatic complexity and code lines, or code
+///   lines alone when no functions were extracted
+///
+/// Files never committed (no churn entry) are skipped.
+pub fn rank(result: &ScanResult, churn: &HashMap<PathBuf, FileChurn>, n: usize) -> Vec<Hotspot> {
+    let mut hotspots: Vec<Hotspot> = result
+        .files
+        .iter()
+        .filter(|f| !f.is_binary)
+        .filter_map(|fi| {
+            let c = churn.get(&fi.path)?;
+            Some(Hotspot {
+                path: fi.path.clone(),
+                commits: c.commits,
+                churn: c.lines(),
+                code: fi.code,
+                max_complexity: fi.max_complexity(),
+                total_complexity: fi.total_complexity(),
+                score: 0.0,
+            })
+        })
+        .collect();
+
+    let max = |f: fn(&Hotspot) -> f64| hotspots.iter().map(f).fold(0.0, f64::max);
+    let norm = |v: f64, max: f64| if max > 0.0 { v / max } else { 0.0 };
+    let max_commits = max(|h| h.commits as f64);
+    let max_churn = max(|h| h.churn as f64);
+    let max_code = max(|h| h.code as f64);
+    let max_complexity = max(|h| h.total_complexity as f64);
+
+    for h in &mut hotspots {
+        let change = (norm(h.commits as f64, max_commits) + norm(h.churn as f64, max_churn)) / 2.0;
+        let size = norm(h.code as f64, max_code);
+        let complexity = if max_complexity > 0.0 {
+            (norm(h.total_complexity as f64, max_complexity) + size) / 2.0
+        } else {
+            size
+        };
+        h.score = change * complexity;
+    }
+
+    hotspots.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.path.cmp(&b.path)));
+    hotspots.truncate(n);
+    hotspots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Breakdown, FileInfo, FunctionInfo};
+
+    fn file(path: &str, code: usize, complexity: u32) -> FileInfo {
+        let func = FunctionInfo {
+            name: "f".into(),
+            parent: None,
+            qualified_name: String::new(),
+            line_start: 1,
+            line_end: 2,
+            parameters: vec![],
+            is_async: false,
+            is_method: false,
+            is_class: false,
+            docstring: None,
+            decorators: vec![],
+            complexity,
+            cognitive_complexity: 0,
+            halstead: Default::default(),
+            max_nesting: 0,
+        };
+        FileInfo::new(PathBuf::from(path), code, code, 0, 0, false, None).with_functions(vec![func])
+    }
+
+    fn churn(commits: usize, lines: usize) -> FileChurn {
+        FileChurn {
+            commits,
+            added: lines,
+            deleted: 0,
+        }
+    }
+
+    #[test]
+    fn test_rank_prefers_churning_complex_files() {
+        let result = ScanResult {
+            root: PathBuf::from("/r"),
+            files: vec![
+                file("/r/hot.rs", 400, 30),
+                file("/r/stable_complex.rs", 500, 40),
+                file("/r/busy_trivial.rs", 20, 1),
+                file("/r/untracked.rs", 900, 90),
+            ],
+            breakdown: Breakdown::new(),
+        };
+        let churn = HashMap::from([
+            (PathBuf::from("/r/hot.rs"), churn(40, 900)),
+            (PathBuf::from("/r/stable_complex.rs"), churn(2, 50)),
+            (PathBuf::from("/r/busy_trivial.rs"), churn(50, 1000)),
+        ]);
+
+        let ranked = rank(&result, &churn, 2);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].path, PathBuf::from("/r/hot.rs"));
+        assert_eq!(ranked[0].max_complexity, 30);
+        assert!(ranked[0].score > ranked[1].score);
+        assert!(ranked.iter().all(|h| !h.path.ends_with("untracked.rs")));
+    }
+}
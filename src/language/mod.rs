@@ -15,6 +15,7 @@ pub static LANGUAGE_MAP: Lazy<HashMap<&'static str, Vec<&'static str>>> = Lazy::
     m.insert("java", vec![".java"]);
     m.insert("kotlin", vec![".kt", ".kts"]);
     m.insert("swift", vec![".swift"]);
+    m.insert("dart", vec![".dart"]);
     m.insert("c", vec![".c", ".h"]);
     m.insert("cpp", vec![".cpp", ".cc", ".cxx", ".hpp", ".hxx", ".h++"]);
     m.insert("csharp", vec![".cs"]);
@@ -93,6 +94,11 @@ pub fn resolve_extensions(input: &str) -> Vec<String> {
     vec![format!(".{}", lower)]
 }
 
+/// Whether `ext` (e.g. `".rs"`) belongs to a language in [`LANGUAGE_MAP`].
+pub fn is_known_extension(ext: &str) -> bool {
+    LANGUAGE_MAP.values().any(|exts| exts.contains(&ext))
+}
+
 /// Binary extensions — files with these extensions are skipped for line counting.
 pub static BINARY_EXTENSIONS: Lazy<std::collections::HashSet<&'static str>> = Lazy::new(|| {
     [
@@ -110,75 +116,287 @@ pub static BINARY_EXTENSIONS: Lazy<std::collections::HashSet<&'static str>> = La
 /// Specification for comment markers in a language.
 #[derive(Debug, Clone, Copy)]
 pub struct CommentSpec {
-    /// Single-line comment marker (e.g. "//" or "#")
-    pub single: Option<&'static str>,
-    /// Multi-line comment (start, end)
-    pub multi: Option<(&'static str, &'static str)>,
+    /// Single-line comment markers (e.g. "//" or "#")
+    pub single: &'static [&'static str],
+    /// Block comment (start, end) pairs, tried before the single-line markers
+    pub multi: &'static [(&'static str, &'static str)],
+    /// Block comments nest, so `/* /* */ */` is one comment
+    pub nested: bool,
+    /// String literal forms, tried in order, so comment markers inside strings are ignored
+    pub strings: &'static [StringSpec],
+    /// Documentation comment markers, tried before `single` (e.g. "///")
+    pub doc_single: &'static [&'static str],
+    /// Documentation block (start, end) pairs, tried before `multi` (e.g. "/**")
+    pub doc_multi: &'static [(&'static str, &'static str)],
+    /// Ordinary comments directly above a line starting with one of these
+    /// keywords are documentation (Go doc comments)
+    pub doc_before: &'static [&'static str],
+    /// A multi-line string that opens a line is a docstring and counts as documentation (Python)
+    pub docstrings: bool,
+}
+
+/// A string literal form recognised by the line classifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringSpec {
+    /// Text between `open` and `close`. With `escapes`, a backslash skips the
+    /// next character; without `multiline`, the literal ends with its line.
+    Quoted {
+        open: &'static str,
+        close: &'static str,
+        escapes: bool,
+        multiline: bool,
+    },
+    /// `'x'` or `'\n'`; any other `'` (a Rust lifetime, a C++ digit separator) is code.
+    Char,
+    /// Rust raw strings: `r"…"`, `r#"…"#`, `br##"…"##`.
+    RawHash,
+    /// `<<TAG` (also `<<-`, `<<~`, PHP `<<<`, quoted tags): the following lines
+    /// up to one starting with `TAG` are string content.
+    Heredoc,
+}
+
+const fn quoted(
+    open: &'static str,
+    close: &'static str,
+    escapes: bool,
+    multiline: bool,
+) -> StringSpec {
+    StringSpec::Quoted {
+        open,
+        close,
+        escapes,
+        multiline,
+    }
 }
 
+// Common literal forms. `_RAW` forms have no backslash escapes.
+const DOUBLE: StringSpec = quoted("\"", "\"", true, false);
+const SINGLE: StringSpec = quoted("'", "'", true, false);
+const DOUBLE_MULTILINE: StringSpec = quoted("\"", "\"", true, true);
+const SINGLE_MULTILINE: StringSpec = quoted("'", "'", true, true);
+const SINGLE_RAW: StringSpec = quoted("'", "'", false, false);
+const SINGLE_RAW_MULTILINE: StringSpec = quoted("'", "'", false, true);
+const DOUBLE_RAW: StringSpec = quoted("\"", "\"", false, false);
+const TRIPLE_DOUBLE: StringSpec = quoted("\"\"\"", "\"\"\"", true, true);
+const TRIPLE_DOUBLE_RAW: StringSpec = quoted("\"\"\"", "\"\"\"", false, true);
+const TRIPLE_SINGLE: StringSpec = quoted("'''", "'''", true, true);
+const TRIPLE_SINGLE_RAW: StringSpec = quoted("'''", "'''", false, true);
+/// JavaScript template literal
+const TEMPLATE: StringSpec = quoted("`", "`", true, true);
+const BACKTICK_RAW: StringSpec = quoted("`", "`", false, true);
+/// C# verbatim string
+const VERBATIM: StringSpec = quoted("@\"", "\"", false, true);
+/// Zig multi-line string line: `\\` to the end of the line
+const ZIG_LINE: StringSpec = quoted("\\\\", "\n", false, false);
+/// Lua long string
+const LUA_LONG: StringSpec = quoted("[[", "]]", false, true);
+/// Dart literals: raw `r'…'` forms have no escapes, triple-quoted forms span lines
+const DART_STRINGS: &[StringSpec] = &[
+    quoted("r'''", "'''", false, true),
+    quoted("r\"\"\"", "\"\"\"", false, true),
+    TRIPLE_SINGLE,
+    TRIPLE_DOUBLE,
+    quoted("r'", "'", false, false),
+    quoted("r\"", "\"", false, false),
+    DOUBLE,
+    SINGLE,
+];
+
 /// Registry for comment specifications by extension.
 pub static COMMENT_REGISTRY: Lazy<HashMap<&'static str, CommentSpec>> = Lazy::new(|| {
     let mut m = HashMap::new();
 
     let c_style = CommentSpec {
-        single: Some("//"),
-        multi: Some(("/*", "*/")),
+        single: &["//"],
+        multi: &[("/*", "*/")],
+        nested: false,
+        strings: &[DOUBLE, StringSpec::Char],
+        // Doxygen, rustdoc, Javadoc, JSDoc, KDoc, Swift and C# XML docs
+        doc_single: &["///", "//!"],
+        doc_multi: &[("/**", "*/"), ("/*!", "*/")],
+        doc_before: &[],
+        docstrings: false,
+    };
+    let rust_style = CommentSpec {
+        nested: true,
+        strings: &[StringSpec::RawHash, DOUBLE_MULTILINE, StringSpec::Char],
+        ..c_style
+    };
+    let go_style = CommentSpec {
+        strings: &[BACKTICK_RAW, DOUBLE, StringSpec::Char],
+        doc_single: &[],
+        doc_multi: &[],
+        doc_before: &["func ", "type ", "var ", "const ", "package "],
+        ..c_style
+    };
+    let js_style = CommentSpec {
+        strings: &[TEMPLATE, DOUBLE, SINGLE],
+        ..c_style
+    };
+    // Java text blocks and Swift multi-line strings keep escapes; Kotlin and Scala raw strings don't
+    let java_style = CommentSpec {
+        strings: &[TRIPLE_DOUBLE, DOUBLE, StringSpec::Char],
+        ..c_style
+    };
+    let kotlin_style = CommentSpec {
+        nested: true,
+        strings: &[TRIPLE_DOUBLE_RAW, DOUBLE, StringSpec::Char],
+        ..c_style
+    };
+    let swift_style = CommentSpec {
+        nested: true,
+        strings: &[TRIPLE_DOUBLE, DOUBLE],
+        ..c_style
+    };
+    let dart_style = CommentSpec {
+        nested: true,
+        strings: DART_STRINGS,
+        ..c_style
+    };
+    let csharp_style = CommentSpec {
+        strings: &[VERBATIM, TRIPLE_DOUBLE_RAW, DOUBLE, StringSpec::Char],
+        ..c_style
+    };
+    let php_style = CommentSpec {
+        strings: &[StringSpec::Heredoc, DOUBLE_MULTILINE, SINGLE_MULTILINE],
+        ..c_style
+    };
+    let zig_style = CommentSpec {
+        strings: &[ZIG_LINE, DOUBLE, StringSpec::Char],
+        ..c_style
     };
     let bash_style = CommentSpec {
-        single: Some("#"),
-        multi: None,
+        single: &["#"],
+        multi: &[],
+        nested: false,
+        strings: &[DOUBLE, SINGLE_RAW],
+        doc_single: &[],
+        doc_multi: &[],
+        doc_before: &[],
+        docstrings: false,
+    };
+    let shell_style = CommentSpec {
+        strings: &[StringSpec::Heredoc, DOUBLE_MULTILINE, SINGLE_RAW_MULTILINE],
+        ..bash_style
+    };
+    let toml_style = CommentSpec {
+        strings: &[TRIPLE_DOUBLE, TRIPLE_SINGLE_RAW, DOUBLE, SINGLE_RAW],
+        ..bash_style
+    };
+    let elixir_style = CommentSpec {
+        strings: &[
+            TRIPLE_DOUBLE,
+            TRIPLE_SINGLE,
+            DOUBLE_MULTILINE,
+            SINGLE_MULTILINE,
+        ],
+        ..bash_style
+    };
+    let nim_style = CommentSpec {
+        multi: &[("#[", "]#")],
+        nested: true,
+        strings: &[TRIPLE_DOUBLE_RAW, DOUBLE, StringSpec::Char],
+        doc_single: &["##"],
+        doc_multi: &[("##[", "]##")],
+        ..bash_style
     };
     let py_style = CommentSpec {
-        single: Some("#"),
-        multi: Some(("\"\"\"", "\"\"\"")), // Triple quotes are often used as docstrings but act like block comments
+        single: &["#"],
+        multi: &[],
+        nested: false,
+        strings: &[TRIPLE_DOUBLE, TRIPLE_SINGLE, DOUBLE, SINGLE],
+        doc_single: &[],
+        doc_multi: &[],
+        doc_before: &[],
+        // Triple-quoted docstrings are documentation; other triple-quoted strings are code
+        docstrings: true,
     };
     let html_style = CommentSpec {
-        single: None,
-        multi: Some(("<!--", "-->")),
+        single: &[],
+        multi: &[("<!--", "-->")],
+        nested: false,
+        strings: &[],
+        ..bash_style
+    };
+    let css_style = CommentSpec {
+        single: &[],
+        multi: &[("/*", "*/")],
+        nested: false,
+        strings: &[DOUBLE, SINGLE],
+        ..bash_style
     };
+    // Sass and Less add line comments
+    let scss_style = CommentSpec {
+        single: &["//"],
+        ..css_style
+    };
+    // Quotes are doubled rather than escaped
     let sql_style = CommentSpec {
-        single: Some("--"),
-        multi: Some(("/*", "*/")),
+        single: &["--"],
+        multi: &[("/*", "*/")],
+        nested: false,
+        strings: &[SINGLE_RAW_MULTILINE, DOUBLE_RAW],
+        ..bash_style
     };
     let lua_style = CommentSpec {
-        single: Some("--"),
-        multi: Some(("--[[", "]]")),
+        single: &["--"],
+        multi: &[("--[[", "]]"), ("--[=[", "]=]"), ("--[==[", "]==]")],
+        nested: false,
+        strings: &[LUA_LONG, DOUBLE, SINGLE],
+        // LDoc
+        doc_single: &["---"],
+        ..bash_style
     };
     let haskell_style = CommentSpec {
-        single: Some("--"),
-        multi: Some(("{-", "-}")),
+        single: &["--"],
+        multi: &[("{-", "-}")],
+        nested: true,
+        strings: &[DOUBLE],
+        // Haddock
+        doc_single: &["-- |", "-- ^"],
+        doc_multi: &[("{-|", "-}")],
+        ..bash_style
     };
     let ruby_style = CommentSpec {
-        single: Some("#"),
-        multi: Some(("=begin", "=end")),
+        single: &["#"],
+        multi: &[("=begin", "=end")],
+        nested: false,
+        strings: &[StringSpec::Heredoc, DOUBLE_MULTILINE, SINGLE_MULTILINE],
+        ..bash_style
     };
 
     // Mapping
     let mappings = [
         (
-            vec![
-                ".rs", ".go", ".java", ".kt", ".kts", ".swift", ".c", ".h", ".cpp", ".cc", ".cxx",
-                ".hpp", ".hxx", ".h++", ".cs", ".js", ".mjs", ".cjs", ".ts", ".tsx", ".mts",
-                ".php", ".scala", ".sc", ".zig",
-            ],
+            vec![".c", ".h", ".cpp", ".cc", ".cxx", ".hpp", ".hxx", ".h++"],
             c_style,
         ),
+        (vec![".rs"], rust_style),
+        (vec![".go"], go_style),
+        (vec![".js", ".mjs", ".cjs", ".ts", ".tsx", ".mts"], js_style),
+        (vec![".java"], java_style),
+        (vec![".kt", ".kts", ".scala", ".sc"], kotlin_style),
+        (vec![".swift"], swift_style),
+        (vec![".dart"], dart_style),
+        (vec![".cs"], csharp_style),
+        (vec![".php"], php_style),
+        (vec![".zig"], zig_style),
         (vec![".py", ".pyw", ".pyi"], py_style),
-        (
-            vec![
-                ".sh", ".bash", ".zsh", ".fish", ".rb", ".rake", ".gemspec", ".yaml", ".yml",
-                ".toml", ".ex", ".exs", ".nim", ".nims",
-            ],
-            bash_style,
-        ),
+        (vec![".sh", ".bash", ".zsh", ".fish"], shell_style),
+        (vec![".yaml", ".yml"], bash_style),
+        (vec![".toml"], toml_style),
+        (vec![".ex", ".exs"], elixir_style),
+        (vec![".nim", ".nims"], nim_style),
+        (vec![".rb", ".rake", ".gemspec"], ruby_style),
         (
             vec![".html", ".htm", ".xml", ".xsl", ".xslt", ".vue", ".svelte"],
             html_style,
         ),
+        (vec![".css"], css_style),
+        (vec![".scss", ".sass", ".less"], scss_style),
         (vec![".sql"], sql_style),
         (vec![".lua"], lua_style),
         (vec![".hs", ".lhs"], haskell_style),
-        (vec![".rb"], ruby_style), // Overwrite for block comments
     ];
 
     for (exts, spec) in mappings {
@@ -245,21 +463,21 @@ mod tests {
     fn test_resolve_extensions_aliases() {
         assert_eq!(
             resolve_extensions("py"),
-            vec![".py", ".pyw", ".pyi"]
+            [".py", ".pyw", ".pyi"]
                 .iter()
                 .map(|s| s.to_string())
                 .collect::<Vec<_>>()
         );
         assert_eq!(
             resolve_extensions("javascript"),
-            vec![".js", ".mjs", ".cjs"]
+            [".js", ".mjs", ".cjs"]
                 .iter()
                 .map(|s| s.to_string())
                 .collect::<Vec<_>>()
         );
         assert_eq!(
             resolve_extensions("js"),
-            vec![".js", ".mjs", ".cjs"]
+            [".js", ".mjs", ".cjs"]
                 .iter()
                 .map(|s| s.to_string())
                 .collect::<Vec<_>>()
@@ -271,7 +489,7 @@ mod tests {
         assert_eq!(resolve_extensions("RUST"), vec![".rs".to_string()]);
         assert_eq!(
             resolve_extensions("Py"),
-            vec![".py", ".pyw", ".pyi"]
+            [".py", ".pyw", ".pyi"]
                 .iter()
                 .map(|s| s.to_string())
                 .collect::<Vec<_>>()
@@ -0,0 +1,42 @@
+// Author: kelexine (https://github.com/kelexine)
+// lib.rs — Library API for embedding loc-rs in other tools
+
+//! # loc-rs
+//!
+//! Library half of the `loc` binary. Everything the CLI does is available
+//! without going through clap:
+//!
+//! ```no_run
+//! use loc_rs::{ScanConfig, run_scan};
+//!
+//! let config = ScanConfig::new("src")?
+//!     .languages(["rust"])
+//!     .extract_functions(true);
+//! let result = run_scan(&config)?;
+//! println!("{} lines in {} files", result.total_lines(), result.text_file_count());
+//!
+//! let mut out = Vec::new();
+//! loc_rs::export::write_json(&result, &mut out, true)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod blame;
+pub mod cache;
+pub mod cli;
+pub mod compare;
+pub mod config;
+pub mod counter;
+pub mod display;
+pub mod export;
+pub mod extractors;
+pub mod git;
+pub mod history;
+pub mod hotspots;
+pub mod language;
+pub mod models;
+pub mod thresholds;
+
+pub use counter::{ScanConfig, run_scan};
+pub use export::ExportFormat;
+pub use extractors::{Extractor, HeaderLanguage, get_extractor};
+pub use models::{Breakdown, ExtensionStats, FileInfo, FunctionInfo, ScanResult};
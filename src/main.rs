@@ -15,17 +15,9 @@
 //   • walkdir traversal (faster than os.walk)
 //   • Typed errors via anyhow — no silent panics
 
-mod cli;
-mod config;
-mod counter;
-mod display;
-mod export;
-mod extractors;
-mod language;
-mod models;
-
 use clap::Parser;
 use colored::Colorize;
+use loc_rs::{blame, cli, compare, counter, display, export, history, hotspots, thresholds};
 use std::process;
 
 fn main() {
@@ -43,6 +35,18 @@ fn main() {
             process::exit(1);
         }
     };
+    for lang in &config.unknown_languages {
+        eprintln!(
+            "{} Unknown language filter: {}",
+            "[WARNING]".yellow().bold(),
+            lang
+        );
+    }
+
+    if args.history {
+        run_history(&args, &config);
+        return;
+    }
 
     let result = match counter::run_scan(&config) {
         Ok(r) => r,
@@ -67,9 +71,128 @@ fn main() {
         display::display_function_analysis(&result, &config.target_dir);
     }
 
+    // Optional baseline comparison
+    let diff = args.compare.as_ref().map(|baseline_file| {
+        let baseline_path = std::path::Path::new(baseline_file);
+        let baseline = match compare::load_baseline(baseline_path) {
+            Ok(b) => b,
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red().bold(), e);
+                process::exit(1);
+            }
+        };
+        if baseline.function_extraction_enabled != config.extract_functions {
+            eprintln!(
+                "{} Baseline and current scan differ in function extraction (-f); function and complexity deltas are not comparable",
+                "[WARNING]".yellow().bold()
+            );
+        }
+        compare::compare(&baseline, baseline_path, &result)
+    });
+
+    if let Some(ref diff) = diff {
+        display::display_comparison(diff);
+    }
+
+    // Optional churn × complexity ranking
+    let hotspots = args.hotspots.map(|n| {
+        let churn = match hotspots::collect_churn(&config) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red().bold(), e);
+                process::exit(1);
+            }
+        };
+        hotspots::rank(&result, &churn, n)
+    });
+
+    if let Some(ref hotspots) = hotspots {
+        display::display_hotspots(hotspots, &config.target_dir);
+    }
+
+    // Optional per-author ownership
+    let blame_report = if args.blame {
+        match blame::run_blame(&result, &config) {
+            Ok(r) => Some(r),
+            Err(e) => {
+                eprintln!("{} {}", "[ERROR]".red().bold(), e);
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    if let Some(ref report) = blame_report {
+        display::display_blame(report, &config.target_dir);
+    }
+
+    // Quality gates
+    let violations = if config.thresholds.is_empty() {
+        None
+    } else {
+        let v = thresholds::check(&result, &config.thresholds);
+        display::display_violations(&v, &config.target_dir);
+        Some(v)
+    };
+
+    let sections = export::ReportSections {
+        comparison: diff.as_ref(),
+        violations: violations.as_deref(),
+        hotspots: hotspots.as_deref(),
+        blame: blame_report.as_ref(),
+        thresholds: Some(&config.thresholds),
+        warn_size: config.warn_size,
+    };
+
     // Optional export
     if let Some(ref output_file) = args.export
-        && let Err(e) = export::export(&result, output_file, config.extract_functions)
+        && let Err(e) = export::export(&result, output_file, config.extract_functions, &sections)
+    {
+        eprintln!("{} {}", "[ERROR]".red().bold(), e);
+        process::exit(1);
+    }
+
+    if violations.is_some_and(|v| !v.is_empty()) {
+        process::exit(thresholds::EXIT_GATE_FAILED);
+    }
+}
+
+/// `--history`: measure sampled commits instead of the working tree.
+fn run_history(args: &cli::Args, config: &counter::ScanConfig) {
+    let interval = match args
+        .history_interval
+        .as_deref()
+        .map(history::parse_interval)
+    {
+        Some(Ok(d)) => Some(d),
+        Some(Err(e)) => {
+            eprintln!("{} {}", "[ERROR]".red().bold(), e);
+            process::exit(1);
+        }
+        None => None,
+    };
+    let mut options = history::HistoryOptions {
+        every: args.history_every,
+        interval,
+        ..Default::default()
+    };
+    if let Some(ref rev) = args.rev {
+        options.rev = rev.clone();
+    }
+
+    let history = match history::run_history(config, &options) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("{} {}", "[ERROR]".red().bold(), e);
+            process::exit(1);
+        }
+    };
+
+    display::display_history(&history);
+
+    if let Some(ref output_file) = args.export
+        && let Err(e) = export::history::export_history(&history, output_file)
     {
         eprintln!("{} {}", "[ERROR]".red().bold(), e);
         process::exit(1);
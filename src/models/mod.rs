@@ -10,6 +10,12 @@ use std::path::PathBuf;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionInfo {
     pub name: String,
+    /// Enclosing module, type or class, e.g. `ScanConfig` or `pkg.(*T)`
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// `name` prefixed by its parent scope, e.g. `ScanConfig::from_args`
+    #[serde(default)]
+    pub qualified_name: String,
     pub line_start: usize,
     pub line_end: usize,
     pub parameters: Vec<String>,
@@ -18,8 +24,17 @@ pub struct FunctionInfo {
     pub is_class: bool,
     pub docstring: Option<String>,
     pub decorators: Vec<String>,
-    /// Cyclomatic complexity (simplified branch-count heuristic)
+    /// Cyclomatic complexity: one plus each branch, loop, case, handler and `&&`/`||`
     pub complexity: u32,
+    /// Cognitive complexity (SonarSource): structures score one plus their
+    /// nesting depth, with extra points for `else`, mixed `&&`/`||` and recursion
+    #[serde(default)]
+    pub cognitive_complexity: u32,
+    #[serde(default)]
+    pub halstead: Halstead,
+    /// Deepest nesting of control structures and closures in the body
+    #[serde(default)]
+    pub max_nesting: u32,
 }
 
 impl FunctionInfo {
@@ -28,6 +43,25 @@ impl FunctionInfo {
         self.line_end.saturating_sub(self.line_start) + 1
     }
 
+    /// The qualified name, or the bare name when none was recorded.
+    pub fn full_name(&self) -> &str {
+        if self.qualified_name.is_empty() {
+            &self.name
+        } else {
+            &self.qualified_name
+        }
+    }
+
+    /// Maintainability Index on the 0–100 scale used by Visual Studio:
+    /// `171 - 5.2 ln(volume) - 0.23 complexity - 16.2 ln(lines)`, rescaled and
+    /// clamped at 0. Below 10 is hard to maintain, 20 and up is fine.
+    pub fn maintainability_index(&self) -> f64 {
+        let volume = self.halstead.volume().max(1.0);
+        let lines = self.line_count() as f64;
+        let mi = 171.0 - 5.2 * volume.ln() - 0.23 * self.complexity as f64 - 16.2 * lines.ln();
+        (mi * 100.0 / 171.0).clamp(0.0, 100.0)
+    }
+
     /// Truncate docstring to 100 chars for export compactness.
     pub fn truncated_docstring(&self) -> Option<String> {
         self.docstring.as_ref().map(|d| {
@@ -41,18 +75,74 @@ impl FunctionInfo {
     }
 }
 
+/// Halstead token counts of a function: operators are keywords, punctuation
+/// and operator tokens, operands are identifiers and literals.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Halstead {
+    /// Distinct operators (n1).
+    pub distinct_operators: u32,
+    /// Distinct operands (n2).
+    pub distinct_operands: u32,
+    /// Total operators (N1).
+    pub operators: u32,
+    /// Total operands (N2).
+    pub operands: u32,
+}
+
+impl Halstead {
+    /// Program volume: `N * log2(n)`.
+    pub fn volume(&self) -> f64 {
+        let vocabulary = self.distinct_operators + self.distinct_operands;
+        if vocabulary == 0 {
+            return 0.0;
+        }
+        (self.operators + self.operands) as f64 * (vocabulary as f64).log2()
+    }
+
+    /// Difficulty: `n1 / 2 * N2 / n2`.
+    pub fn difficulty(&self) -> f64 {
+        if self.distinct_operands == 0 {
+            return 0.0;
+        }
+        self.distinct_operators as f64 / 2.0 * self.operands as f64 / self.distinct_operands as f64
+    }
+
+    /// Effort: difficulty times volume.
+    pub fn effort(&self) -> f64 {
+        self.difficulty() * self.volume()
+    }
+}
+
 /// Aggregated information about a single source file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileInfo {
     pub path: PathBuf,
     pub lines: usize,
+    // Older JSON exports carried only `lines`; default the split so they still load.
+    #[serde(default)]
     pub code: usize,
+    #[serde(default)]
     pub comment: usize,
+    #[serde(default)]
     pub blank: usize,
+    /// Code lines that also carry a comment; included in `code`.
+    #[serde(default)]
+    pub mixed: usize,
+    /// Documentation comment lines; included in `comment`.
+    #[serde(default)]
+    pub doc: usize,
+    #[serde(default)]
     pub is_binary: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_modified: Option<DateTime<Utc>>,
+    #[serde(default)]
     pub functions: Vec<FunctionInfo>,
+    /// Regions in other languages (a `.vue` file's `<script>` and `<style>`,
+    /// Markdown code fences), keyed like `Breakdown`. Their lines and
+    /// functions are included in the totals above.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub embedded: HashMap<String, ExtensionStats>,
 }
 
 impl FileInfo {
@@ -71,17 +161,35 @@ impl FileInfo {
             code,
             comment,
             blank,
+            mixed: 0,
+            doc: 0,
             is_binary,
             last_modified,
             functions: Vec::new(),
+            embedded: HashMap::new(),
         }
     }
 
+    pub fn with_mixed(mut self, mixed: usize) -> Self {
+        self.mixed = mixed;
+        self
+    }
+
+    pub fn with_doc(mut self, doc: usize) -> Self {
+        self.doc = doc;
+        self
+    }
+
     pub fn with_functions(mut self, functions: Vec<FunctionInfo>) -> Self {
         self.functions = functions;
         self
     }
 
+    pub fn with_embedded(mut self, embedded: HashMap<String, ExtensionStats>) -> Self {
+        self.embedded = embedded;
+        self
+    }
+
     #[inline]
     pub fn function_count(&self) -> usize {
         self.functions.len()
@@ -101,29 +209,159 @@ impl FileInfo {
         total as f64 / non_class.len() as f64
     }
 
+    /// Non-class functions that carry a docstring or doc comment.
+    pub fn documented_function_count(&self) -> usize {
+        self.functions
+            .iter()
+            .filter(|f| !f.is_class && f.docstring.is_some())
+            .count()
+    }
+
+    /// Percentage of non-class functions that are documented (0 without any).
+    pub fn documented_percent(&self) -> f64 {
+        let non_class = self.functions.iter().filter(|f| !f.is_class).count();
+        percent(self.documented_function_count(), non_class)
+    }
+
     /// File extension without the leading dot, or empty string.
     pub fn extension(&self) -> &str {
         self.path.extension().and_then(|e| e.to_str()).unwrap_or("")
     }
+
+    /// Key this file is grouped under in the `Breakdown`: its extension, or
+    /// the file name for extension-less files like `Makefile`.
+    pub fn breakdown_key(&self) -> String {
+        if self.extension().is_empty() {
+            self.path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("(no ext)")
+                .to_string()
+        } else {
+            self.extension().to_string()
+        }
+    }
+
+    /// What this file adds to the `Breakdown`: an entry under its own key,
+    /// and one per embedded language with those lines and functions moved
+    /// out of it. Only the file's own entry counts it as a file.
+    pub fn breakdown_stats(&self) -> Vec<(String, ExtensionStats)> {
+        let mut own = ExtensionStats {
+            lines: self.lines,
+            code: self.code,
+            comment: self.comment,
+            blank: self.blank,
+            mixed: self.mixed,
+            doc: self.doc,
+            files: 1,
+            functions: self.function_count(),
+        };
+        for region in self.embedded.values() {
+            own.lines = own.lines.saturating_sub(region.lines);
+            own.code = own.code.saturating_sub(region.code);
+            own.comment = own.comment.saturating_sub(region.comment);
+            own.blank = own.blank.saturating_sub(region.blank);
+            own.mixed = own.mixed.saturating_sub(region.mixed);
+            own.doc = own.doc.saturating_sub(region.doc);
+            own.functions = own.functions.saturating_sub(region.functions);
+        }
+
+        let mut stats = vec![(self.breakdown_key(), own)];
+        stats.extend(self.embedded.iter().map(|(k, s)| (k.clone(), s.clone())));
+        stats
+    }
+
+    /// Sum of cyclomatic complexity over all non-class functions.
+    pub fn total_complexity(&self) -> u32 {
+        self.functions
+            .iter()
+            .filter(|f| !f.is_class)
+            .map(|f| f.complexity)
+            .sum()
+    }
+
+    /// Summed Halstead volume of all non-class functions.
+    pub fn halstead_volume(&self) -> f64 {
+        self.functions
+            .iter()
+            .filter(|f| !f.is_class)
+            .map(|f| f.halstead.volume())
+            .sum()
+    }
+
+    /// Summed Halstead effort of all non-class functions.
+    pub fn halstead_effort(&self) -> f64 {
+        self.functions
+            .iter()
+            .filter(|f| !f.is_class)
+            .map(|f| f.halstead.effort())
+            .sum()
+    }
+
+    /// Maintainability Index of the file: the functions' indices weighted by
+    /// their length. `None` without functions.
+    pub fn maintainability_index(&self) -> Option<f64> {
+        weighted_maintainability(self.functions.iter())
+    }
+
+    /// Highest cyclomatic complexity of any non-class function (0 without any).
+    pub fn max_complexity(&self) -> u32 {
+        self.functions
+            .iter()
+            .filter(|f| !f.is_class)
+            .map(|f| f.complexity)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Highest cognitive complexity of any non-class function (0 without any).
+    pub fn max_cognitive_complexity(&self) -> u32 {
+        self.functions
+            .iter()
+            .filter(|f| !f.is_class)
+            .map(|f| f.cognitive_complexity)
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 /// Per-extension aggregated statistics.
-#[derive(Debug, Default, Clone, Serialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ExtensionStats {
     pub lines: usize,
     pub code: usize,
     pub comment: usize,
     pub blank: usize,
+    /// Code lines that also carry a comment; included in `code`.
+    pub mixed: usize,
+    /// Documentation comment lines; included in `comment`.
+    pub doc: usize,
     pub files: usize,
     pub functions: usize,
 }
 
+impl ExtensionStats {
+    pub fn add(&mut self, other: &ExtensionStats) {
+        self.lines += other.lines;
+        self.code += other.code;
+        self.comment += other.comment;
+        self.blank += other.blank;
+        self.mixed += other.mixed;
+        self.doc += other.doc;
+        self.files += other.files;
+        self.functions += other.functions;
+    }
+}
+
 /// Breakdown map: extension → stats.
 pub type Breakdown = HashMap<String, ExtensionStats>;
 
 /// The full scan result returned from the counter.
 #[derive(Debug)]
 pub struct ScanResult {
+    /// Directory the scan was rooted at; file paths live underneath it.
+    pub root: PathBuf,
     pub files: Vec<FileInfo>,
     pub breakdown: Breakdown,
 }
@@ -153,6 +391,14 @@ impl ScanResult {
             .sum()
     }
 
+    pub fn total_doc(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| !f.is_binary)
+            .map(|f| f.doc)
+            .sum()
+    }
+
     pub fn total_blank(&self) -> usize {
         self.files
             .iter()
@@ -176,4 +422,46 @@ impl ScanResult {
     pub fn total_classes(&self) -> usize {
         self.files.iter().map(|f| f.class_count()).sum()
     }
+
+    /// Percentage of all non-class functions that are documented.
+    pub fn documented_percent(&self) -> f64 {
+        let non_class = self
+            .files
+            .iter()
+            .flat_map(|f| &f.functions)
+            .filter(|f| !f.is_class)
+            .count();
+        let documented = self
+            .files
+            .iter()
+            .map(|f| f.documented_function_count())
+            .sum();
+        percent(documented, non_class)
+    }
+
+    /// Length-weighted Maintainability Index over every non-class function.
+    pub fn maintainability_index(&self) -> Option<f64> {
+        weighted_maintainability(self.files.iter().flat_map(|f| &f.functions))
+    }
+}
+
+fn weighted_maintainability<'a>(functions: impl Iterator<Item = &'a FunctionInfo>) -> Option<f64> {
+    let (weighted, lines) =
+        functions
+            .filter(|f| !f.is_class)
+            .fold((0.0, 0usize), |(sum, lines), f| {
+                (
+                    sum + f.maintainability_index() * f.line_count() as f64,
+                    lines + f.line_count(),
+                )
+            });
+    (lines > 0).then(|| weighted / lines as f64)
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
 }
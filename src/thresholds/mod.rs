@@ -0,0 +1,350 @@
+// Author: kelexine (https://github.com/kelexine)
+// thresholds/mod.rs — Quality gates evaluated against a finished scan
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::ScanResult;
+
+/// Process exit code used when at least one gate fails (errors exit with 1).
+pub const EXIT_GATE_FAILED: i32 = 2;
+
+/// Limits checked after a scan. Unset limits are not enforced.
+///
+/// Loaded from the `[thresholds]` table of `config.toml` and overridden per run by CLI flags.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Thresholds {
+    /// Maximum total lines in a single file.
+    pub max_file_lines: Option<usize>,
+    /// Maximum lines in a single function or method.
+    pub max_function_lines: Option<usize>,
+    /// Maximum cyclomatic complexity of a single function or method.
+    pub max_complexity: Option<u32>,
+    /// Maximum cognitive complexity of a single function or method.
+    pub max_cognitive_complexity: Option<u32>,
+    /// Minimum `comment / (code + comment)` per file, between 0.0 and 1.0.
+    /// Only checked for languages with known comment syntax.
+    pub min_comment_ratio: Option<f64>,
+    /// Maximum code lines in one directory, counting every file beneath it.
+    pub max_dir_lines: Option<usize>,
+}
+
+impl Thresholds {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether any limit needs `FileInfo::functions` to be populated.
+    pub fn needs_functions(&self) -> bool {
+        self.max_function_lines.is_some()
+            || self.max_complexity.is_some()
+            || self.max_cognitive_complexity.is_some()
+    }
+
+    /// Fill every limit unset in `self` from `fallback`.
+    pub fn or(self, fallback: &Thresholds) -> Self {
+        Self {
+            max_file_lines: self.max_file_lines.or(fallback.max_file_lines),
+            max_function_lines: self.max_function_lines.or(fallback.max_function_lines),
+            max_complexity: self.max_complexity.or(fallback.max_complexity),
+            max_cognitive_complexity: self
+                .max_cognitive_complexity
+                .or(fallback.max_cognitive_complexity),
+            min_comment_ratio: self.min_comment_ratio.or(fallback.min_comment_ratio),
+            max_dir_lines: self.max_dir_lines.or(fallback.max_dir_lines),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    MaxFileLines,
+    MaxFunctionLines,
+    MaxComplexity,
+    MaxCognitiveComplexity,
+    MinCommentRatio,
+    MaxDirLines,
+}
+
+impl Rule {
+    /// Stable identifier, matching the `config.toml` key with dashes.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::MaxFileLines => "max-file-lines",
+            Rule::MaxFunctionLines => "max-function-lines",
+            Rule::MaxComplexity => "max-complexity",
+            Rule::MaxCognitiveComplexity => "max-cognitive-complexity",
+            Rule::MinCommentRatio => "min-comment-ratio",
+            Rule::MaxDirLines => "max-dir-lines",
+        }
+    }
+}
+
+/// A single failed gate.
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    pub rule: Rule,
+    /// File or directory the violation applies to.
+    pub path: PathBuf,
+    /// First line of the offending function, when the rule is function-level.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_end: Option<usize>,
+    /// Offending function name, when the rule is function-level.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    pub actual: f64,
+    pub limit: f64,
+    pub message: String,
+}
+
+/// Evaluate every configured limit, returning violations ordered by path then line.
+pub fn check(result: &ScanResult, thresholds: &Thresholds) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for fi in result.files.iter().filter(|f| !f.is_binary) {
+        if let Some(max) = thresholds.max_file_lines
+            && fi.lines > max
+        {
+            violations.push(Violation {
+                rule: Rule::MaxFileLines,
+                path: fi.path.clone(),
+                line: None,
+                line_end: None,
+                function: None,
+                actual: fi.lines as f64,
+                limit: max as f64,
+                message: format!("file has {} lines (max {})", fi.lines, max),
+            });
+        }
+
+        if let Some(min) = thresholds.min_comment_ratio
+            && has_comment_syntax(&fi.path)
+            && fi.code + fi.comment > 0
+        {
+            let ratio = fi.comment as f64 / (fi.code + fi.comment) as f64;
+            if ratio < min {
+                violations.push(Violation {
+                    rule: Rule::MinCommentRatio,
+                    path: fi.path.clone(),
+                    line: None,
+                    line_end: None,
+                    function: None,
+                    actual: ratio,
+                    limit: min,
+                    message: format!(
+                        "comment ratio {:.1}% is below {:.1}%",
+                        ratio * 100.0,
+                        min * 100.0
+                    ),
+                });
+            }
+        }
+
+        for func in fi.functions.iter().filter(|f| !f.is_class) {
+            if let Some(max) = thresholds.max_function_lines
+                && func.line_count() > max
+            {
+                violations.push(Violation {
+                    rule: Rule::MaxFunctionLines,
+                    path: fi.path.clone(),
+                    line: Some(func.line_start),
+                    line_end: Some(func.line_end),
+                    function: Some(func.full_name().to_string()),
+                    actual: func.line_count() as f64,
+                    limit: max as f64,
+                    message: format!(
+                        "function `{}` has {} lines (max {})",
+                        func.full_name(),
+                        func.line_count(),
+                        max
+                    ),
+                });
+            }
+
+            if let Some(max) = thresholds.max_complexity
+                && func.complexity > max
+            {
+                violations.push(Violation {
+                    rule: Rule::MaxComplexity,
+                    path: fi.path.clone(),
+                    line: Some(func.line_start),
+                    line_end: Some(func.line_end),
+                    function: Some(func.full_name().to_string()),
+                    actual: func.complexity as f64,
+                    limit: max as f64,
+                    message: format!(
+                        "function `{}` has cyclomatic complexity {} (max {})",
+                        func.full_name(),
+                        func.complexity,
+                        max
+                    ),
+                });
+            }
+
+            if let Some(max) = thresholds.max_cognitive_complexity
+                && func.cognitive_complexity > max
+            {
+                violations.push(Violation {
+                    rule: Rule::MaxCognitiveComplexity,
+                    path: fi.path.clone(),
+                    line: Some(func.line_start),
+                    line_end: Some(func.line_end),
+                    function: Some(func.full_name().to_string()),
+                    actual: func.cognitive_complexity as f64,
+                    limit: max as f64,
+                    message: format!(
+                        "function `{}` has cognitive complexity {} (max {})",
+                        func.full_name(),
+                        func.cognitive_complexity,
+                        max
+                    ),
+                });
+            }
+        }
+    }
+
+    if let Some(max) = thresholds.max_dir_lines {
+        // Each file's code counts towards every directory between it and the scan root
+        let mut per_dir: BTreeMap<&Path, usize> = BTreeMap::new();
+        for fi in result.files.iter().filter(|f| !f.is_binary) {
+            for dir in fi.path.ancestors().skip(1) {
+                if !dir.starts_with(&result.root) {
+                    break;
+                }
+                *per_dir.entry(dir).or_default() += fi.code;
+            }
+        }
+        for (dir, lines) in per_dir {
+            if lines > max {
+                violations.push(Violation {
+                    rule: Rule::MaxDirLines,
+                    path: dir.to_path_buf(),
+                    line: None,
+                    line_end: None,
+                    function: None,
+                    actual: lines as f64,
+                    limit: max as f64,
+                    message: format!("directory has {} code lines (max {})", lines, max),
+                });
+            }
+        }
+    }
+
+    violations.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+    violations
+}
+
+fn has_comment_syntax(path: &Path) -> bool {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| format!(".{}", e.to_lowercase()))
+        .unwrap_or_default();
+    crate::language::COMMENT_REGISTRY.contains_key(ext.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Breakdown, FileInfo, FunctionInfo};
+
+    fn func(name: &str, start: usize, end: usize, complexity: u32) -> FunctionInfo {
+        FunctionInfo {
+            name: name.to_string(),
+            parent: None,
+            qualified_name: String::new(),
+            line_start: start,
+            line_end: end,
+            parameters: vec![],
+            is_async: false,
+            is_method: false,
+            is_class: false,
+            docstring: None,
+            decorators: vec![],
+            complexity,
+            cognitive_complexity: complexity,
+            halstead: Default::default(),
+            max_nesting: 0,
+        }
+    }
+
+    fn sample() -> ScanResult {
+        let big = FileInfo::new(PathBuf::from("/r/src/big.rs"), 120, 110, 2, 8, false, None)
+            .with_functions(vec![func("long", 10, 70, 3), func("tangled", 80, 90, 14)]);
+        let small = FileInfo::new(PathBuf::from("/r/src/small.rs"), 20, 10, 5, 5, false, None);
+        let data = FileInfo::new(PathBuf::from("/r/data.json"), 50, 50, 0, 0, false, None);
+        ScanResult {
+            root: PathBuf::from("/r"),
+            files: vec![big, small, data],
+            breakdown: Breakdown::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_reports_each_rule() {
+        let thresholds = Thresholds {
+            max_file_lines: Some(100),
+            max_function_lines: Some(50),
+            max_complexity: Some(10),
+            max_cognitive_complexity: Some(12),
+            min_comment_ratio: Some(0.1),
+            max_dir_lines: Some(130),
+        };
+        let violations = check(&sample(), &thresholds);
+        let rules: Vec<_> = violations.iter().map(|v| v.rule).collect();
+
+        assert_eq!(violations.len(), 6, "{:#?}", violations);
+        assert!(rules.contains(&Rule::MaxFileLines));
+        assert!(rules.contains(&Rule::MaxCognitiveComplexity));
+        assert!(rules.contains(&Rule::MinCommentRatio));
+        assert!(rules.contains(&Rule::MaxDirLines));
+
+        // src/ holds 120 code lines; only the root, with data.json's 50 on top, is over
+        let dir = violations
+            .iter()
+            .find(|v| v.rule == Rule::MaxDirLines)
+            .unwrap();
+        assert_eq!(dir.path, PathBuf::from("/r"));
+        assert_eq!(dir.actual, 170.0);
+
+        let long = violations
+            .iter()
+            .find(|v| v.rule == Rule::MaxFunctionLines)
+            .unwrap();
+        assert_eq!(long.line, Some(10));
+        assert_eq!(long.function.as_deref(), Some("long"));
+
+        let tangled = violations
+            .iter()
+            .find(|v| v.rule == Rule::MaxComplexity)
+            .unwrap();
+        assert_eq!(tangled.line, Some(80));
+
+        // data.json has no comment syntax, so it is exempt from the ratio gate
+        assert!(!violations.iter().any(|v| v.path.ends_with("data.json")));
+    }
+
+    #[test]
+    fn test_cli_values_override_config() {
+        let config = Thresholds {
+            max_file_lines: Some(500),
+            max_complexity: Some(15),
+            ..Default::default()
+        };
+        let cli = Thresholds {
+            max_complexity: Some(10),
+            ..Default::default()
+        };
+        let merged = cli.or(&config);
+        assert_eq!(merged.max_file_lines, Some(500));
+        assert_eq!(merged.max_complexity, Some(10));
+        assert!(merged.needs_functions());
+        assert!(Thresholds::default().is_empty());
+    }
+}
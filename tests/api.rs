@@ -0,0 +1,111 @@
+// tests/api.rs — Testing the library API without going through the CLI
+
+mod common;
+use common::{TempDir, make_fixture};
+use loc_rs::{ExportFormat, ScanConfig, export, run_scan};
+use std::fs;
+
+#[test]
+fn test_scan_config_builder_and_run_scan() {
+    let fixture = make_fixture(&[
+        (
+            "main.rs",
+            "// entry\nfn main() {}\n\nfn helper(a: i32) {}\n",
+        ),
+        ("script.py", "print('hello')\n"),
+    ]);
+
+    let config = ScanConfig::new(fixture.path())
+        .unwrap()
+        .languages(["rust"])
+        .extract_functions(true)
+        .parallel(false);
+    let result = run_scan(&config).unwrap();
+
+    assert_eq!(result.text_file_count(), 1);
+    assert_eq!(result.total_lines(), 4);
+    assert_eq!(result.total_comment(), 1);
+    assert_eq!(result.total_functions(), 2);
+    assert!(result.breakdown.contains_key("rs"));
+    assert!(!result.breakdown.contains_key("py"));
+}
+
+#[test]
+fn test_unknown_language_filters_are_reported_not_printed() {
+    let fixture = make_fixture(&[("main.rs", "fn main() {}\n")]);
+    let config = ScanConfig::new(fixture.path())
+        .unwrap()
+        .languages(["rs", "xyzzy"]);
+    assert_eq!(config.unknown_languages, vec!["xyzzy"]);
+}
+
+#[test]
+fn test_scan_config_rejects_missing_directory() {
+    assert!(ScanConfig::new("/tmp/this_dir_definitely_does_not_exist_loc_api_xyz").is_err());
+}
+
+#[test]
+fn test_exporters_write_to_any_writer() {
+    let fixture = make_fixture(&[(
+        "lib.rs",
+        "/// Adds.\npub fn add(a: i32, b: i32) -> i32 { a + b }\n",
+    )]);
+    let config = ScanConfig::new(fixture.path())
+        .unwrap()
+        .extract_functions(true);
+    let result = run_scan(&config).unwrap();
+
+    let mut json_buf = Vec::new();
+    export::write_to(&result, ExportFormat::Json, &mut json_buf, true).unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&json_buf).unwrap();
+    assert_eq!(parsed["metadata"]["total_functions"], 1);
+    assert_eq!(parsed["metadata"]["total_doc"], 1);
+    assert_eq!(parsed["files"][0]["comment"], 1);
+    assert_eq!(parsed["breakdown"]["rs"]["doc"], 1);
+    assert_eq!(parsed["files"][0]["functions"][0]["docstring"], "Adds.");
+    assert_eq!(parsed["metadata"]["documented_functions_pct"], 100.0);
+    let add = &parsed["files"][0]["functions"][0];
+    assert!(add["halstead"]["volume"].as_f64().unwrap() > 0.0);
+    assert_eq!(
+        parsed["files"][0]["maintainability_index"],
+        add["maintainability_index"]
+    );
+
+    let mut csv_buf = Vec::new();
+    export::write_csv(&result, &mut csv_buf, false).unwrap();
+    let csv_text = String::from_utf8(csv_buf).unwrap();
+    assert!(csv_text.starts_with("Path,Lines,Extension,Last Modified,Code,Comment,Doc,Blank"));
+    assert!(csv_text.contains("lib.rs"));
+}
+
+#[test]
+fn test_scan_cache_reuses_unchanged_files() {
+    let fixture = make_fixture(&[("a.rs", "fn a() {}\n"), ("b.rs", "// b\nfn b() {}\n")]);
+    let cache_dir = TempDir::new().unwrap();
+    let config = ScanConfig::new(fixture.path())
+        .unwrap()
+        .extract_functions(true)
+        .cache_dir(Some(cache_dir.path().to_path_buf()));
+    assert_eq!(run_scan(&config).unwrap().total_code(), 2);
+
+    // Same size and mtime: the cached counts are served without reading the file
+    let a = fixture.path().join("a.rs");
+    let mtime = fs::metadata(&a).unwrap().modified().unwrap();
+    fs::write(&a, "// a     \n").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&a)
+        .unwrap()
+        .set_modified(mtime)
+        .unwrap();
+    // A changed size invalidates the entry
+    fs::write(fixture.path().join("b.rs"), "// b\n").unwrap();
+
+    let result = run_scan(&config).unwrap();
+    assert_eq!(result.total_code(), 1);
+    assert_eq!(result.total_functions(), 1);
+
+    // Without the cache the real content is counted
+    let fresh = run_scan(&config.clone().cache_dir(None)).unwrap();
+    assert_eq!(fresh.total_code(), 0);
+}
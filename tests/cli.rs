@@ -1,7 +1,8 @@
 // tests/cli.rs — Testing CLI flags, outputs, and errors
 
 mod common;
-use common::{make_fixture, run_loc};
+use common::{commit_files, commit_files_as, git, make_fixture, run_loc};
+use std::fs;
 
 #[test]
 fn test_basic_scan_exits_zero() {
@@ -115,3 +116,258 @@ fn test_multilingual_summary() {
     assert!(stdout.contains("rs"), "Summary missing Rust");
     assert!(stdout.contains("py"), "Summary missing Python");
 }
+
+#[test]
+fn test_quality_gates_exit_code() {
+    let fixture = make_fixture(&[(
+        "lib.rs",
+        "fn tangled(a: i32) -> i32 {\n    if a > 0 && a < 10 { 1 } else if a > 10 || a < -10 { 2 } else { 3 }\n}\n",
+    )]);
+    let dir = fixture.path().to_str().unwrap();
+
+    let out = run_loc(&[dir, "--max-complexity", "2"]);
+    assert_eq!(out.status.code(), Some(2), "gate failure should exit 2");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("lib.rs:1") && stdout.contains("max-complexity"),
+        "Violation should name path, line and rule:\n{}",
+        stdout
+    );
+
+    let out = run_loc(&[dir, "--max-complexity", "50", "--max-file-lines", "100"]);
+    assert!(out.status.success(), "passing gates should exit 0");
+}
+
+#[test]
+fn test_history_time_series() {
+    let repo = make_fixture(&[]);
+    git(repo.path(), &["init", "-q"]);
+    commit_files(
+        repo.path(),
+        &[("a.rs", "fn a() {}\n")],
+        "2024-01-01T00:00:00Z",
+    );
+    commit_files(
+        repo.path(),
+        &[("b.py", "# b\ndef b():\n    pass\n")],
+        "2024-01-02T00:00:00Z",
+    );
+    commit_files(
+        repo.path(),
+        &[("a.rs", "fn a() {}\n\nfn c() {}\n")],
+        "2024-03-01T00:00:00Z",
+    );
+    // Uncommitted changes must not show up in history
+    fs::write(repo.path().join("dirty.rs"), "fn dirty() {}\n").unwrap();
+
+    let out_json = repo.path().join("history.json");
+    let out = run_loc(&[
+        repo.path().to_str().unwrap(),
+        "--history",
+        "-e",
+        out_json.to_str().unwrap(),
+    ]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&out_json).unwrap()).unwrap();
+    let points = parsed["history"].as_array().unwrap();
+    let lines: Vec<_> = points
+        .iter()
+        .map(|p| p["total"]["lines"].as_u64().unwrap())
+        .collect();
+    assert_eq!(lines, [1, 4, 6]);
+    assert_eq!(points[2]["breakdown"]["rs"]["code"], 2);
+    assert_eq!(points[2]["breakdown"]["py"]["comment"], 1);
+
+    // Monthly sampling keeps the first commit and the newest one
+    let out_csv = repo.path().join("history.csv");
+    let out = run_loc(&[
+        repo.path().to_str().unwrap(),
+        "--history",
+        "--history-interval",
+        "1m",
+        "-e",
+        out_csv.to_str().unwrap(),
+    ]);
+    assert!(out.status.success());
+    let csv = fs::read_to_string(&out_csv).unwrap();
+    let totals: Vec<_> = csv.lines().filter(|l| l.contains(",TOTAL,")).collect();
+    assert_eq!(totals.len(), 2, "{}", csv);
+    assert!(totals[1].contains("2024-03-01"));
+}
+
+#[test]
+fn test_scan_git_revision() {
+    let repo = make_fixture(&[]);
+    git(repo.path(), &["init", "-q"]);
+    commit_files(
+        repo.path(),
+        &[("src/a.rs", "// a\nfn a() {}\n")],
+        "2024-01-01T00:00:00Z",
+    );
+    git(repo.path(), &["tag", "v1"]);
+    commit_files(
+        repo.path(),
+        &[("src/b.rs", "fn b() {}\nfn c() {}\n")],
+        "2024-02-01T00:00:00Z",
+    );
+    // Working-tree edits and deletions are invisible to --rev
+    fs::write(repo.path().join("src/a.rs"), "fn changed() {}\n").unwrap();
+    fs::remove_file(repo.path().join("src/b.rs")).unwrap();
+
+    let out_json = repo.path().join("rev.json");
+    let out = run_loc(&[
+        repo.path().to_str().unwrap(),
+        "--rev",
+        "v1",
+        "-f",
+        "-e",
+        out_json.to_str().unwrap(),
+    ]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&out_json).unwrap()).unwrap();
+    let files = parsed["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(files[0]["path"].as_str().unwrap().ends_with("a.rs"));
+    assert_eq!(files[0]["comment"], 1);
+    assert_eq!(files[0]["functions"][0]["name"], "a");
+
+    let out = run_loc(&[
+        repo.path().to_str().unwrap(),
+        "--rev",
+        "HEAD",
+        "-e",
+        out_json.to_str().unwrap(),
+    ]);
+    assert!(out.status.success());
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&out_json).unwrap()).unwrap();
+    assert_eq!(parsed["metadata"]["total_lines"], 4);
+
+    let out = run_loc(&[repo.path().to_str().unwrap(), "--rev", "no-such-tag"]);
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn test_hotspots_ranking() {
+    let repo = make_fixture(&[]);
+    git(repo.path(), &["init", "-q"]);
+    let complex = "fn hot(x: i32) -> i32 {\n    if x > 0 {\n        if x > 10 {\n            return 2;\n        }\n        return 1;\n    }\n    0\n}\n";
+    commit_files(
+        repo.path(),
+        &[("hot.rs", complex), ("calm.rs", "fn calm() {}\n")],
+        "2024-01-01T00:00:00Z",
+    );
+    for i in 0..3 {
+        let edited = format!("{}// edit {}\n", complex, i);
+        commit_files(repo.path(), &[("hot.rs", &edited)], "2024-01-02T00:00:00Z");
+    }
+
+    let out_json = repo.path().join("hotspots.json");
+    // A bare --hotspots must not swallow the path that follows it
+    let out = run_loc(&[
+        "--hotspots",
+        repo.path().to_str().unwrap(),
+        "-e",
+        out_json.to_str().unwrap(),
+    ]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Hotspots"));
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&out_json).unwrap()).unwrap();
+    let hotspots = parsed["hotspots"].as_array().unwrap();
+    assert_eq!(hotspots.len(), 2);
+    assert!(hotspots[0]["path"].as_str().unwrap().ends_with("hot.rs"));
+    assert_eq!(hotspots[0]["commits"], 4);
+    assert_eq!(hotspots[0]["score"], 1.0);
+    assert!(hotspots[0]["max_complexity"].as_u64().unwrap() > 1);
+
+    let not_git = make_fixture(&[("a.rs", "fn a() {}\n")]);
+    let out = run_loc(&[not_git.path().to_str().unwrap(), "--hotspots=3"]);
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn test_blame_ownership() {
+    let repo = make_fixture(&[]);
+    git(repo.path(), &["init", "-q"]);
+    commit_files_as(
+        repo.path(),
+        &[(
+            "core/a.rs",
+            "// owned by ada\nfn a() {}\nfn b() {}\nfn c() {}\n",
+        )],
+        "2024-01-01T00:00:00Z",
+        "ada",
+    );
+    commit_files_as(
+        repo.path(),
+        &[("core/d.rs", "fn d() {}\n"), ("util/e.rs", "fn e() {}\n\n")],
+        "2024-01-02T00:00:00Z",
+        "bob",
+    );
+
+    let out_json = repo.path().join("blame.json");
+    let out = run_loc(&[
+        repo.path().to_str().unwrap(),
+        "--blame",
+        "-e",
+        out_json.to_str().unwrap(),
+    ]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Code Ownership"));
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&out_json).unwrap()).unwrap();
+    let blame = &parsed["blame"];
+    // Comments and blank lines are not attributed
+    assert_eq!(blame["total"]["lines"], 5);
+    assert_eq!(blame["total"]["authors"]["ada"], 3);
+    assert_eq!(blame["total"]["authors"]["bob"], 2);
+    assert_eq!(blame["total"]["bus_factor"], 1);
+
+    let core = repo.path().canonicalize().unwrap().join("core");
+    let core = &blame["directories"][core.to_str().unwrap()];
+    assert_eq!(core["lines"], 4);
+    assert_eq!(core["authors"].as_object().unwrap().len(), 2);
+
+    let out_csv = repo.path().join("blame.csv");
+    let out = run_loc(&[
+        repo.path().to_str().unwrap(),
+        "--blame",
+        "-e",
+        out_csv.to_str().unwrap(),
+    ]);
+    assert!(out.status.success());
+    let csv = fs::read_to_string(&out_csv).unwrap();
+    assert!(
+        csv.lines()
+            .next()
+            .unwrap()
+            .ends_with("Top Author,Top Author Share,Authors,Bus Factor")
+    );
+    assert!(
+        csv.lines()
+            .any(|l| l.contains("a.rs") && l.contains(",ada,1.0000,1,1"))
+    );
+}
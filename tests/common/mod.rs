@@ -1,7 +1,9 @@
 // tests/common/mod.rs — Shared helpers for integration tests
+// Each test crate uses a different subset of these helpers.
+#![allow(dead_code)]
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 pub use tempfile::TempDir;
 
 /// Resolve the compiled binary from the workspace target directory
@@ -38,3 +40,42 @@ pub fn make_fixture(files: &[(&str, &str)]) -> TempDir {
     }
     dir
 }
+
+/// Run git in `dir` with a fixed identity, panicking on failure.
+pub fn git(dir: &Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(["-c", "user.name=loc", "-c", "user.email=loc@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to execute git")
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// Write `files` into `dir` and commit everything with the given ISO date.
+pub fn commit_files(dir: &Path, files: &[(&str, &str)], date: &str) {
+    commit_files_as(dir, files, date, "loc");
+}
+
+/// Like [`commit_files`], authored and committed by `author`.
+pub fn commit_files_as(dir: &Path, files: &[(&str, &str)], date: &str, author: &str) {
+    for (name, content) in files {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&path, content).unwrap();
+    }
+    git(dir, &["add", "-A"]);
+    let status = std::process::Command::new("git")
+        .args(["-c", &format!("user.name={}", author)])
+        .args(["-c", &format!("user.email={}@example.com", author)])
+        .args(["commit", "-q", "-m", date])
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .current_dir(dir)
+        .status()
+        .expect("Failed to execute git");
+    assert!(status.success(), "git commit failed");
+}
@@ -87,3 +87,102 @@ fn test_export_html() {
     );
     assert!(content.contains("main.rs"), "HTML missing file data");
 }
+
+#[test]
+fn test_compare_against_baseline_export() {
+    let fixture = make_fixture(&[("main.rs", "fn main() {}\n"), ("old.py", "x = 1\ny = 2\n")]);
+    let baseline = fixture.path().join("baseline.json");
+    let out = run_loc(&[
+        fixture.path().to_str().unwrap(),
+        "-f",
+        "-e",
+        baseline.to_str().unwrap(),
+    ]);
+    assert!(out.status.success());
+
+    fs::write(
+        fixture.path().join("main.rs"),
+        "fn main() {\n    helper();\n}\n\nfn helper() {}\n",
+    )
+    .unwrap();
+    fs::remove_file(fixture.path().join("old.py")).unwrap();
+
+    let report = fixture.path().join("delta.json");
+    let out = run_loc(&[
+        fixture.path().to_str().unwrap(),
+        "-f",
+        "-t",
+        "rust",
+        "python",
+        "--compare",
+        baseline.to_str().unwrap(),
+        "-e",
+        report.to_str().unwrap(),
+    ]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Changes vs Baseline"), "{}", stdout);
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    let cmp = &parsed["comparison"];
+    assert_eq!(cmp["total_delta"]["lines"], 5 - 3);
+    assert_eq!(cmp["total_delta"]["functions"], 1);
+
+    let files = cmp["files"].as_array().unwrap();
+    assert!(
+        files
+            .iter()
+            .any(|f| f["path"] == "old.py" && f["kind"] == "removed")
+    );
+    assert!(
+        files
+            .iter()
+            .any(|f| f["path"] == "main.rs" && f["kind"] == "modified")
+    );
+}
+
+#[test]
+fn test_export_sarif() {
+    let long_body: String = (0..30)
+        .map(|i| format!("    let x{} = {};\n", i, i))
+        .collect();
+    let source = format!("fn small() {{}}\n\nfn long() {{\n{}}}\n", long_body);
+    let fixture = make_fixture(&[("src/lib.rs", &source)]);
+    let out_sarif = fixture.path().join("out.sarif");
+
+    let out = run_loc(&[
+        fixture.path().to_str().unwrap(),
+        "--max-function-lines",
+        "20",
+        "--warn-size",
+        "10",
+        "-e",
+        out_sarif.to_str().unwrap(),
+    ]);
+    assert_eq!(out.status.code(), Some(2), "gate should still fail the run");
+
+    let log: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&out_sarif).unwrap()).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    let long = results
+        .iter()
+        .find(|r| r["ruleId"] == "max-function-lines")
+        .expect("missing overlong function result");
+    assert_eq!(long["level"], "error");
+    let loc = &long["locations"][0]["physicalLocation"];
+    assert_eq!(loc["artifactLocation"]["uri"], "src/lib.rs");
+    assert_eq!(loc["region"]["startLine"], 3);
+
+    let size = results
+        .iter()
+        .find(|r| r["ruleId"] == "max-file-lines")
+        .expect("missing oversized file result");
+    assert_eq!(size["level"], "warning");
+}